@@ -0,0 +1,17 @@
+use wasmesh_proto::*;
+
+/// Asks `http://upstream` for the requested path, then echoes the request
+/// body with the upstream status.
+#[wasm_handler(0)]
+fn handle_http(ctx: Ctx, req: HttpRequest) -> Result<HttpResponse> {
+    let mut upstream = HttpRequest::new();
+    upstream.set_method(HttpMethod::GET);
+    upstream.set_url(format!("http://upstream{}", req.get_path()));
+    let upstream: HttpResponse = ctx.call_host(Method::from(VmMethod::V_HTTP), &upstream)?;
+
+    let mut resp = HttpResponse::new();
+    resp.set_status(200);
+    resp.headers.insert("x-upstream-status".to_string(), upstream.get_status().to_string());
+    resp.set_body(req.body.clone());
+    Ok(resp)
+}
@@ -0,0 +1,51 @@
+use std::error::Error;
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+use structopt::{clap::AppSettings, StructOpt};
+
+use wasmesh_ctl::plane::{self, DesiredState, Plane};
+use wasmesh_ctl::tls::RpcTls;
+
+/// Control plane pushing desired state to the pods subscribed with `--rpc`.
+#[derive(StructOpt, Debug)]
+#[structopt(global_settings = & [AppSettings::ColorAuto, AppSettings::ColoredHelp])]
+struct PlaneArgs {
+    /// RPC listening address pods subscribe to
+    #[structopt(long, default_value = "127.0.0.1:9091")]
+    rpc: String,
+    /// desired state file (TOML) with `services`, `limits` and `authz` like a pod config, reloaded when it changes
+    #[structopt(long, parse(from_os_str))]
+    state: PathBuf,
+    /// directory of the `.wasm` files pods fetch when a service references a module by digest (`sha256:<hex>`)
+    #[structopt(long, parse(from_os_str))]
+    modules: Option<PathBuf>,
+    /// how often the desired state file is checked for changes, in milliseconds
+    #[structopt(long, default_value = "1000")]
+    interval: u64,
+    /// CA directory like the one of `wasmesh-pod --tls-ca-dir`, serves mutual TLS to peers with a certificate of the CA
+    #[structopt(long = "tls-ca-dir", parse(from_os_str))]
+    tls_ca_dir: Option<PathBuf>,
+    /// identity of the control plane, `<name>.pem` and `<name>.key` of the CA directory
+    #[structopt(long = "tls-name", default_value = "wasmesh.plane")]
+    tls_name: String,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = PlaneArgs::from_args();
+    let state = DesiredState::load(&args.state)?;
+    println!("desired state {} loaded from {}", state.version(), args.state.display());
+    let tls = match &args.tls_ca_dir {
+        Some(dir) => Some(RpcTls::load(dir, &args.tls_name)?),
+        None => None,
+    };
+    let plane = Plane::new(state, args.modules);
+    let listener = TcpListener::bind(&args.rpc)?;
+    println!("Control plane listening on {}{}", listener.local_addr()?, if tls.is_some() { " (mTLS)" } else { "" });
+    let server = plane.clone();
+    thread::spawn(move || server.serve(listener, tls));
+    plane::watch(&plane, &args.state, Duration::from_millis(args.interval));
+    Ok(())
+}
@@ -0,0 +1,494 @@
+//! Headless commands, for scripts and CI.
+
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+use serde::Serialize;
+use structopt::{clap::AppSettings, StructOpt};
+
+use crate::client::*;
+use crate::plane::{plane_status, PlaneSummary};
+use crate::tls::RpcTls;
+
+/// Admin address used when no pod is given.
+pub const DEFAULT_POD: &str = "http://127.0.0.1:9190";
+
+/// How often `logs --follow` polls the pod.
+const FOLLOW_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(StructOpt, Debug)]
+#[structopt(name = "wasmesh-ctl", global_settings = & [AppSettings::VersionlessSubcommands, AppSettings::ColorAuto, AppSettings::ColoredHelp])]
+pub struct CtlArgs {
+    /// Admin address of a pod, repeatable; defaults to the comma separated $WASMESH_PODS, then http://127.0.0.1:9190.
+    /// Commands other than `pods ls` act on the first pod
+    #[structopt(long = "pod", short = "p", global = true, number_of_values = 1)]
+    pub pods: Vec<String>,
+    /// Bearer token of pods started with `--admin-token`
+    #[structopt(long, global = true, env = "WASMESH_ADMIN_TOKEN", hide_env_values = true)]
+    pub token: Option<String>,
+    /// CA directory of `https://` pods started with `--tls-ca-dir`
+    #[structopt(long = "tls-ca-dir", global = true, parse(from_os_str))]
+    pub tls_ca_dir: Option<PathBuf>,
+    /// Identity presented over mutual TLS, `<name>.pem` and `<name>.key` of the CA directory
+    #[structopt(long = "tls-name", global = true, default_value = "wasmesh.ctl")]
+    pub tls_name: String,
+    /// Print JSON instead of text, one object per line for `logs`
+    #[structopt(long, global = true)]
+    pub json: bool,
+    /// Opens the GUI when no command is given
+    #[structopt(subcommand)]
+    pub command: Option<Command>,
+}
+
+impl CtlArgs {
+    /// Returns the admin addresses of the pods to talk to.
+    pub fn pods(&self) -> Vec<String> {
+        if !self.pods.is_empty() {
+            return self.pods.clone();
+        }
+        let pods: Vec<String> = env::var("WASMESH_PODS")
+            .unwrap_or_default()
+            .split(',')
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty())
+            .collect();
+        if pods.is_empty() { vec![DEFAULT_POD.to_string()] } else { pods }
+    }
+
+    /// Loads the mutual TLS identity given by `--tls-ca-dir` and `--tls-name`.
+    pub fn tls(&self) -> Result<Option<RpcTls>> {
+        match &self.tls_ca_dir {
+            Some(dir) => RpcTls::load(dir, &self.tls_name).map(Some).map_err(Error::Usage),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the admin clients of the pods, with the token and TLS identity.
+    pub fn admin_clients(&self, tls: Option<&RpcTls>) -> Vec<(String, AdminClient)> {
+        self.pods()
+            .into_iter()
+            .map(|pod| {
+                let client = AdminClient::new(&pod).with_token(self.token.clone()).with_tls(tls);
+                (pod, client)
+            })
+            .collect()
+    }
+}
+
+#[derive(StructOpt, Debug, Clone, PartialEq)]
+pub enum Command {
+    /// Opens the GUI
+    Gui,
+    /// Pods
+    Pods(PodsCommand),
+    /// Service routes and weights
+    Routes(RoutesCommand),
+    /// Modules serving the versions of a service
+    Module(ModuleCommand),
+    /// Prints the counters of every version
+    Metrics,
+    /// Prints the pod log
+    Logs {
+        /// Keep polling for new records
+        #[structopt(long, short = "f")]
+        follow: bool,
+        /// Only print records after this sequence number
+        #[structopt(long, default_value = "0")]
+        after: u64,
+        /// Maximum number of records per request
+        #[structopt(long, default_value = "100")]
+        limit: usize,
+    },
+    /// Pod configuration
+    Config(ConfigCommand),
+    /// Control plane the pods subscribe to
+    Plane(PlaneCommand),
+}
+
+#[derive(StructOpt, Debug, Clone, PartialEq)]
+pub enum PodsCommand {
+    /// Lists the pods and whether they are reachable
+    Ls,
+}
+
+#[derive(StructOpt, Debug, Clone, PartialEq)]
+pub enum RoutesCommand {
+    /// Prints the routes of every service, or of one
+    Get {
+        service: Option<String>,
+    },
+    /// Sets the weights of versions, e.g. `routes set-weights orders v1=90 v2=10`
+    SetWeights {
+        service: String,
+        #[structopt(required = true)]
+        weights: Vec<String>,
+    },
+}
+
+#[derive(StructOpt, Debug, Clone, PartialEq)]
+pub enum ModuleCommand {
+    /// Lists the loaded modules
+    Ls,
+    /// Loads a module on the pod and serves a version with it
+    Deploy {
+        service: String,
+        version: String,
+        /// `sha256:<hex>` digest of a pushed module
+        wasm: String,
+        /// Weight of the version, keeps the current one when omitted
+        #[structopt(long)]
+        weight: Option<u32>,
+    },
+    /// Adds a local module to the pod's module store and prints its digest
+    Push {
+        #[structopt(parse(from_os_str))]
+        wasm: PathBuf,
+        /// Detached signature file pushed along, default to `<wasm>.sig` when it exists
+        #[structopt(long, parse(from_os_str))]
+        signature: Option<PathBuf>,
+    },
+    /// Serves a version with the module it had before the last deploy
+    Rollback {
+        service: String,
+        version: String,
+    },
+    /// Loads the module of a version again from disk
+    Reload {
+        service: String,
+        version: String,
+    },
+}
+
+#[derive(StructOpt, Debug, Clone, PartialEq)]
+pub enum ConfigCommand {
+    /// Re-reads the pod config file
+    Reload,
+}
+
+#[derive(StructOpt, Debug, Clone, PartialEq)]
+pub enum PlaneCommand {
+    /// Prints the desired state version and whether each pod runs it
+    Status {
+        /// RPC address of the control plane, dialed over mutual TLS with `--tls-ca-dir`
+        #[structopt(long, default_value = "127.0.0.1:9091")]
+        plane: String,
+    },
+}
+
+/// Reachability of a pod, printed by `pods ls`.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+struct PodStatus {
+    pod: String,
+    reachable: bool,
+    services: usize,
+    requests: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Runs a command against `pods`, writing its output to `out`.
+///
+/// `Command::Gui` is not handled here and does nothing.
+pub fn run<A: Admin>(pods: &[(String, A)], json: bool, tls: Option<&RpcTls>, command: &Command, out: &mut dyn Write) -> Result<()> {
+    let mut printer = Printer { json, out };
+    if let Command::Pods(PodsCommand::Ls) = command {
+        let status: Vec<PodStatus> = pods.iter().map(|(pod, admin)| pod_status(pod, admin)).collect();
+        return printer.pods(&status);
+    }
+    if let Command::Plane(PlaneCommand::Status { plane }) = command {
+        let status = plane_status(plane, tls).map_err(|e| Error::Transport(format!("{}: {}", plane, e)))?;
+        return printer.plane(&status);
+    }
+    let admin = match pods.first() {
+        Some((_, admin)) => admin,
+        None => return Err(Error::Usage("no pod given".to_string())),
+    };
+    match command {
+        Command::Gui | Command::Pods(_) | Command::Plane(_) => Ok(()),
+        Command::Routes(RoutesCommand::Get { service }) => {
+            let services = admin.routes()?;
+            match service {
+                Some(name) => printer.service(&find_service(services, name)?),
+                None => printer.services(&services),
+            }
+        }
+        Command::Routes(RoutesCommand::SetWeights { service, weights }) => {
+            let weights = parse_weights(weights)?;
+            printer.service(&find_service(admin.set_weights(service, &weights)?, service)?)
+        }
+        Command::Module(ModuleCommand::Ls) => printer.modules(&admin.modules()?),
+        Command::Module(ModuleCommand::Deploy { service, version, wasm, weight }) => {
+            printer.service(&find_service(admin.deploy(service, version, wasm, *weight)?, service)?)
+        }
+        Command::Module(ModuleCommand::Push { wasm, signature }) => {
+            let read = |path: &PathBuf| fs::read(path).map_err(|e| Error::Usage(format!("read {}: {}", path.display(), e)));
+            let pushed = admin.push_module(&read(wasm)?)?;
+            let signature = signature.clone().or_else(|| {
+                Some(PathBuf::from(format!("{}.sig", wasm.display()))).filter(|path| path.exists())
+            });
+            if let Some(path) = &signature {
+                admin.push_signature(&pushed.digest, &read(path)?)?;
+            }
+            printer.pushed(&pushed)
+        }
+        Command::Module(ModuleCommand::Rollback { service, version }) => {
+            printer.service(&find_service(admin.rollback(service, version)?, service)?)
+        }
+        Command::Module(ModuleCommand::Reload { service, version }) => {
+            printer.service(&find_service(admin.reload_module(service, version)?, service)?)
+        }
+        Command::Metrics => printer.metrics(&admin.metrics()?),
+        Command::Logs { follow, after, limit } => {
+            let (mut cursor, limit) = (*after, (*limit).max(1));
+            loop {
+                let entries = admin.logs(cursor, limit)?;
+                for entry in &entries {
+                    printer.log(entry)?;
+                    cursor = entry.seq;
+                }
+                // a full page means more records are waiting
+                if entries.len() < limit {
+                    if !*follow {
+                        return Ok(());
+                    }
+                    printer.flush()?;
+                    thread::sleep(FOLLOW_INTERVAL);
+                }
+            }
+        }
+        Command::Config(ConfigCommand::Reload) => printer.services(&admin.reload_config()?),
+    }
+}
+
+fn pod_status<A: Admin>(pod: &str, admin: &A) -> PodStatus {
+    match admin.routes() {
+        Ok(services) => PodStatus {
+            pod: pod.to_string(),
+            reachable: true,
+            services: services.len(),
+            requests: services.iter().flat_map(|s| &s.versions).map(|v| v.requests).sum(),
+            error: None,
+        },
+        Err(e) => PodStatus {
+            pod: pod.to_string(),
+            reachable: false,
+            services: 0,
+            requests: 0,
+            error: Some(e.to_string()),
+        },
+    }
+}
+
+fn find_service(services: Vec<ServiceStatus>, name: &str) -> Result<ServiceStatus> {
+    services.into_iter()
+            .find(|s| s.name == name)
+            .ok_or_else(|| Error::Usage(format!("no service {}", name)))
+}
+
+/// Parses `version=weight` pairs.
+fn parse_weights(pairs: &[String]) -> Result<HashMap<String, u32>> {
+    pairs.iter().map(|pair| {
+        let invalid = || Error::Usage(format!("invalid weight {:?}, expected version=weight", pair));
+        let (version, weight) = pair.split_once('=').ok_or_else(invalid)?;
+        let weight = weight.trim().parse().map_err(|_| invalid())?;
+        Ok((version.trim().to_string(), weight))
+    }).collect()
+}
+
+struct Printer<'a> {
+    json: bool,
+    out: &'a mut dyn Write,
+}
+
+impl Printer<'_> {
+    fn write(&mut self, text: &str) -> Result<()> {
+        writeln!(self.out, "{}", text).map_err(|e| Error::Output(e.to_string()))
+    }
+    fn flush(&mut self) -> Result<()> {
+        self.out.flush().map_err(|e| Error::Output(e.to_string()))
+    }
+    fn json<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let text = serde_json::to_string_pretty(value).map_err(|e| Error::Output(e.to_string()))?;
+        self.write(&text)
+    }
+
+    fn pods(&mut self, pods: &[PodStatus]) -> Result<()> {
+        if self.json {
+            return self.json(pods);
+        }
+        for p in pods {
+            let line = match &p.error {
+                None => format!("{}\tup\tservices {}\trequests {}", p.pod, p.services, p.requests),
+                Some(e) => format!("{}\tdown\t{}", p.pod, e),
+            };
+            self.write(&line)?;
+        }
+        Ok(())
+    }
+
+    fn plane(&mut self, plane: &PlaneSummary) -> Result<()> {
+        if self.json {
+            return self.json(plane);
+        }
+        self.write(&format!("desired version {}", plane.version))?;
+        for p in &plane.pods {
+            let state = match (p.connected, p.in_sync) {
+                (false, _) => "disconnected",
+                (true, true) => "in sync",
+                (true, false) => "behind",
+            };
+            let mut line = format!(
+                "{}\t{}\t{}\tversion {}\tservices {}\trequests {}\terrors {}",
+                p.pod_id, p.address, state, p.version, p.services, p.requests, p.errors,
+            );
+            if let Some(e) = &p.error {
+                line.push_str(&format!("\trejected: {}", e));
+            }
+            self.write(&line)?;
+        }
+        Ok(())
+    }
+
+    fn pushed(&mut self, pushed: &PushedModule) -> Result<()> {
+        if self.json {
+            return self.json(pushed);
+        }
+        self.write(&pushed.digest)
+    }
+
+    fn services(&mut self, services: &[ServiceStatus]) -> Result<()> {
+        if self.json {
+            return self.json(services);
+        }
+        for s in services {
+            self.service_text(s)?;
+        }
+        Ok(())
+    }
+
+    fn service(&mut self, service: &ServiceStatus) -> Result<()> {
+        if self.json {
+            return self.json(service);
+        }
+        self.service_text(service)
+    }
+
+    fn service_text(&mut self, s: &ServiceStatus) -> Result<()> {
+        self.write(&format!("{}\t{}", s.name, s.path))?;
+        for v in &s.versions {
+            self.write(&format!(
+                "  {}\tweight {}\trequests {}\terrors {}\tmean {} µs\t{}",
+                v.name, v.weight, v.requests, v.errors, v.mean_latency_micros, v.wasm,
+            ))?;
+        }
+        if let Some(m) = &s.mirror {
+            self.write(&format!(
+                "  mirror\t{}%\trequests {}\terrors {}\tdiffs {}\tdropped {}\t{}",
+                m.percent, m.requests, m.errors, m.diffs, m.dropped, m.wasm,
+            ))?;
+        }
+        Ok(())
+    }
+
+    fn modules(&mut self, modules: &[ModuleInfo]) -> Result<()> {
+        if self.json {
+            return self.json(modules);
+        }
+        for m in modules {
+            let sha256 = m.sha256.as_deref().map_or("-", |d| &d[..d.len().min(12)]);
+            let signed = m.signature.as_ref().and_then(|s| s.key.as_deref()).unwrap_or("-");
+            self.write(&format!("{}/{}\t{}\t{}\t{}\t{}", m.service, m.version, m.module, sha256, signed, m.wasm))?;
+        }
+        Ok(())
+    }
+
+    fn metrics(&mut self, metrics: &[VersionMetrics]) -> Result<()> {
+        if self.json {
+            return self.json(metrics);
+        }
+        for m in metrics {
+            self.write(&format!(
+                "{}/{}\trequests {}\terrors {}\tmean {} µs",
+                m.service, m.version, m.requests, m.errors, m.mean_latency_micros,
+            ))?;
+        }
+        Ok(())
+    }
+
+    fn log(&mut self, entry: &LogEntry) -> Result<()> {
+        if self.json {
+            let text = serde_json::to_string(entry).map_err(|e| Error::Output(e.to_string()))?;
+            return self.write(&text);
+        }
+        self.write(&format!("#{} {} [{}] {}", entry.seq, entry.time_ms, entry.level, entry.message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+
+    use crate::fake::FakePod;
+    use crate::plane::{DesiredState, Plane};
+    use crate::tls::issue_ca;
+
+    use super::*;
+
+    fn run_fake(args: &[&str]) -> (Result<()>, String) {
+        let args = CtlArgs::from_iter_safe(std::iter::once("wasmesh-ctl").chain(args.iter().copied())).unwrap();
+        let pods = vec![("local".to_string(), FakePod::new())];
+        let mut out = Vec::new();
+        let r = run(&pods, args.json, None, args.command.as_ref().unwrap(), &mut out);
+        (r, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn commands_print_text_and_json() {
+        let (r, out) = run_fake(&["routes", "set-weights", "orders", "v1=70", "v2=30"]);
+        assert_eq!(r, Ok(()));
+        assert!(out.starts_with("orders\t/orders*\n  v1\tweight 70\t"), "{}", out);
+
+        let (r, out) = run_fake(&["--pod", "http://10.0.0.1:9190", "--json", "pods", "ls"]);
+        assert_eq!(r, Ok(()));
+        let pods: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(pods[0]["reachable"], true);
+        assert_eq!(pods[0]["services"], 1);
+
+        let (_, out) = run_fake(&["logs", "--json", "--after", "1"]);
+        assert_eq!(out.lines().count(), 2);
+
+        let (r, _) = run_fake(&["routes", "set-weights", "orders", "v1"]);
+        assert_eq!(r, Err(Error::Usage("invalid weight \"v1\", expected version=weight".to_string())));
+        let (r, _) = run_fake(&["module", "rollback", "orders", "v2"]);
+        assert_eq!(r.unwrap_err().to_string(), "422 nothing to roll back for orders/v2");
+    }
+
+    #[test]
+    fn tls_options_are_global() {
+        let dir = tempfile::tempdir().unwrap();
+        issue_ca(dir.path(), &["wasmesh.plane", "ops.ctl"]);
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let plane = Plane::new(DesiredState::default(), None);
+        let tls = RpcTls::load(dir.path(), "wasmesh.plane").unwrap();
+        std::thread::spawn(move || plane.serve(listener, Some(tls)));
+
+        let ca_dir = dir.path().to_str().unwrap();
+        let args = CtlArgs::from_iter_safe(["wasmesh-ctl", "plane", "status", "--plane", &addr, "--tls-ca-dir", ca_dir, "--tls-name", "ops.ctl"]).unwrap();
+        assert_eq!((args.tls_ca_dir.as_deref(), args.tls_name.as_str()), (Some(dir.path()), "ops.ctl"));
+        let tls = args.tls().unwrap();
+        let mut out = Vec::new();
+        let pods = args.admin_clients(tls.as_ref());
+        assert_eq!(run(&pods, true, tls.as_ref(), args.command.as_ref().unwrap(), &mut out), Ok(()));
+        assert_eq!(serde_json::from_slice::<serde_json::Value>(&out).unwrap()["pods"], serde_json::json!([]));
+
+        let args = CtlArgs::from_iter_safe(["wasmesh-ctl", "--tls-ca-dir", ca_dir, "pods", "ls"]).unwrap();
+        assert!(matches!(args.tls(), Err(Error::Usage(e)) if e.contains("wasmesh.ctl.pem")));
+        assert!(CtlArgs::from_iter_safe(["wasmesh-ctl", "pods", "ls"]).unwrap().tls().unwrap().is_none());
+    }
+}
@@ -0,0 +1,284 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde::de::DeserializeOwned;
+
+use crate::tls::RpcTls;
+
+/// Status of a service, as reported by `GET /routes`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ServiceStatus {
+    pub name: String,
+    pub path: String,
+    pub versions: Vec<VersionStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mirror: Option<MirrorStatus>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct VersionStatus {
+    pub name: String,
+    pub wasm: String,
+    pub weight: u32,
+    pub requests: u64,
+    pub errors: u64,
+    pub mean_latency_micros: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MirrorStatus {
+    pub wasm: String,
+    pub percent: f64,
+    pub requests: u64,
+    pub errors: u64,
+    pub diffs: u64,
+    /// requests not mirrored because too many mirrors were running
+    #[serde(default)]
+    pub dropped: u64,
+    pub mean_latency_micros: u64,
+}
+
+/// A loaded module, as reported by `GET /modules`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ModuleInfo {
+    pub service: String,
+    pub version: String,
+    pub module: String,
+    pub wasm: String,
+    /// SHA-256 of the module file, hex encoded
+    #[serde(default)]
+    pub sha256: Option<String>,
+    #[serde(default)]
+    pub signature: Option<SignatureStatus>,
+}
+
+/// Signature check of a loaded module.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SignatureStatus {
+    /// `verified`, or `not_required` when the pod trusts no key
+    pub status: String,
+    /// name of the trusted key the module is signed with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+}
+
+/// A module added to the pod's module store, as reported by `POST /modules`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PushedModule {
+    /// `sha256:<hex>`, deployable like a path
+    pub digest: String,
+}
+
+/// Counters of a version, as reported by `GET /metrics`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct VersionMetrics {
+    pub service: String,
+    pub version: String,
+    pub requests: u64,
+    pub errors: u64,
+    pub mean_latency_micros: u64,
+}
+
+/// A pod log record, as reported by `GET /logs`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LogEntry {
+    pub seq: u64,
+    pub time_ms: u64,
+    pub level: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// the pod answered with an error status
+    Status { status: u16, message: String },
+    /// the pod could not be reached
+    Transport(String),
+    /// the pod answered with an unexpected body
+    Decode(String),
+    /// the request was invalid before reaching a pod
+    Usage(String),
+    /// the result could not be written out
+    Output(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Status { status, message } => write!(f, "{} {}", status, message),
+            Error::Transport(e) => write!(f, "transport error: {}", e),
+            Error::Decode(e) => write!(f, "invalid response: {}", e),
+            Error::Usage(e) => f.write_str(e),
+            Error::Output(e) => write!(f, "write output: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Operations of the pod admin API.
+pub trait Admin {
+    fn routes(&self) -> Result<Vec<ServiceStatus>>;
+    fn modules(&self) -> Result<Vec<ModuleInfo>>;
+    fn metrics(&self) -> Result<Vec<VersionMetrics>>;
+    /// Sets the weights of the given versions, the others keep theirs.
+    fn set_weights(&self, service: &str, weights: &HashMap<String, u32>) -> Result<Vec<ServiceStatus>>;
+    /// Points a version at another module, adding the version when it is new.
+    fn deploy(&self, service: &str, version: &str, wasm: &str, weight: Option<u32>) -> Result<Vec<ServiceStatus>>;
+    /// Undoes the last deploy of a version.
+    fn rollback(&self, service: &str, version: &str) -> Result<Vec<ServiceStatus>>;
+    /// Adds a module to the pod's module store, to deploy it by digest.
+    fn push_module(&self, wasm: &[u8]) -> Result<PushedModule>;
+    /// Stores the detached signature of a module pushed before.
+    fn push_signature(&self, digest: &str, signature: &[u8]) -> Result<PushedModule>;
+    fn reload_module(&self, service: &str, version: &str) -> Result<Vec<ServiceStatus>>;
+    fn reload_config(&self) -> Result<Vec<ServiceStatus>>;
+    /// Returns up to `limit` log records after the sequence number `after`.
+    fn logs(&self, after: u64, limit: usize) -> Result<Vec<LogEntry>>;
+}
+
+/// Timeout of an admin request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// HTTP client of a pod's admin listener, e.g. `http://127.0.0.1:9190`.
+#[derive(Clone)]
+pub struct AdminClient {
+    base: String,
+    agent: ureq::Agent,
+    token: Option<String>,
+}
+
+impl AdminClient {
+    pub fn new(base: &str) -> Self {
+        AdminClient {
+            base: base.trim_end_matches('/').to_string(),
+            agent: ureq::builder().timeout(REQUEST_TIMEOUT).build(),
+            token: None,
+        }
+    }
+
+    /// Connects over mutual TLS, for `https://` pods started with `--tls-ca-dir`.
+    pub fn with_tls(mut self, tls: Option<&RpcTls>) -> Self {
+        if let Some(tls) = tls {
+            self.agent = ureq::builder().timeout(REQUEST_TIMEOUT).tls_config(tls.client_config()).build();
+        }
+        self
+    }
+
+    /// Sends a bearer token, for pods started with `--admin-token`.
+    pub fn with_token(mut self, token: Option<String>) -> Self {
+        self.token = token;
+        self
+    }
+
+    pub fn base(&self) -> &str {
+        &self.base
+    }
+
+    fn request(&self, method: &str, path: &str) -> ureq::Request {
+        let req = self.agent.request(method, &format!("{}{}", self.base, path));
+        match &self.token {
+            Some(token) => req.set("authorization", &format!("Bearer {}", token)),
+            None => req,
+        }
+    }
+
+    fn call<T: DeserializeOwned>(&self, method: &str, path: &str, body: Option<serde_json::Value>) -> Result<T> {
+        let req = self.request(method, path);
+        Self::decode(match body {
+            Some(body) => req.send_json(body),
+            None => req.call(),
+        })
+    }
+
+    fn decode<T: DeserializeOwned>(r: std::result::Result<ureq::Response, ureq::Error>) -> Result<T> {
+        match r {
+            Ok(resp) => resp.into_json().map_err(|e| Error::Decode(e.to_string())),
+            Err(ureq::Error::Status(status, resp)) => {
+                let text = resp.into_string().unwrap_or_default();
+                let message = serde_json::from_str::<serde_json::Value>(&text).ok()
+                    .and_then(|v| v.get("error").and_then(|e| e.as_str()).map(str::to_string))
+                    .unwrap_or(text);
+                Err(Error::Status { status, message })
+            }
+            Err(ureq::Error::Transport(e)) => Err(Error::Transport(e.to_string())),
+        }
+    }
+}
+
+impl Admin for AdminClient {
+    fn routes(&self) -> Result<Vec<ServiceStatus>> {
+        self.call("GET", "/routes", None)
+    }
+    fn modules(&self) -> Result<Vec<ModuleInfo>> {
+        self.call("GET", "/modules", None)
+    }
+    fn metrics(&self) -> Result<Vec<VersionMetrics>> {
+        self.call("GET", "/metrics", None)
+    }
+    fn set_weights(&self, service: &str, weights: &HashMap<String, u32>) -> Result<Vec<ServiceStatus>> {
+        self.call("PUT", &format!("/routes/{}/weights", service), Some(serde_json::json!(weights)))
+    }
+    fn deploy(&self, service: &str, version: &str, wasm: &str, weight: Option<u32>) -> Result<Vec<ServiceStatus>> {
+        let body = serde_json::json!({ "wasm": wasm, "weight": weight });
+        self.call("PUT", &format!("/routes/{}/versions/{}", service, version), Some(body))
+    }
+    fn rollback(&self, service: &str, version: &str) -> Result<Vec<ServiceStatus>> {
+        self.call("POST", &format!("/routes/{}/versions/{}/rollback", service, version), None)
+    }
+    fn push_module(&self, wasm: &[u8]) -> Result<PushedModule> {
+        Self::decode(self.request("POST", "/modules").set("content-type", "application/wasm").send_bytes(wasm))
+    }
+    fn push_signature(&self, digest: &str, signature: &[u8]) -> Result<PushedModule> {
+        Self::decode(self.request("PUT", &format!("/modules/{}/signature", digest)).send_bytes(signature))
+    }
+    fn reload_module(&self, service: &str, version: &str) -> Result<Vec<ServiceStatus>> {
+        self.call("POST", &format!("/routes/{}/versions/{}/reload", service, version), None)
+    }
+    fn reload_config(&self) -> Result<Vec<ServiceStatus>> {
+        self.call("POST", "/config/reload", None)
+    }
+    fn logs(&self, after: u64, limit: usize) -> Result<Vec<LogEntry>> {
+        self.call("GET", &format!("/logs?after={}&limit={}", after, limit), None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    use crate::tls::issue_ca;
+
+    use super::*;
+
+    #[test]
+    fn pods_are_called_over_mutual_tls() {
+        let dir = tempfile::tempdir().unwrap();
+        issue_ca(dir.path(), &["shop.orders", "wasmesh.ctl"]);
+        let pod = RpcTls::load(dir.path(), "shop.orders").unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("https://{}", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            for socket in listener.incoming() {
+                let mut conn = BufReader::new(pod.accept(socket.unwrap()).unwrap());
+                let mut line = String::new();
+                // a client without a certificate fails the handshake here
+                while conn.read_line(&mut line).is_ok_and(|n| n > 2) {
+                    line.clear();
+                }
+                let _ = conn.get_mut().write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\n[]");
+                let _ = conn.get_mut().flush();
+            }
+        });
+
+        let ctl = RpcTls::load(dir.path(), "wasmesh.ctl").unwrap();
+        assert_eq!(AdminClient::new(&base).with_tls(Some(&ctl)).routes(), Ok(vec![]));
+        assert!(matches!(AdminClient::new(&base).routes(), Err(Error::Transport(_))));
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+
+use crate::client::{Admin, LogEntry, ModuleInfo, ServiceStatus};
+
+/// Log records kept per console.
+const LOG_CAPACITY: usize = 500;
+
+/// A pod the console controls.
+pub struct Pod<A> {
+    pub name: String,
+    pub admin: A,
+}
+
+/// What the operator asked for.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    SelectPod(usize),
+    /// reload routes and modules of the selected pod
+    Refresh,
+    SetWeight { service: String, version: String, weight: u32 },
+    Deploy { service: String, version: String, wasm: String },
+    Rollback { service: String, version: String },
+    ReloadModule { service: String, version: String },
+    ReloadConfig,
+    /// fetch log records newer than the ones shown
+    PollLogs,
+}
+
+/// State of the control tool, independent of how it is displayed.
+pub struct Console<A> {
+    pods: Vec<Pod<A>>,
+    selected: Option<usize>,
+    services: Vec<ServiceStatus>,
+    modules: Vec<ModuleInfo>,
+    logs: Vec<LogEntry>,
+    log_cursor: u64,
+    notice: Option<String>,
+    error: Option<String>,
+}
+
+impl<A> Default for Console<A> {
+    fn default() -> Self {
+        Console {
+            pods: Vec::new(),
+            selected: None,
+            services: Vec::new(),
+            modules: Vec::new(),
+            logs: Vec::new(),
+            log_cursor: 0,
+            notice: None,
+            error: None,
+        }
+    }
+}
+
+impl<A: Admin> Console<A> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a pod, selecting it when it is the first one.
+    pub fn add_pod(&mut self, name: &str, admin: A) {
+        self.pods.push(Pod { name: name.to_string(), admin });
+        if self.selected.is_none() {
+            self.apply(Action::SelectPod(0));
+        }
+    }
+
+    pub fn pods(&self) -> &[Pod<A>] {
+        &self.pods
+    }
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+    pub fn services(&self) -> &[ServiceStatus] {
+        &self.services
+    }
+    pub fn modules(&self) -> &[ModuleInfo] {
+        &self.modules
+    }
+    pub fn logs(&self) -> &[LogEntry] {
+        &self.logs
+    }
+    /// Outcome of the last successful change.
+    pub fn notice(&self) -> Option<&str> {
+        self.notice.as_deref()
+    }
+    /// Error of the last action.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    pub fn apply(&mut self, action: Action) {
+        self.error = None;
+        if let Action::SelectPod(index) = action {
+            if index >= self.pods.len() {
+                self.error = Some(format!("no pod #{}", index));
+                return;
+            }
+            self.selected = Some(index);
+            self.services.clear();
+            self.modules.clear();
+            self.logs.clear();
+            self.log_cursor = 0;
+            self.notice = None;
+            self.refresh();
+            return;
+        }
+        let admin = match self.selected.and_then(|i| self.pods.get(i)) {
+            Some(pod) => &pod.admin,
+            None => {
+                self.error = Some("no pod selected".to_string());
+                return;
+            }
+        };
+        let r = match &action {
+            Action::SelectPod(_) => unreachable!(),
+            Action::Refresh => {
+                self.refresh();
+                return;
+            }
+            Action::PollLogs => match admin.logs(self.log_cursor, LOG_CAPACITY) {
+                Ok(entries) => {
+                    self.push_logs(entries);
+                    return;
+                }
+                Err(e) => Err(e),
+            },
+            Action::SetWeight { service, version, weight } => {
+                let weights: HashMap<String, u32> = [(version.clone(), *weight)].into_iter().collect();
+                admin.set_weights(service, &weights).map(|s| (s, format!("{}/{} weight set to {}", service, version, weight)))
+            }
+            Action::Deploy { service, version, wasm } => {
+                admin.deploy(service, version, wasm, None).map(|s| (s, format!("deployed {} to {}/{}", wasm, service, version)))
+            }
+            Action::Rollback { service, version } => {
+                admin.rollback(service, version).map(|s| (s, format!("rolled back {}/{}", service, version)))
+            }
+            Action::ReloadModule { service, version } => {
+                admin.reload_module(service, version).map(|s| (s, format!("reloaded {}/{}", service, version)))
+            }
+            Action::ReloadConfig => admin.reload_config().map(|s| (s, "config reloaded".to_string())),
+        };
+        match r {
+            Ok((services, notice)) => {
+                self.services = services;
+                self.notice = Some(notice);
+                if !matches!(action, Action::SetWeight { .. }) {
+                    self.refresh_modules();
+                }
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+    }
+
+    fn refresh(&mut self) {
+        let admin = match self.selected.and_then(|i| self.pods.get(i)) {
+            Some(pod) => &pod.admin,
+            None => return,
+        };
+        match admin.routes() {
+            Ok(services) => self.services = services,
+            Err(e) => return self.error = Some(e.to_string()),
+        }
+        self.refresh_modules();
+    }
+
+    fn refresh_modules(&mut self) {
+        if let Some(pod) = self.selected.and_then(|i| self.pods.get(i)) {
+            match pod.admin.modules() {
+                Ok(modules) => self.modules = modules,
+                Err(e) => self.error = Some(e.to_string()),
+            }
+        }
+    }
+
+    fn push_logs(&mut self, entries: Vec<LogEntry>) {
+        if let Some(last) = entries.last() {
+            self.log_cursor = last.seq;
+        }
+        self.logs.extend(entries);
+        if self.logs.len() > LOG_CAPACITY {
+            self.logs.drain(..self.logs.len() - LOG_CAPACITY);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fake::FakePod;
+
+    use super::*;
+
+    #[test]
+    fn console_drives_pod() {
+        let mut console = Console::new();
+        console.add_pod("local", FakePod::new());
+        assert_eq!(console.selected(), Some(0));
+        assert_eq!(console.services()[0].versions.len(), 2);
+        assert_eq!(console.modules()[1].module, "orders-v2");
+
+        console.apply(Action::SetWeight { service: "orders".to_string(), version: "v2".to_string(), weight: 5 });
+        assert_eq!(console.services()[0].versions[1].weight, 5);
+        assert_eq!(console.notice(), Some("orders/v2 weight set to 5"));
+
+        console.apply(Action::Deploy { service: "orders".to_string(), version: "v3".to_string(), wasm: "x.wasm".to_string() });
+        assert_eq!(console.error(), Some("422 load orders: no such file"));
+
+        console.apply(Action::PollLogs);
+        console.apply(Action::PollLogs);
+        assert_eq!(console.logs().iter().map(|e| e.seq).collect::<Vec<_>>(), [1, 2, 3]);
+
+        console.apply(Action::SelectPod(3));
+        assert_eq!(console.error(), Some("no pod #3"));
+        assert_eq!(console.selected(), Some(0));
+    }
+}
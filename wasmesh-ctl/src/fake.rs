@@ -0,0 +1,86 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use sha2::{Digest, Sha256};
+
+use crate::client::*;
+
+/// Admin API of a pod held in memory.
+#[derive(Default)]
+pub(crate) struct FakePod {
+    services: RefCell<Vec<ServiceStatus>>,
+    logs: Vec<LogEntry>,
+}
+
+impl FakePod {
+    pub(crate) fn new() -> Self {
+        let version = |name: &str, weight| VersionStatus {
+            name: name.to_string(),
+            wasm: format!("orders-{}.wasm", name),
+            weight,
+            requests: 0,
+            errors: 0,
+            mean_latency_micros: 0,
+        };
+        let log = |seq| LogEntry { seq, time_ms: 0, level: "info".to_string(), message: format!("#{}", seq) };
+        FakePod {
+            services: RefCell::new(vec![ServiceStatus {
+                name: "orders".to_string(),
+                path: "/orders*".to_string(),
+                versions: vec![version("v1", 100), version("v2", 0)],
+                mirror: None,
+            }]),
+            logs: (1..=3).map(log).collect(),
+        }
+    }
+}
+
+impl Admin for FakePod {
+    fn routes(&self) -> Result<Vec<ServiceStatus>> {
+        Ok(self.services.borrow().clone())
+    }
+    fn modules(&self) -> Result<Vec<ModuleInfo>> {
+        Ok(self.services.borrow().iter().flat_map(|s| s.versions.iter().map(move |v| ModuleInfo {
+            service: s.name.clone(),
+            version: v.name.clone(),
+            module: v.wasm.trim_end_matches(".wasm").to_string(),
+            wasm: v.wasm.clone(),
+            sha256: None,
+            signature: None,
+        })).collect())
+    }
+    fn metrics(&self) -> Result<Vec<VersionMetrics>> {
+        Ok(vec![])
+    }
+    fn set_weights(&self, service: &str, weights: &HashMap<String, u32>) -> Result<Vec<ServiceStatus>> {
+        for s in self.services.borrow_mut().iter_mut().filter(|s| s.name == service) {
+            for v in s.versions.iter_mut() {
+                if let Some(w) = weights.get(&v.name) {
+                    v.weight = *w;
+                }
+            }
+        }
+        self.routes()
+    }
+    fn deploy(&self, service: &str, _version: &str, _wasm: &str, _weight: Option<u32>) -> Result<Vec<ServiceStatus>> {
+        Err(Error::Status { status: 422, message: format!("load {}: no such file", service) })
+    }
+    fn rollback(&self, service: &str, version: &str) -> Result<Vec<ServiceStatus>> {
+        Err(Error::Status { status: 422, message: format!("nothing to roll back for {}/{}", service, version) })
+    }
+    fn push_module(&self, wasm: &[u8]) -> Result<PushedModule> {
+        Ok(PushedModule { digest: format!("sha256:{:x}", Sha256::digest(wasm)) })
+    }
+    fn push_signature(&self, digest: &str, _signature: &[u8]) -> Result<PushedModule> {
+        Ok(PushedModule { digest: digest.to_string() })
+    }
+    fn reload_module(&self, _service: &str, _version: &str) -> Result<Vec<ServiceStatus>> {
+        self.routes()
+    }
+    fn reload_config(&self) -> Result<Vec<ServiceStatus>> {
+        self.routes()
+    }
+    fn logs(&self, after: u64, limit: usize) -> Result<Vec<LogEntry>> {
+        Ok(self.logs.iter().filter(|e| e.seq > after).take(limit).cloned().collect())
+    }
+}
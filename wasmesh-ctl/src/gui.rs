@@ -0,0 +1,301 @@
+use std::time::Duration;
+
+use iced::{
+    alignment, Application, button, Button, Checkbox, Color, Column, Command, Container, Element,
+    executor, Length, Row, scrollable, Scrollable, Settings, Space, Subscription, Text,
+    text_input, TextInput,
+};
+
+use wasmesh_ctl::{Action, AdminClient, Console};
+
+/// Opens the console window on the pods, by their admin addresses.
+pub fn run(pods: Vec<(String, AdminClient)>) -> iced::Result {
+    Ctl::run(Settings::with_flags(pods))
+}
+
+struct Ctl {
+    console: Console<AdminClient>,
+    tail: bool,
+    pod_buttons: Vec<button::State>,
+    refresh_button: button::State,
+    reload_config_button: button::State,
+    versions: Vec<VersionRow>,
+    deploy: DeployForm,
+    scroll: scrollable::State,
+}
+
+/// Widgets of a service version.
+struct VersionRow {
+    service: String,
+    version: String,
+    weight: String,
+    weight_input: text_input::State,
+    set_button: button::State,
+    reload_button: button::State,
+    rollback_button: button::State,
+}
+
+#[derive(Default)]
+struct DeployForm {
+    service: String,
+    version: String,
+    wasm: String,
+    service_input: text_input::State,
+    version_input: text_input::State,
+    wasm_input: text_input::State,
+    button: button::State,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    SelectPod(usize),
+    Refresh,
+    ReloadConfig,
+    TailToggled(bool),
+    Tick,
+    WeightChanged(usize, String),
+    SetWeight(usize),
+    ReloadModule(usize),
+    Rollback(usize),
+    DeployServiceChanged(String),
+    DeployVersionChanged(String),
+    DeployWasmChanged(String),
+    Deploy,
+}
+
+impl Application for Ctl {
+    type Executor = executor::Default;
+    type Message = Message;
+    type Flags = Vec<(String, AdminClient)>;
+
+    fn new(pods: Vec<(String, AdminClient)>) -> (Ctl, Command<Message>) {
+        let mut console = Console::new();
+        for (pod, client) in &pods {
+            console.add_pod(pod, client.clone());
+        }
+        let mut ctl = Ctl {
+            console,
+            tail: false,
+            pod_buttons: pods.iter().map(|_| button::State::new()).collect(),
+            refresh_button: button::State::new(),
+            reload_config_button: button::State::new(),
+            versions: Vec::new(),
+            deploy: DeployForm::default(),
+            scroll: scrollable::State::new(),
+        };
+        ctl.sync_versions();
+        (ctl, Command::none())
+    }
+
+    fn title(&self) -> String {
+        match self.console.selected().and_then(|i| self.console.pods().get(i)) {
+            Some(pod) => format!("{} - wasmesh-ctl", pod.name),
+            None => "wasmesh-ctl".to_string(),
+        }
+    }
+
+    fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::SelectPod(index) => self.console.apply(Action::SelectPod(index)),
+            Message::Refresh => self.console.apply(Action::Refresh),
+            Message::ReloadConfig => self.console.apply(Action::ReloadConfig),
+            Message::TailToggled(tail) => {
+                self.tail = tail;
+                if tail {
+                    self.console.apply(Action::PollLogs);
+                }
+            }
+            Message::Tick => self.console.apply(Action::PollLogs),
+            Message::WeightChanged(i, weight) => self.versions[i].weight = weight,
+            Message::SetWeight(i) => {
+                let row = &self.versions[i];
+                match row.weight.trim().parse() {
+                    Ok(weight) => self.console.apply(Action::SetWeight {
+                        service: row.service.clone(),
+                        version: row.version.clone(),
+                        weight,
+                    }),
+                    Err(_) => self.console.apply(Action::Refresh),
+                }
+            }
+            Message::ReloadModule(i) => {
+                let row = &self.versions[i];
+                self.console.apply(Action::ReloadModule { service: row.service.clone(), version: row.version.clone() })
+            }
+            Message::Rollback(i) => {
+                let row = &self.versions[i];
+                self.console.apply(Action::Rollback { service: row.service.clone(), version: row.version.clone() })
+            }
+            Message::DeployServiceChanged(v) => self.deploy.service = v,
+            Message::DeployVersionChanged(v) => self.deploy.version = v,
+            Message::DeployWasmChanged(v) => self.deploy.wasm = v,
+            Message::Deploy => self.console.apply(Action::Deploy {
+                service: self.deploy.service.trim().to_string(),
+                version: self.deploy.version.trim().to_string(),
+                wasm: self.deploy.wasm.trim().to_string(),
+            }),
+        }
+        self.sync_versions();
+        Command::none()
+    }
+
+    fn subscription(&self) -> Subscription<Message> {
+        if self.tail {
+            iced::time::every(Duration::from_secs(2)).map(|_| Message::Tick)
+        } else {
+            Subscription::none()
+        }
+    }
+
+    fn view(&mut self) -> Element<Message> {
+        let Ctl { console, tail, pod_buttons, refresh_button, reload_config_button, versions, deploy, scroll } = self;
+
+        let mut pods = Row::new().spacing(10);
+        for (i, (pod, state)) in console.pods().iter().zip(pod_buttons.iter_mut()).enumerate() {
+            let style = if console.selected() == Some(i) { style::Button::Primary } else { style::Button::Secondary };
+            pods = pods.push(button(state, &pod.name).on_press(Message::SelectPod(i)).style(style));
+        }
+
+        let controls = Row::new()
+            .spacing(10)
+            .push(button(refresh_button, "Refresh").on_press(Message::Refresh).style(style::Button::Primary))
+            .push(button(reload_config_button, "Reload config").on_press(Message::ReloadConfig).style(style::Button::Secondary))
+            .push(Space::with_width(Length::Fill))
+            .push(Checkbox::new(*tail, "Tail logs", Message::TailToggled));
+
+        let mut content = Column::new().spacing(20).padding(20).push(pods).push(controls);
+        if let Some(error) = console.error() {
+            content = content.push(Text::new(error).color(Color::from_rgb(0.8, 0.1, 0.1)));
+        } else if let Some(notice) = console.notice() {
+            content = content.push(Text::new(notice).color(Color::from_rgb(0.1, 0.5, 0.1)));
+        }
+
+        content = content.push(Text::new("Routes").size(30));
+        let mut rows = versions.iter_mut().enumerate().peekable();
+        for service in console.services() {
+            content = content.push(Text::new(format!("{}  {}", service.name, service.path)).size(22));
+            while let Some((i, row)) = rows.next_if(|(_, row)| row.service == service.name) {
+                let status = service.versions.iter().find(|v| v.name == row.version);
+                let counters = status.map_or_else(String::new, |v| format!(
+                    "{}  requests {}  errors {}  mean {} µs", v.wasm, v.requests, v.errors, v.mean_latency_micros,
+                ));
+                content = content.push(
+                    Row::new()
+                        .spacing(10)
+                        .align_items(alignment::Alignment::Center)
+                        .push(Text::new(&row.version).width(Length::Units(80)))
+                        .push(Text::new(counters).width(Length::Fill))
+                        .push(TextInput::new(&mut row.weight_input, "weight", &row.weight, move |w| Message::WeightChanged(i, w))
+                            .padding(8)
+                            .width(Length::Units(70)))
+                        .push(button(&mut row.set_button, "Set weight").on_press(Message::SetWeight(i)).style(style::Button::Primary))
+                        .push(button(&mut row.reload_button, "Reload").on_press(Message::ReloadModule(i)).style(style::Button::Secondary))
+                        .push(button(&mut row.rollback_button, "Rollback").on_press(Message::Rollback(i)).style(style::Button::Secondary)),
+                );
+            }
+            if let Some(m) = &service.mirror {
+                content = content.push(Text::new(format!(
+                    "mirror {} ({}%)  requests {}  diffs {}", m.wasm, m.percent, m.requests, m.diffs,
+                )));
+            }
+        }
+
+        content = content
+            .push(Text::new("Deploy").size(30))
+            .push(
+                Row::new()
+                    .spacing(10)
+                    .push(TextInput::new(&mut deploy.service_input, "service", &deploy.service, Message::DeployServiceChanged).padding(8))
+                    .push(TextInput::new(&mut deploy.version_input, "version", &deploy.version, Message::DeployVersionChanged).padding(8))
+                    .push(TextInput::new(&mut deploy.wasm_input, "sha256:<hex> of a pushed module", &deploy.wasm, Message::DeployWasmChanged).padding(8))
+                    .push(button(&mut deploy.button, "Deploy").on_press(Message::Deploy).style(style::Button::Primary)),
+            );
+
+        content = content.push(Text::new("Modules").size(30));
+        for m in console.modules() {
+            let sha256 = m.sha256.as_deref().map_or("-", |d| &d[..d.len().min(12)]);
+            let signed = m.signature.as_ref().and_then(|s| s.key.as_deref()).unwrap_or("-");
+            content = content.push(Text::new(format!("{}/{}  {}  {}  {}  {}", m.service, m.version, m.module, sha256, signed, m.wasm)));
+        }
+
+        if *tail {
+            content = content.push(Text::new("Logs").size(30));
+            let logs = console.logs();
+            for entry in &logs[logs.len().saturating_sub(50)..] {
+                content = content.push(Text::new(format!("#{} [{}] {}", entry.seq, entry.level, entry.message)).size(16));
+            }
+        }
+
+        let scrollable = Scrollable::new(scroll).push(Container::new(content).width(Length::Fill));
+        Container::new(scrollable).height(Length::Fill).into()
+    }
+}
+
+impl Ctl {
+    /// Keeps one row of widgets per version shown, preserving typed weights.
+    fn sync_versions(&mut self) {
+        let mut old = std::mem::take(&mut self.versions);
+        for service in self.console.services() {
+            for v in &service.versions {
+                let row = match old.iter().position(|r| r.service == service.name && r.version == v.name) {
+                    Some(i) => old.swap_remove(i),
+                    None => VersionRow {
+                        service: service.name.clone(),
+                        version: v.name.clone(),
+                        weight: String::new(),
+                        weight_input: text_input::State::new(),
+                        set_button: button::State::new(),
+                        reload_button: button::State::new(),
+                        rollback_button: button::State::new(),
+                    },
+                };
+                let weight = if row.weight_input.is_focused() { row.weight } else { v.weight.to_string() };
+                self.versions.push(VersionRow { weight, ..row });
+            }
+        }
+    }
+}
+
+fn button<'a, Message: Clone>(
+    state: &'a mut button::State,
+    label: &str,
+) -> Button<'a, Message> {
+    Button::new(
+        state,
+        Text::new(label).horizontal_alignment(alignment::Horizontal::Center),
+    )
+        .padding(12)
+        .min_width(100)
+}
+
+mod style {
+    use iced::{Background, button, Color, Vector};
+
+    pub enum Button {
+        Primary,
+        Secondary,
+    }
+
+    impl button::StyleSheet for Button {
+        fn active(&self) -> button::Style {
+            button::Style {
+                background: Some(Background::Color(match self {
+                    Button::Primary => Color::from_rgb(0.11, 0.42, 0.87),
+                    Button::Secondary => Color::from_rgb(0.5, 0.5, 0.5),
+                })),
+                border_radius: 12.0,
+                shadow_offset: Vector::new(1.0, 1.0),
+                text_color: Color::from_rgb8(0xEE, 0xEE, 0xEE),
+                ..button::Style::default()
+            }
+        }
+
+        fn hovered(&self) -> button::Style {
+            button::Style {
+                text_color: Color::WHITE,
+                shadow_offset: Vector::new(1.0, 2.0),
+                ..self.active()
+            }
+        }
+    }
+}
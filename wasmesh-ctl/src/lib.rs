@@ -0,0 +1,13 @@
+//! Client of the pod admin API and the state of the control tool, shared by
+//! the GUI and the command line.
+
+pub use client::*;
+pub use console::*;
+
+pub mod cli;
+mod client;
+mod console;
+pub mod plane;
+pub mod tls;
+#[cfg(test)]
+mod fake;
@@ -0,0 +1,630 @@
+//! Control plane: holds the desired state of a fleet of pods and streams it to
+//! pods subscribed over the RPC transport, see `wasmesh-plane`. Served with an
+//! identity, the transport is mutual TLS and every peer must present a
+//! certificate of the same CA, see [`RpcTls`].
+//!
+//! Every stream starts with the full state. From then on, a reconciliation
+//! loop sends a pod the resources that differ from the state it last
+//! acknowledged, and waits before retrying a state the pod rejected. An
+//! update replacing one the pod has not answered yet is a full state again,
+//! since the pod may have applied either.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use wasmesh_proto::{
+    ConfigUpdate, Module, PlaneMessage, PlaneMessage_oneof_kind, PlaneStatus, PodMessage, PodMessage_oneof_kind, PodReport,
+    Resource, StatusRequest,
+};
+use wasmesh_proto::rpc::{read_frame, write_frame, FrameReader, Stream};
+
+use crate::tls::RpcTls;
+
+/// How often a stream is reconciled with the desired state.
+const TICK: Duration = Duration::from_millis(200);
+
+/// Wait for the first message of a connection, and for answers of the plane.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Wait before a state a pod rejected is sent to it again.
+const RETRY_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Desired state of the pods, a set of named resources.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DesiredState {
+    version: String,
+    resources: BTreeMap<String, Resource>,
+}
+
+impl DesiredState {
+    /// Parses desired state written like a pod config: `services`, `limits`
+    /// and `authz`. Each service becomes a resource of its own.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let table = match toml::from_str::<toml::Value>(text).map_err(|e| e.to_string())? {
+            toml::Value::Table(table) => table,
+            _ => return Err("desired state must be a table".to_string()),
+        };
+        let mut resources = BTreeMap::new();
+        for (key, value) in table {
+            match key.as_str() {
+                "services" => {
+                    let services = value.as_array().ok_or("services must be an array")?;
+                    for (order, service) in services.iter().enumerate() {
+                        let name = service.get("name").and_then(|n| n.as_str()).ok_or("every service needs a name")?;
+                        let r = resource(&format!("service/{}", name), service, order as u32)?;
+                        if resources.insert(r.name.clone(), r).is_some() {
+                            return Err(format!("duplicate service {}", name));
+                        }
+                    }
+                }
+                "limits" | "authz" => {
+                    resources.insert(key.clone(), resource(&key, &value, 0)?);
+                }
+                _ => return Err(format!("unknown field {}, expected services, limits or authz", key)),
+            }
+        }
+        Ok(DesiredState::new(resources))
+    }
+
+    fn new(resources: BTreeMap<String, Resource>) -> Self {
+        // the same on every build and host, so a restarted plane keeps the version pods acked
+        let mut hasher = Sha256::new();
+        for r in resources.values() {
+            for field in [r.name.as_bytes(), r.json.as_bytes()] {
+                hasher.update((field.len() as u64).to_le_bytes());
+                hasher.update(field);
+            }
+            hasher.update(r.order.to_le_bytes());
+        }
+        let digest = format!("{:x}", hasher.finalize());
+        DesiredState { version: digest[..16].to_string(), resources }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("read {}: {}", path.display(), e))?;
+        Self::parse(&text).map_err(|e| format!("parse {}: {}", path.display(), e))
+    }
+
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Update taking a pod from the state it acknowledged, or from nothing, to this one.
+    fn diff(&self, acked: Option<&DesiredState>, nonce: String) -> ConfigUpdate {
+        let mut update = ConfigUpdate::new();
+        update.set_version(self.version.clone());
+        update.set_nonce(nonce);
+        match acked {
+            None => {
+                update.set_full(true);
+                update.resources = self.resources.values().cloned().collect();
+            }
+            Some(acked) => {
+                update.resources = self.resources
+                                       .values()
+                                       .filter(|r| acked.resources.get(&r.name) != Some(r))
+                                       .cloned()
+                                       .collect();
+                update.removed = acked.resources
+                                      .keys()
+                                      .filter(|name| !self.resources.contains_key(*name))
+                                      .cloned()
+                                      .collect();
+            }
+        }
+        update
+    }
+}
+
+fn resource(name: &str, value: &toml::Value, order: u32) -> Result<Resource, String> {
+    let mut r = Resource::new();
+    r.set_name(name.to_string());
+    r.set_json(serde_json::to_string(value).map_err(|e| format!("{}: {}", name, e))?);
+    r.set_order(order);
+    Ok(r)
+}
+
+/// Control plane server.
+pub struct Plane {
+    state: RwLock<Arc<DesiredState>>,
+    pods: Mutex<BTreeMap<String, PodReport>>,
+    nonce: AtomicU64,
+    // directory of the modules pods may fetch by digest
+    modules: Option<PathBuf>,
+    index: Mutex<ModuleIndex>,
+}
+
+/// Digests of the `.wasm` files of the modules directory, so a file is
+/// hashed again only once it changed.
+#[derive(Default)]
+struct ModuleIndex {
+    files: HashMap<PathBuf, (SystemTime, String)>,
+}
+
+impl ModuleIndex {
+    /// Hashes the files added or modified since the last refresh.
+    fn refresh(&mut self, dir: &Path) -> Result<(), String> {
+        let paths = fs::read_dir(dir).map_err(|e| format!("read {}: {}", dir.display(), e))?
+                                     .filter_map(|entry| entry.ok().map(|e| e.path()))
+                                     .filter(|path| path.extension().is_some_and(|ext| ext == "wasm"));
+        let mut files = HashMap::new();
+        for path in paths {
+            let modified = match fs::metadata(&path).and_then(|m| m.modified()) {
+                Ok(modified) => modified,
+                Err(_) => continue,
+            };
+            let digest = match self.files.remove(&path) {
+                Some((hashed, digest)) if hashed == modified => digest,
+                _ => match fs::read(&path) {
+                    Ok(wasm) => format!("{:x}", Sha256::digest(wasm)),
+                    Err(_) => continue,
+                },
+            };
+            files.insert(path, (modified, digest));
+        }
+        self.files = files;
+        Ok(())
+    }
+
+    fn find(&self, hex: &str) -> Option<PathBuf> {
+        self.files.iter().find(|(_, (_, digest))| digest == hex).map(|(path, _)| path.clone())
+    }
+}
+
+impl Plane {
+    /// Creates a plane serving the desired state, and the `.wasm` files of
+    /// `modules` to pods fetching a module by digest.
+    pub fn new(state: DesiredState, modules: Option<PathBuf>) -> Arc<Self> {
+        Arc::new(Plane {
+            state: RwLock::new(Arc::new(state)),
+            pods: Mutex::new(BTreeMap::new()),
+            nonce: AtomicU64::new(0),
+            modules,
+            index: Mutex::new(ModuleIndex::default()),
+        })
+    }
+
+    /// Replaces the desired state, returns whether it changed.
+    pub fn set_state(&self, state: DesiredState) -> bool {
+        let mut current = self.state.write().unwrap();
+        if current.version == state.version {
+            return false;
+        }
+        *current = Arc::new(state);
+        true
+    }
+
+    pub fn state(&self) -> Arc<DesiredState> {
+        self.state.read().unwrap().clone()
+    }
+
+    /// Desired state version and the pods that subscribed since the start.
+    pub fn status(&self) -> PlaneStatus {
+        let mut status = PlaneStatus::new();
+        status.set_version(self.state().version.clone());
+        status.pods = self.pods.lock().unwrap().values().cloned().collect();
+        status
+    }
+
+    /// Module with the given `sha256:<hex>` digest: `<hex>.wasm` of the
+    /// modules directory, or else any `.wasm` file there with that content.
+    /// Returns its signature file too, `<file>.sig`, when there is one.
+    pub fn module(&self, digest: &str) -> Result<(Vec<u8>, Option<Vec<u8>>), String> {
+        let dir = self.modules.as_ref().ok_or("the control plane serves no modules")?;
+        let hex = digest.strip_prefix("sha256:")
+                        .filter(|hex| hex.len() == 64 && hex.bytes().all(|b| b.is_ascii_hexdigit()))
+                        .ok_or_else(|| format!("invalid module digest {}", digest))?;
+        let read = |path: &Path| fs::read(path).ok().filter(|wasm| format!("{:x}", Sha256::digest(wasm)) == hex);
+        let direct = dir.join(format!("{}.wasm", hex));
+        let (path, wasm) = match read(&direct) {
+            Some(wasm) => (direct, wasm),
+            None => {
+                let mut index = self.index.lock().unwrap();
+                index.refresh(dir)?;
+                let path = index.find(hex).ok_or_else(|| format!("module not found: {}", digest))?;
+                match read(&path) {
+                    Some(wasm) => (path, wasm),
+                    None => {
+                        // changed within the precision of its modification time
+                        index.files.remove(&path);
+                        return Err(format!("module not found: {}", digest));
+                    }
+                }
+            }
+        };
+        let mut signature = path.into_os_string();
+        signature.push(".sig");
+        Ok((wasm, fs::read(signature).ok()))
+    }
+
+    /// Serves subscriptions, status requests and module fetches, a thread per
+    /// connection, over mutual TLS with `tls`.
+    pub fn serve(self: &Arc<Self>, listener: TcpListener, tls: Option<RpcTls>) {
+        for conn in listener.incoming() {
+            let conn = match conn {
+                Ok(conn) => conn,
+                Err(e) => {
+                    eprintln!("PLANE accept error: {}", e);
+                    continue;
+                }
+            };
+            let plane = self.clone();
+            let tls = tls.clone();
+            thread::spawn(move || {
+                let peer = conn.peer_addr().map_or_else(|_| "?".to_string(), |a| a.to_string());
+                let r = conn.set_read_timeout(Some(REQUEST_TIMEOUT))
+                            .and_then(|_| conn.try_clone())
+                            .and_then(|socket| match &tls {
+                                Some(tls) => tls.accept(socket),
+                                None => Ok(Box::new(socket) as Box<dyn Stream>),
+                            })
+                            .and_then(|stream| plane.handle(&peer, conn, stream));
+                if let Err(e) = r {
+                    eprintln!("PLANE stream error: peer={}, {}", peer, e);
+                }
+            });
+        }
+    }
+
+    fn handle(&self, peer: &str, socket: TcpStream, mut conn: Box<dyn Stream>) -> io::Result<()> {
+        match read_frame::<PodMessage, _>(&mut conn)?.kind {
+            Some(PodMessage_oneof_kind::status_request(_)) => {
+                let mut msg = PlaneMessage::new();
+                msg.set_status(self.status());
+                write_frame(&mut conn, &msg)
+            }
+            Some(PodMessage_oneof_kind::fetch_module(fetch)) => {
+                let mut module = Module::new();
+                match self.module(&fetch.digest) {
+                    Ok((wasm, signature)) => {
+                        module.set_wasm(wasm.into());
+                        module.set_signature(signature.unwrap_or_default().into());
+                    }
+                    Err(e) => module.set_error(e),
+                }
+                module.set_digest(fetch.digest);
+                let mut msg = PlaneMessage::new();
+                msg.set_module(module);
+                write_frame(&mut conn, &msg)
+            }
+            Some(PodMessage_oneof_kind::subscribe(subscribe)) if !subscribe.pod_id.is_empty() => {
+                let pod_id = subscribe.pod_id.clone();
+                let mut report = PodReport::new();
+                report.set_pod_id(pod_id.clone());
+                report.set_address(peer.to_string());
+                report.set_connected(true);
+                report.set_last_seen_ms(now_ms());
+                self.pods.lock().unwrap().insert(pod_id.clone(), report);
+                let r = self.stream(&pod_id, &socket, conn);
+                if let Some(report) = self.pods.lock().unwrap().get_mut(&pod_id) {
+                    report.set_connected(false);
+                }
+                r
+            }
+            _ => Err(io::Error::new(io::ErrorKind::InvalidData, "expected a subscription, a status request or a module fetch")),
+        }
+    }
+
+    /// Keeps a subscribed pod in sync with the desired state.
+    fn stream(&self, pod_id: &str, socket: &TcpStream, mut conn: Box<dyn Stream>) -> io::Result<()> {
+        // a read times out every tick, then the stream is reconciled
+        socket.set_read_timeout(Some(TICK))?;
+        let mut frames = FrameReader::new();
+        // state the pod runs, as far as it acknowledged
+        let mut acked: Option<Arc<DesiredState>> = None;
+        let mut pending: Option<(String, Arc<DesiredState>)> = None;
+        let mut rejected: Option<(String, Instant)> = None;
+        let r = loop {
+            let desired = self.state();
+            let in_sync = acked.as_ref().is_some_and(|s| s.version == desired.version);
+            let waiting = pending.as_ref().is_some_and(|(_, s)| s.version == desired.version);
+            let backing_off = rejected.as_ref().is_some_and(|(v, at)| *v == desired.version && at.elapsed() < RETRY_INTERVAL);
+            if !in_sync && !waiting && !backing_off {
+                let nonce = self.nonce.fetch_add(1, Ordering::Relaxed).to_string();
+                // the pod runs the acknowledged state, or the pending one
+                let base = if pending.is_some() { None } else { acked.as_deref() };
+                let mut msg = PlaneMessage::new();
+                msg.set_update(desired.diff(base, nonce.clone()));
+                if let Err(e) = write_frame(&mut conn, &msg) {
+                    break Err(e);
+                }
+                pending = Some((nonce, desired));
+            }
+            let msg = match frames.read::<PodMessage, _>(&mut conn) {
+                Ok(Some(msg)) => msg,
+                Ok(None) => continue,
+                Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => break Ok(()),
+                Err(e) => break Err(e),
+            };
+            let mut pods = self.pods.lock().unwrap();
+            let report = pods.entry(pod_id.to_string()).or_default();
+            report.set_last_seen_ms(now_ms());
+            match msg.kind {
+                Some(PodMessage_oneof_kind::ack(ack)) => {
+                    let state = match pending.take_if(|(nonce, _)| *nonce == ack.nonce) {
+                        Some((_, state)) => state,
+                        None => continue,
+                    };
+                    if ack.error.is_empty() {
+                        report.set_acked_version(state.version.clone());
+                        report.clear_error();
+                        acked = Some(state);
+                        rejected = None;
+                    } else {
+                        report.set_error(ack.error.clone());
+                        rejected = Some((state.version.clone(), Instant::now()));
+                    }
+                }
+                Some(PodMessage_oneof_kind::status(status)) => report.set_status(status),
+                _ => {}
+            }
+        };
+        let _ = socket.shutdown(Shutdown::Both);
+        r
+    }
+}
+
+/// Reloads the desired state from `path` whenever the file changes.
+pub fn watch(plane: &Plane, path: &Path, interval: Duration) {
+    let modified = || fs::metadata(path).and_then(|m| m.modified()).ok();
+    let mut last = modified();
+    loop {
+        thread::sleep(interval);
+        let current = modified();
+        if current == last {
+            continue;
+        }
+        last = current;
+        match DesiredState::load(path) {
+            Ok(state) => {
+                let version = state.version.clone();
+                if plane.set_state(state) {
+                    println!("desired state {} loaded from {}", version, path.display());
+                }
+            }
+            Err(e) => eprintln!("PLANE state error: {}", e),
+        }
+    }
+}
+
+/// Asks the control plane at `addr` for the status of its pods, over mutual
+/// TLS with `tls`.
+pub fn plane_status(addr: &str, tls: Option<&RpcTls>) -> io::Result<PlaneSummary> {
+    let socket = TcpStream::connect(addr)?;
+    socket.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    let mut conn = match tls {
+        Some(tls) => tls.connect(addr, socket)?,
+        None => Box::new(socket),
+    };
+    let mut msg = PodMessage::new();
+    msg.set_status_request(StatusRequest::new());
+    write_frame(&mut conn, &msg)?;
+    match read_frame::<PlaneMessage, _>(&mut conn)?.kind {
+        Some(PlaneMessage_oneof_kind::status(status)) => Ok(PlaneSummary::from(status)),
+        _ => Err(io::Error::new(io::ErrorKind::InvalidData, "expected a status")),
+    }
+}
+
+/// Status of a control plane, as printed by `wasmesh-ctl plane status`.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct PlaneSummary {
+    pub version: String,
+    pub pods: Vec<PodSummary>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct PodSummary {
+    pub pod_id: String,
+    pub address: String,
+    pub connected: bool,
+    /// whether the pod runs the desired state
+    pub in_sync: bool,
+    pub version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub services: u32,
+    pub requests: u64,
+    pub errors: u64,
+    pub last_seen_ms: u64,
+}
+
+impl From<PlaneStatus> for PlaneSummary {
+    fn from(status: PlaneStatus) -> Self {
+        let pods = status.pods.iter().map(|p| PodSummary {
+            pod_id: p.pod_id.clone(),
+            address: p.address.clone(),
+            connected: p.connected,
+            in_sync: p.acked_version == status.version,
+            version: p.acked_version.clone(),
+            error: Some(p.error.clone()).filter(|e| !e.is_empty()),
+            services: p.get_status().services,
+            requests: p.get_status().requests,
+            errors: p.get_status().errors,
+            last_seen_ms: p.last_seen_ms,
+        }).collect();
+        PlaneSummary { version: status.version, pods }
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use wasmesh_proto::{Ack, Subscribe};
+
+    use crate::tls::issue_ca;
+
+    use super::*;
+
+    /// A pod speaking the RPC transport, applying nothing.
+    struct TestPod {
+        conn: TcpStream,
+    }
+
+    impl TestPod {
+        fn subscribe(addr: &str, pod_id: &str) -> Self {
+            let mut conn = TcpStream::connect(addr).unwrap();
+            conn.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+            let mut subscribe = Subscribe::new();
+            subscribe.set_pod_id(pod_id.to_string());
+            let mut msg = PodMessage::new();
+            msg.set_subscribe(subscribe);
+            write_frame(&mut conn, &msg).unwrap();
+            TestPod { conn }
+        }
+        fn next_update(&mut self) -> ConfigUpdate {
+            read_frame::<PlaneMessage, _>(&mut self.conn).unwrap().take_update()
+        }
+        fn ack(&mut self, update: &ConfigUpdate, error: &str) {
+            let mut ack = Ack::new();
+            ack.set_version(update.version.clone());
+            ack.set_nonce(update.nonce.clone());
+            ack.set_error(error.to_string());
+            let mut msg = PodMessage::new();
+            msg.set_ack(ack);
+            write_frame(&mut self.conn, &msg).unwrap();
+        }
+    }
+
+    fn state(billing: &str, limits: bool) -> DesiredState {
+        let mut text = format!(r#"
+            [[services]]
+            name = "orders"
+            path = "/orders/*"
+            versions = [{{ name = "v1", wasm = "orders-v1.wasm", weight = 100 }}]
+
+            [[services]]
+            name = "billing"
+            path = "/billing/*"
+            versions = [{{ name = "v1", wasm = "{}", weight = 100 }}]
+        "#, billing);
+        if limits {
+            text.push_str("[[limits]]\nkey = \"client_ip\"\nrate = 50.0\n");
+        }
+        DesiredState::parse(&text).unwrap()
+    }
+
+    fn wait_for<F: Fn(&PlaneSummary) -> bool>(addr: &str, f: F) -> PlaneSummary {
+        for _ in 0..50 {
+            let status = plane_status(addr, None).unwrap();
+            if f(&status) {
+                return status;
+            }
+            thread::sleep(Duration::from_millis(100));
+        }
+        panic!("plane status never matched: {:?}", plane_status(addr, None).unwrap());
+    }
+
+    #[test]
+    fn pods_receive_incremental_updates() {
+        let plane = Plane::new(state("billing-v1.wasm", true), None);
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let server = plane.clone();
+        thread::spawn(move || server.serve(listener, None));
+
+        let mut pods = [TestPod::subscribe(&addr, "pod-a"), TestPod::subscribe(&addr, "pod-b")];
+        for pod in pods.iter_mut() {
+            let update = pod.next_update();
+            assert!(update.full);
+            assert_eq!(update.resources.iter().map(|r| r.name.as_str()).collect::<Vec<_>>(),
+                       ["limits", "service/billing", "service/orders"]);
+            pod.ack(&update, "");
+        }
+        let v1 = plane.state().version().to_string();
+        wait_for(&addr, |s| s.pods.len() == 2 && s.pods.iter().all(|p| p.in_sync));
+
+        // billing moves to v2 and the limits go
+        assert!(plane.set_state(state("billing-v2.wasm", false)));
+        let update = pods[0].next_update();
+        assert!(!update.full);
+        assert_eq!(update.resources.iter().map(|r| r.name.as_str()).collect::<Vec<_>>(), ["service/billing"]);
+        assert_eq!(update.removed.to_vec(), ["limits"]);
+        pods[0].ack(&update, "");
+        let update = pods[1].next_update();
+        pods[1].ack(&update, "load billing-v2.wasm: no such file");
+
+        let status = wait_for(&addr, |s| s.pods.iter().any(|p| p.error.is_some()));
+        assert_ne!(status.version, v1);
+        assert!(status.pods[0].in_sync);
+        assert!(!status.pods[1].in_sync);
+        assert_eq!(status.pods[1].version, v1);
+        assert_eq!(status.pods[1].error.as_deref(), Some("load billing-v2.wasm: no such file"));
+    }
+
+    #[test]
+    fn versions_follow_the_content() {
+        let v1 = state("billing-v1.wasm", false).version;
+        assert_eq!(v1.len(), 16);
+        assert_eq!(state("billing-v1.wasm", false).version, v1);
+        assert_ne!(state("billing-v2.wasm", false).version, v1);
+        assert_ne!(state("billing-v1.wasm", true).version, v1);
+    }
+
+    #[test]
+    fn replaced_updates_are_sent_in_full() {
+        let plane = Plane::new(state("billing-v1.wasm", false), None);
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let server = plane.clone();
+        thread::spawn(move || server.serve(listener, None));
+
+        let mut pod = TestPod::subscribe(&addr, "pod-a");
+        let update = pod.next_update();
+        pod.ack(&update, "");
+        wait_for(&addr, |s| s.pods.iter().any(|p| p.in_sync));
+
+        // the limits come with an update the pod applies but has not acked
+        // when they go again
+        plane.set_state(state("billing-v2.wasm", true));
+        let update = pod.next_update();
+        assert_eq!(update.resources.iter().map(|r| r.name.as_str()).collect::<Vec<_>>(), ["limits", "service/billing"]);
+        plane.set_state(state("billing-v3.wasm", false));
+        let update = pod.next_update();
+        assert!(update.full);
+        assert!(update.resources.iter().all(|r| r.name != "limits"));
+    }
+
+    #[test]
+    fn peers_need_a_certificate_of_the_ca() {
+        let dir = tempfile::tempdir().unwrap();
+        issue_ca(dir.path(), &["wasmesh.plane", "wasmesh.ctl"]);
+        let plane = Plane::new(state("billing-v1.wasm", false), None);
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let tls = RpcTls::load(dir.path(), "wasmesh.plane").unwrap();
+        thread::spawn(move || plane.serve(listener, Some(tls)));
+
+        let ctl = RpcTls::load(dir.path(), "wasmesh.ctl").unwrap();
+        assert_eq!(plane_status(&addr, Some(&ctl)).unwrap().pods, []);
+        assert!(plane_status(&addr, None).is_err());
+    }
+
+    #[test]
+    fn modules_are_found_by_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let plane = Plane::new(DesiredState::default(), Some(dir.path().to_path_buf()));
+        let digest = |wasm: &[u8]| format!("sha256:{:x}", Sha256::digest(wasm));
+        let (v1, v2) = (b"\0asm\x01\0\0\0".to_vec(), b"\0asm\x01\0\0\0\0".to_vec());
+        fs::write(dir.path().join("orders.wasm"), &v1).unwrap();
+        fs::write(dir.path().join("orders.wasm.sig"), b"sig").unwrap();
+        assert_eq!(plane.module(&digest(&v1)).unwrap(), (v1.clone(), Some(b"sig".to_vec())));
+
+        // overwritten in place, the directory itself does not change
+        thread::sleep(Duration::from_millis(10));
+        fs::write(dir.path().join("orders.wasm"), &v2).unwrap();
+        assert_eq!(plane.module(&digest(&v2)).unwrap().0, v2);
+        assert!(plane.module(&digest(&v1)).is_err());
+    }
+}
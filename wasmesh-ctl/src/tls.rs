@@ -0,0 +1,105 @@
+//! Mutual TLS of the control plane RPC transport, from a CA directory laid out
+//! like the one of `wasmesh-pod --tls-ca-dir`: the trust root `ca.pem` and, for
+//! every identity, a certificate chain `<name>.pem` with its key `<name>.key`.
+
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::net::TcpStream;
+use std::path::Path;
+use std::sync::Arc;
+
+use rustls::{ClientConfig, ClientConnection, RootCertStore, ServerConfig, ServerConnection, StreamOwned};
+use rustls::crypto::ring;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName};
+use rustls::server::WebPkiClientVerifier;
+use wasmesh_proto::rpc::Stream;
+
+/// Identity of the control plane or of the control tool, trusting the
+/// certificates issued by the CA only.
+#[derive(Clone)]
+pub struct RpcTls {
+    server: Arc<ServerConfig>,
+    client: Arc<ClientConfig>,
+}
+
+impl RpcTls {
+    /// Loads the identity `name` of the CA directory, e.g. `wasmesh.plane`.
+    pub fn load(ca_dir: &Path, name: &str) -> Result<Self, String> {
+        let provider = Arc::new(ring::default_provider());
+        let mut roots = RootCertStore::empty();
+        for cert in load_certs(&ca_dir.join("ca.pem"))? {
+            roots.add(cert).map_err(|e| e.to_string())?;
+        }
+        let roots = Arc::new(roots);
+        let certs = load_certs(&ca_dir.join(format!("{}.pem", name)))?;
+        let key = load_key(&ca_dir.join(format!("{}.key", name)))?;
+
+        let verifier = WebPkiClientVerifier::builder_with_provider(roots.clone(), provider.clone())
+            .build()
+            .map_err(|e| e.to_string())?;
+        let server = ServerConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()
+            .and_then(|b| b.with_client_cert_verifier(verifier).with_single_cert(certs.clone(), key.clone_key()))
+            .map_err(|e| e.to_string())?;
+        let client = ClientConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()
+            .and_then(|b| b.with_root_certificates(roots).with_client_auth_cert(certs, key))
+            .map_err(|e| e.to_string())?;
+        Ok(RpcTls { server: Arc::new(server), client: Arc::new(client) })
+    }
+
+    /// Trusts the CA only and presents the identity, for HTTPS clients.
+    pub(crate) fn client_config(&self) -> Arc<ClientConfig> {
+        self.client.clone()
+    }
+
+    /// Accepts a peer presenting a certificate of the CA.
+    pub(crate) fn accept(&self, socket: TcpStream) -> io::Result<Box<dyn Stream>> {
+        let tls = ServerConnection::new(self.server.clone()).map_err(io::Error::other)?;
+        Ok(Box::new(StreamOwned::new(tls, socket)))
+    }
+
+    /// Dials `addr`, whose certificate must name its host.
+    pub(crate) fn connect(&self, addr: &str, socket: TcpStream) -> io::Result<Box<dyn Stream>> {
+        let host = addr.rsplit_once(':').map_or(addr, |(host, _)| host).trim_start_matches('[').trim_end_matches(']');
+        let name = ServerName::try_from(host.to_string()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        let tls = ClientConnection::new(self.client.clone(), name).map_err(io::Error::other)?;
+        Ok(Box::new(StreamOwned::new(tls, socket)))
+    }
+}
+
+fn load_certs(path: &Path) -> Result<Vec<CertificateDer<'static>>, String> {
+    let file = File::open(path).map_err(|e| format!("open {}: {}", path.display(), e))?;
+    let certs = rustls_pemfile::certs(&mut BufReader::new(file))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("read {}: {}", path.display(), e))?;
+    if certs.is_empty() {
+        return Err(format!("no certificate found in {}", path.display()));
+    }
+    Ok(certs)
+}
+
+fn load_key(path: &Path) -> Result<PrivateKeyDer<'static>, String> {
+    let file = File::open(path).map_err(|e| format!("open {}: {}", path.display(), e))?;
+    rustls_pemfile::private_key(&mut BufReader::new(file))
+        .map_err(|e| format!("read {}: {}", path.display(), e))?
+        .ok_or_else(|| format!("no private key found in {}", path.display()))
+}
+
+/// Writes a CA directory with an identity per name, whose certificates name `127.0.0.1`.
+#[cfg(test)]
+pub(crate) fn issue_ca(dir: &Path, names: &[&str]) {
+    use rcgen::{BasicConstraints, CertificateParams, IsCa, KeyPair};
+
+    let ca_key = KeyPair::generate().unwrap();
+    let mut params = CertificateParams::new(Vec::<String>::new()).unwrap();
+    params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    let ca = params.self_signed(&ca_key).unwrap();
+    std::fs::write(dir.join("ca.pem"), ca.pem()).unwrap();
+    for name in names {
+        let key = KeyPair::generate().unwrap();
+        let cert = CertificateParams::new(vec!["127.0.0.1".to_string()]).unwrap().signed_by(&key, &ca, &ca_key).unwrap();
+        std::fs::write(dir.join(format!("{}.pem", name)), cert.pem()).unwrap();
+        std::fs::write(dir.join(format!("{}.key", name)), key.serialize_pem()).unwrap();
+    }
+}
@@ -0,0 +1,484 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use hyper::{Body, Error, Method, Request, Response, StatusCode};
+use hyper::body::{Bytes, HttpBody};
+use hyper::header::{AUTHORIZATION, CONTENT_TYPE, HeaderValue, WWW_AUTHENTICATE};
+use hyper::server::conn::Http;
+use hyper::service::service_fn;
+use rustls::ServerConfig;
+use serde::{Deserialize, Serialize};
+use serde::de::DeserializeOwned;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpListener;
+use tokio_rustls::TlsAcceptor;
+use wasmesh_proto::rpc::MAX_FRAME_LEN;
+
+use crate::config::{self, PodConfig};
+use crate::http::{glob_match, LimitRule, module_name, PoolStatus, Router, validate_limits};
+use crate::logs;
+use crate::signing::Verification;
+use crate::store;
+use crate::tls;
+
+/// Largest signature file accepted, a base64 encoded ed25519 signature takes 88 bytes.
+const MAX_SIGNATURE_LEN: usize = 512;
+
+/// State of the admin API.
+pub(crate) struct Admin {
+    pub(crate) router: Arc<Router>,
+    pub(crate) auth: AdminAuth,
+    pub(crate) worker_threads: usize,
+}
+
+/// Who may call the admin API, every configured check must pass.
+#[derive(Clone, Default)]
+pub(crate) struct AdminAuth {
+    /// bearer token every request must carry
+    pub(crate) token: Option<String>,
+    /// serves over mutual TLS, callers need a certificate of the pod's CA
+    pub(crate) tls: Option<Arc<ServerConfig>>,
+    /// caller identity patterns allowed over mutual TLS, any verified caller when empty
+    pub(crate) identities: Vec<String>,
+}
+
+impl AdminAuth {
+    fn is_enabled(&self) -> bool {
+        self.token.is_some() || self.tls.is_some()
+    }
+
+    /// Returns the response refusing the request, if the caller is not allowed.
+    fn reject<B>(&self, peer: Option<&str>, req: &Request<B>) -> Option<Response<Body>> {
+        if let Some(token) = &self.token {
+            let given = req.headers()
+                           .get(AUTHORIZATION)
+                           .and_then(|v| v.to_str().ok())
+                           .and_then(|v| v.strip_prefix("Bearer "));
+            if !given.is_some_and(|given| constant_time_eq(given.trim().as_bytes(), token.as_bytes())) {
+                let mut resp = error(StatusCode::UNAUTHORIZED, "missing or invalid admin token");
+                resp.headers_mut().insert(WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+                return Some(resp);
+            }
+        }
+        if self.tls.is_some() && !self.identities.is_empty()
+            && !peer.is_some_and(|peer| self.identities.iter().any(|p| glob_match(p, peer))) {
+            return Some(error(StatusCode::FORBIDDEN, "caller identity is not allowed"));
+        }
+        None
+    }
+}
+
+/// Serves the pod admin API.
+///
+/// - `GET /routes`: services with their versions, weights and counters
+/// - `PUT /routes/{service}/weights`: set version weights, e.g. `{"v1": 95, "v2": 5}`
+/// - `PUT /routes/{service}/versions/{version}`: deploy a module of the store, e.g. `{"wasm": "sha256:<hex>", "weight": 0}`
+/// - `POST /routes/{service}/versions/{version}/reload`: load the version's module again
+/// - `POST /routes/{service}/versions/{version}/rollback`: undo the last deploy of the version
+/// - `GET /modules`: modules of all versions, with the SHA-256 of their files
+/// - `POST /modules`: add the wasm module in the body to the module store, returns its `sha256:<hex>` digest
+/// - `GET /modules/sha256:{hex}`: a module of the store or a loaded module, for peers to fetch
+/// - `GET /modules/sha256:{hex}/signature`: its detached signature
+/// - `PUT /modules/sha256:{hex}/signature`: store the detached signature of a module, base64 encoded
+/// - `GET /signatures`: names of the trusted keys, and the modules refused for their signature
+/// - `GET /metrics`: per-version counters
+/// - `GET /pool`: running and recycled guest instances per version
+/// - `GET /limits`: rate limit rules
+/// - `PUT /limits`: replace the rate limit rules until the config is reloaded
+/// - `GET /config`: the pod config in effect, with the services as currently routed
+/// - `POST /config/reload`: re-read the pod config file, like `SIGHUP`; the
+///   services, limits and authz policy are kept while subscribed to a control plane
+/// - `GET /logs?after={seq}&limit={n}`: pod log records after a sequence number
+///
+/// Without a token or mutual TLS, the listener must be bound to a loopback address.
+pub(crate) async fn serve(admin: Arc<Admin>, addr: SocketAddr) -> anyhow::Result<()> {
+    if !admin.auth.is_enabled() && !addr.ip().is_loopback() {
+        anyhow::bail!("admin API on {} needs --admin-token or --tls-ca-dir, or a loopback address", addr);
+    }
+    let listener = TcpListener::bind(&addr).await?;
+    let acceptor = admin.auth.tls.clone().map(TlsAcceptor::from);
+    println!("Admin listening on {}://{}", if acceptor.is_some() { "https" } else { "http" }, addr);
+    loop {
+        let (stream, remote_addr) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                eprintln!("ADMIN accept error: {}", e);
+                continue;
+            }
+        };
+        let (admin, acceptor) = (admin.clone(), acceptor.clone());
+        tokio::spawn(async move {
+            match acceptor {
+                Some(acceptor) => match acceptor.accept(stream).await {
+                    Ok(stream) => {
+                        let peer = tls::peer_identity(stream.get_ref().1.peer_certificates()).map(|id| id.uri());
+                        serve_connection(admin, stream, peer).await
+                    }
+                    Err(e) => log_warn!("admin: TLS handshake error: remote_addr={}, {}", remote_addr, e),
+                },
+                None => serve_connection(admin, stream, None).await,
+            }
+        });
+    }
+}
+
+async fn serve_connection<S>(admin: Arc<Admin>, stream: S, peer: Option<String>)
+    where S: AsyncRead + AsyncWrite + Unpin + Send + 'static {
+    let service = service_fn(move |req| {
+        let (admin, peer) = (admin.clone(), peer.clone());
+        async move {
+            Ok::<_, Error>(match admin.auth.reject(peer.as_deref(), &req) {
+                Some(resp) => resp,
+                None => handle(&admin, req).await,
+            })
+        }
+    });
+    if let Err(e) = Http::new().serve_connection(stream, service).await {
+        eprintln!("ADMIN error: {}", e);
+    }
+}
+
+#[derive(Serialize)]
+struct VersionMetrics<'a> {
+    service: &'a str,
+    version: &'a str,
+    requests: u64,
+    errors: u64,
+    mean_latency_micros: u64,
+}
+
+#[derive(Serialize)]
+struct ModuleInfo<'a> {
+    service: &'a str,
+    version: &'a str,
+    module: &'a str,
+    wasm: &'a str,
+    sha256: Option<String>,
+    /// signature check, see `trusted_keys` in the pod config
+    signature: Option<Verification>,
+}
+
+/// Signature checks, as reported by `GET /signatures`.
+#[derive(Serialize)]
+struct Signatures {
+    /// names of the keys modules must be signed with, no check when empty
+    trusted_keys: Vec<String>,
+    /// modules refused since they last loaded, with the reason
+    refused: HashMap<String, String>,
+}
+
+/// Guest instances, each worker thread creates one per module on first use.
+#[derive(Serialize)]
+struct Pool {
+    worker_threads: usize,
+    versions: Vec<PoolStatus>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Deploy {
+    wasm: String,
+    #[serde(default)]
+    weight: Option<u32>,
+}
+
+async fn handle(admin: &Admin, req: Request<Body>) -> Response<Body> {
+    let router = &admin.router;
+    let path = req.uri().path().trim_end_matches('/');
+    let segments = match path.split('/').skip(1).map(percent_decode).collect::<Option<Vec<String>>>() {
+        Some(segments) => segments,
+        None => return error(StatusCode::BAD_REQUEST, format!("invalid path {}", path)),
+    };
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+    match (req.method(), segments.as_slice()) {
+        (&Method::GET, ["routes"]) => json(StatusCode::OK, &router.status()),
+        (&Method::PUT, ["routes", service, "weights"]) => {
+            let service = service.to_string();
+            let weights: HashMap<String, u32> = match read_json(req).await {
+                Ok(weights) => weights,
+                Err(resp) => return resp,
+            };
+            match router.set_weights(&service, &weights) {
+                Ok(()) => json(StatusCode::OK, &router.status()),
+                Err(e) => error(StatusCode::NOT_FOUND, e),
+            }
+        }
+        (&Method::PUT, ["routes", service, "versions", version]) => {
+            let (service, version) = (service.to_string(), version.to_string());
+            let deploy: Deploy = match read_json(req).await {
+                Ok(deploy) => deploy,
+                Err(resp) => return resp,
+            };
+            // the admin API never reads files of the pod, modules come from the store
+            if store::digest_of(&deploy.wasm).is_none() {
+                return error(StatusCode::BAD_REQUEST, format!("expected a module of the store, sha256:<hex>, got {}", deploy.wasm));
+            }
+            let wasm = deploy.wasm.clone();
+            let (s, v) = (service.clone(), version.clone());
+            match blocking(router, move |router| router.deploy(&s, &v, &wasm, deploy.weight)).await {
+                Ok(()) => {
+                    log_info!("admin: deployed {} to {}/{}", deploy.wasm, service, version);
+                    json(StatusCode::OK, &router.status())
+                }
+                Err(e) => error(StatusCode::UNPROCESSABLE_ENTITY, format!("{:#}", e)),
+            }
+        }
+        (&Method::POST, ["routes", service, "versions", version, "reload"]) => {
+            let (s, v) = (service.to_string(), version.to_string());
+            match blocking(router, move |router| router.reload_module(&s, &v)).await {
+                Ok(()) => {
+                    log_info!("admin: reloaded {}/{}", service, version);
+                    json(StatusCode::OK, &router.status())
+                }
+                Err(e) => error(StatusCode::UNPROCESSABLE_ENTITY, format!("{:#}", e)),
+            }
+        }
+        (&Method::POST, ["routes", service, "versions", version, "rollback"]) => {
+            let (s, v) = (service.to_string(), version.to_string());
+            match blocking(router, move |router| router.rollback(&s, &v)).await {
+                Ok(()) => {
+                    log_info!("admin: rolled back {}/{}", service, version);
+                    json(StatusCode::OK, &router.status())
+                }
+                Err(e) => error(StatusCode::UNPROCESSABLE_ENTITY, format!("{:#}", e)),
+            }
+        }
+        (&Method::GET, ["modules"]) => {
+            let status = router.status();
+            let modules: Vec<ModuleInfo> = status.iter().flat_map(|s| s.versions.iter().map(move |v| {
+                let loaded = router.loaded(&v.wasm);
+                ModuleInfo {
+                    service: &s.name,
+                    version: &v.name,
+                    module: module_name(&v.wasm),
+                    wasm: &v.wasm,
+                    sha256: loaded.as_ref().map(|m| m.sha256.clone()),
+                    signature: loaded.map(|m| m.signature),
+                }
+            })).collect();
+            json(StatusCode::OK, &modules)
+        }
+        (&Method::POST, ["modules"]) => {
+            // larger modules could not be served to peers in a frame
+            let wasm = match read_body(req.into_body(), MAX_FRAME_LEN).await {
+                Ok(wasm) => wasm,
+                Err(resp) => return resp,
+            };
+            if !wasm.starts_with(b"\0asm") {
+                return error(StatusCode::BAD_REQUEST, "not a wasm module");
+            }
+            match store::put(&wasm) {
+                Ok(digest) => {
+                    log_info!("admin: stored module {}", digest);
+                    json(StatusCode::CREATED, &serde_json::json!({ "digest": digest }))
+                }
+                Err(e) => error(StatusCode::INTERNAL_SERVER_ERROR, format!("{:#}", e)),
+            }
+        }
+        (&Method::GET, ["modules", digest]) => {
+            // loaded modules are in the store, also the ones loaded from a path
+            let wasm = store::digest_of(digest).and_then(store::read);
+            match wasm {
+                Some(wasm) => Response::builder()
+                    .header(CONTENT_TYPE, "application/wasm")
+                    .body(Body::from(wasm))
+                    .unwrap(),
+                None => error(StatusCode::NOT_FOUND, format!("module not found: {}", digest)),
+            }
+        }
+        (&Method::GET, ["modules", digest, "signature"]) => {
+            let signature = store::digest_of(digest).and_then(store::read_signature);
+            match signature {
+                Some(signature) => Response::new(Body::from(signature)),
+                None => error(StatusCode::NOT_FOUND, format!("no signature for {}", digest)),
+            }
+        }
+        (&Method::PUT, ["modules", digest, "signature"]) => {
+            let hex = match store::digest_of(digest) {
+                Some(hex) => hex.to_string(),
+                None => return error(StatusCode::BAD_REQUEST, format!("expected sha256:<hex>, got {}", digest)),
+            };
+            let signature = match read_body(req.into_body(), MAX_SIGNATURE_LEN).await {
+                Ok(signature) => signature,
+                Err(resp) => return resp,
+            };
+            match store::put_signature(&hex, &signature) {
+                Ok(()) => {
+                    log_info!("admin: stored signature of sha256:{}", hex);
+                    json(StatusCode::OK, &serde_json::json!({ "digest": format!("sha256:{}", hex) }))
+                }
+                Err(e) => error(StatusCode::BAD_REQUEST, format!("{:#}", e)),
+            }
+        }
+        (&Method::GET, ["signatures"]) => {
+            let trusted_keys = config::current().trusted_keys.iter().map(|k| k.name.clone()).collect();
+            json(StatusCode::OK, &Signatures { trusted_keys, refused: router.refused() })
+        }
+        (&Method::GET, ["pool"]) => {
+            json(StatusCode::OK, &Pool { worker_threads: admin.worker_threads, versions: router.pool() })
+        }
+        (&Method::GET, ["limits"]) => json(StatusCode::OK, &config::current().limits),
+        (&Method::PUT, ["limits"]) => {
+            let limits: Vec<LimitRule> = match read_json(req).await {
+                Ok(limits) => limits,
+                Err(resp) => return resp,
+            };
+            if let Err(e) = validate_limits(&limits) {
+                return error(StatusCode::BAD_REQUEST, e);
+            }
+            config::update(|config| config.limits = limits);
+            log_info!("admin: replaced rate limits");
+            json(StatusCode::OK, &config::current().limits)
+        }
+        (&Method::GET, ["config"]) => {
+            let config = PodConfig { services: router.configs(), ..PodConfig::clone(&config::current()) };
+            json(StatusCode::OK, &config)
+        }
+        (&Method::POST, ["config", "reload"]) => {
+            let r = blocking(router, |router| {
+                config::reload()?;
+                if config::plane_owned() { Ok(()) } else { router.update(&config::current().services) }
+            });
+            match r.await {
+                Ok(()) => {
+                    log_info!("config: reloaded by admin");
+                    json(StatusCode::OK, &router.status())
+                }
+                Err(e) => error(StatusCode::UNPROCESSABLE_ENTITY, format!("{:#}", e)),
+            }
+        }
+        (&Method::GET, ["logs"]) => {
+            let after = query_param(&req, "after").and_then(|v| v.parse().ok()).unwrap_or(0);
+            let limit = query_param(&req, "limit").and_then(|v| v.parse().ok()).unwrap_or(100);
+            json(StatusCode::OK, &logs::tail(after, limit))
+        }
+        (&Method::GET, ["metrics"]) => {
+            let status = router.status();
+            let metrics: Vec<VersionMetrics> = status.iter().flat_map(|s| s.versions.iter().map(move |v| VersionMetrics {
+                service: &s.name,
+                version: &v.name,
+                requests: v.requests,
+                errors: v.errors,
+                mean_latency_micros: v.mean_latency_micros,
+            })).collect();
+            json(StatusCode::OK, &metrics)
+        }
+        _ => error(StatusCode::NOT_FOUND, "not found"),
+    }
+}
+
+/// Runs router work that may fetch and load modules on the blocking threads.
+async fn blocking<F>(router: &Arc<Router>, f: F) -> anyhow::Result<()>
+    where F: FnOnce(&Router) -> anyhow::Result<()> + Send + 'static {
+    let router = router.clone();
+    tokio::task::spawn_blocking(move || f(&router)).await?
+}
+
+/// Reads the whole body, answering `413 Payload Too Large` beyond `limit` bytes.
+async fn read_body(mut body: Body, limit: usize) -> Result<Bytes, Response<Body>> {
+    let too_large = || error(StatusCode::PAYLOAD_TOO_LARGE, format!("body is larger than {} bytes", limit));
+    if body.size_hint().lower() > limit as u64 {
+        return Err(too_large());
+    }
+    let mut bytes = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.map_err(|e| error(StatusCode::BAD_REQUEST, e))?;
+        if bytes.len() + chunk.len() > limit {
+            return Err(too_large());
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(bytes.into())
+}
+
+async fn read_json<T: DeserializeOwned>(req: Request<Body>) -> Result<T, Response<Body>> {
+    let body = hyper::body::to_bytes(req.into_body()).await.map_err(|e| error(StatusCode::BAD_REQUEST, e))?;
+    serde_json::from_slice(&body).map_err(|e| error(StatusCode::BAD_REQUEST, e))
+}
+
+/// Decodes the `%XX` escapes of a path segment, `None` when they are not valid UTF-8.
+fn percent_decode(segment: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(segment.len());
+    let mut rest = segment.as_bytes();
+    while let Some((&b, tail)) = rest.split_first() {
+        if b == b'%' {
+            let hex = std::str::from_utf8(tail.get(..2)?).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(b);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// Compares without returning early, so the time taken does not reveal the token.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn query_param<'a>(req: &'a Request<Body>, name: &str) -> Option<&'a str> {
+    req.uri().query()?.split('&').filter_map(|pair| pair.split_once('=')).find(|(k, _)| *k == name).map(|(_, v)| v)
+}
+
+fn json<T: Serialize + ?Sized>(status: StatusCode, value: &T) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_vec(value).unwrap_or_default()))
+        .unwrap()
+}
+
+fn error<E: ToString>(status: StatusCode, e: E) -> Response<Body> {
+    json(status, &serde_json::json!({ "error": e.to_string() }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(authorization: Option<&str>) -> Request<()> {
+        let mut builder = Request::builder().uri("/routes");
+        if let Some(value) = authorization {
+            builder = builder.header(AUTHORIZATION, value);
+        }
+        builder.body(()).unwrap()
+    }
+
+    #[test]
+    fn token_is_required() {
+        let auth = AdminAuth { token: Some("s3cret".to_string()), ..AdminAuth::default() };
+        assert!(auth.reject(None, &request(Some("Bearer s3cret"))).is_none());
+        for authorization in [None, Some("Bearer s3cre"), Some("Basic s3cret")] {
+            let resp = auth.reject(None, &request(authorization)).unwrap();
+            assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+            assert_eq!(resp.headers()[WWW_AUTHENTICATE], "Bearer");
+        }
+        assert!(AdminAuth::default().reject(None, &request(None)).is_none());
+    }
+
+    #[tokio::test]
+    async fn bodies_are_capped() {
+        assert_eq!(read_body(Body::from("sig"), 3).await.unwrap(), "sig");
+        assert_eq!(read_body(Body::from("sig\n"), 3).await.unwrap_err().status(), StatusCode::PAYLOAD_TOO_LARGE);
+        let (mut sender, body) = Body::channel();
+        tokio::spawn(async move {
+            for _ in 0..4 {
+                let _ = sender.send_data(Bytes::from_static(b"\0asm")).await;
+            }
+        });
+        assert_eq!(read_body(body, 10).await.unwrap_err().status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[test]
+    fn path_segments_are_decoded() {
+        assert_eq!(percent_decode("canary%2D1").unwrap(), "canary-1");
+        assert_eq!(percent_decode("a%2Fb").unwrap(), "a/b");
+        assert_eq!(percent_decode("v1").unwrap(), "v1");
+        assert!(percent_decode("%2").is_none());
+        assert!(percent_decode("%zz").is_none());
+        assert!(percent_decode("%ff").is_none());
+    }
+}
@@ -1,33 +1,74 @@
-use std::cell::RefCell;
 use std::ffi::OsString;
 use std::net::{AddrParseError, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use structopt::StructOpt;
-use wasmy_vm::{load_wasm, WasmInfo};
 
-use crate::http;
+use crate::admin::{self, Admin, AdminAuth};
+use crate::config;
+use crate::http::{self, LimitRule, Router};
+use crate::ns::Identity;
+use crate::oci::{self, Image};
+use crate::rpc;
+use crate::store::{self, Source};
+use crate::tls::{self, MtlsConfig};
 // make sure submit runtime handlers
 #[allow(unused_imports)]use crate::runtime as _;
 
 #[derive(StructOpt, Debug, Clone)]
 pub struct ServeOpt {
-    /// wasm server file path
+    /// wasm server file path, OCI image layout directory or archive (`path[:tag]`), or image
+    /// reference of a registry (`registry/repository[:tag][@sha256:<hex>]`)
     pub(crate) wasm: String,
+    /// module name, default to the wasm file stem or the image title
+    #[structopt(long)]
+    pub(crate) name: Option<String>,
     /// HTTP listening address
     // #[structopt(long, default_value = "0.0.0.0:9090")]
     #[structopt(long)]
     pub(crate) http: Option<String>,
-    /// RPC listening address
-    // #[structopt(long, default_value = "0.0.0.0:9091")]
-    // #[structopt(long)]
-    // pub(crate) rpc: Option<String>,
+    /// admin API listening address
+    #[structopt(long)]
+    pub(crate) admin: Option<String>,
+    /// bearer token required by the admin API
+    #[structopt(long = "admin-token", env = "WASMESH_ADMIN_TOKEN", hide_env_values = true)]
+    pub(crate) admin_token: Option<String>,
+    /// caller identities allowed to use the admin API over mTLS, patterns ending with `*` match by prefix
+    #[structopt(long = "admin-identity")]
+    pub(crate) admin_identities: Vec<String>,
+    /// RPC address of a control plane to subscribe to, e.g. a `wasmesh-plane`
+    #[structopt(long)]
+    pub(crate) rpc: Option<String>,
+    /// name the pod reports to the control plane, default to `<module>-<process id>`
+    #[structopt(long = "pod-id")]
+    pub(crate) pod_id: Option<String>,
+    /// directory of the module store, for modules referenced by digest (`sha256:<hex>`),
+    /// default to `wasmesh/modules` in the user cache directory
+    #[structopt(long = "module-store", parse(from_os_str))]
+    pub(crate) module_store: Option<PathBuf>,
+    /// admin API of a pod to fetch modules missing from the store from, tried before the control plane
+    #[structopt(long = "module-peer", number_of_values = 1)]
+    pub(crate) module_peers: Vec<String>,
+    /// bearer token sent to the `--module-peer` pods, never the `--admin-token` of this pod
+    #[structopt(long = "module-peer-token", env = "WASMESH_MODULE_PEER_TOKEN", hide_env_values = true)]
+    pub(crate) module_peer_token: Option<String>,
     /// worker threads, default to lazy auto-detection (one thread per CPU core)
     #[structopt(long, default_value = "0")]
     pub(crate) threads: usize,
-    /// WASI pre-opened directory
+    /// namespace of the module, used to derive its workload identity
+    #[structopt(long, default_value = "default")]
+    pub(crate) namespace: String,
+    /// local CA directory, enables mutual TLS for inbound and outbound HTTP
+    #[structopt(long = "tls-ca-dir")]
+    pub(crate) tls_ca_dir: Option<String>,
+    /// pod config file (TOML), reloaded on SIGHUP
+    #[structopt(long, parse(from_os_str))]
+    pub(crate) config: Option<PathBuf>,
+    /// WASI pre-opened directory, refused: wasmy-vm cannot pass it to the module
     #[structopt(long = "dir", multiple = true, group = "wasi")]
     pub(crate) pre_opened_directories: Vec<String>,
-    /// Application arguments
+    /// Application arguments, refused: wasmy-vm cannot pass them to the module
     #[structopt(multiple = true, parse(from_os_str))]
     pub(crate) args: Vec<OsString>,
 }
@@ -37,9 +78,9 @@ impl ServeOpt {
     pub(crate) fn parse_http_addr(&self) -> Result<Option<SocketAddr>, AddrParseError> {
         Self::parse_addr(self.http.as_ref())
     }
-    // pub(crate) fn parse_rpc_addr(&self) -> Result<Option<SocketAddr>, AddrParseError> {
-    //     Self::parse_addr(self.rpc.as_ref())
-    // }
+    pub(crate) fn parse_admin_addr(&self) -> Result<Option<SocketAddr>, AddrParseError> {
+        Self::parse_addr(self.admin.as_ref())
+    }
     fn parse_addr(addr: Option<&String>) -> Result<Option<SocketAddr>, AddrParseError> {
         if addr.is_none() {
             return Ok(None)
@@ -51,17 +92,55 @@ impl ServeOpt {
                     .and_then(|a| Ok(SocketAddr::V6(a)))
         })?))
     }
-    pub(crate) fn get_name(&self) -> &String {
-        &self.wasm
+    pub(crate) fn get_name(&self) -> &str {
+        match &self.name {
+            Some(name) => name,
+            None => http::module_name(&self.wasm),
+        }
+    }
+    /// Serves the module of an image; returns the rate limits its config carries.
+    pub(crate) fn apply_image(&mut self, image: Image) -> Vec<LimitRule> {
+        println!("module image {}: {}", self.wasm, image.wasm);
+        self.name.get_or_insert(image.name);
+        self.wasm = image.wasm;
+        image.config.limits
+    }
+    pub(crate) fn get_pod_id(&self) -> String {
+        match &self.pod_id {
+            Some(id) => id.clone(),
+            None => format!("{}-{}", self.get_name(), std::process::id()),
+        }
+    }
+    pub(crate) fn get_identity(&self) -> Identity {
+        Identity::new(&self.namespace, self.get_name())
+    }
+    pub(crate) fn load_mtls(&self) -> anyhow::Result<Option<MtlsConfig>> {
+        match &self.tls_ca_dir {
+            Some(dir) => Ok(Some(MtlsConfig::load(Path::new(dir), self.get_identity())?)),
+            None => Ok(None),
+        }
     }
     pub(crate) fn get_wasm_path(&self) -> &String {
         &self.wasm
     }
-    pub(crate) fn get_preopen_dirs(&self) -> &Vec<String> {
-        &self.pre_opened_directories
+    /// Refuses `--dir` and module arguments: wasmy-vm instantiates modules from their path alone and
+    /// cannot pass either to them.
+    pub(crate) fn check_wasi(&self) -> anyhow::Result<()> {
+        let mut unsupported = Vec::new();
+        if !self.pre_opened_directories.is_empty() {
+            unsupported.push("--dir");
+        }
+        if !self.args.is_empty() {
+            unsupported.push("module arguments");
+        }
+        if !unsupported.is_empty() {
+            anyhow::bail!("{} cannot be passed to a module by wasmesh-pod", unsupported.join(" and "));
+        }
+        Ok(())
     }
-    pub(crate) fn to_args_unchecked(&self) -> impl IntoIterator<Item=&str> {
-        self.args.iter().map(|v| v.to_str().unwrap()).collect::<Vec<&str>>()
+    pub(crate) fn get_module_sources(&self) -> Vec<Source> {
+        let peers = self.module_peers.iter().cloned().map(Source::Peer);
+        peers.chain(self.rpc.clone().map(Source::Plane)).collect()
     }
     pub(crate) fn get_worker_threads(&self) -> usize {
         if self.threads > 0 {
@@ -75,41 +154,74 @@ impl ServeOpt {
     }
 }
 
-thread_local! {
-    static WASM_INFO: RefCell<WasmInfo> = RefCell::new(WasmInfo{wasm_path:"".to_string()});
-}
-
-pub fn serve(serve_options: ServeOpt) -> anyhow::Result<()> {
+pub fn serve(mut serve_options: ServeOpt) -> anyhow::Result<()> {
+    store::init(serve_options.module_store.clone(), serve_options.get_module_sources(), serve_options.module_peer_token.clone())?;
+    let image_limits = match oci::open(&serve_options.wasm)? {
+        Some(image) => serve_options.apply_image(image),
+        None => Vec::new(),
+    };
+    serve_options.check_wasi()?;
+    config::init(serve_options.config.clone(), image_limits)?;
+    http::init_backend(&config::current().limit_store)?;
+    let mtls = serve_options.load_mtls()?;
+    if let Some(ref mtls) = mtls {
+        println!("mTLS identity: {}", mtls.identity);
+        tls::install_mesh_client(mtls.client.clone());
+    }
     let mut builder = tokio::runtime::Builder::new_multi_thread();
     builder.worker_threads(serve_options.get_worker_threads());
     builder.enable_all()
            .build()?
         .block_on(async {
-            WASM_INFO.with(|wi| {
-                let info = WasmInfo { wasm_path: serve_options.get_wasm_path().clone() };
-                wi.replace(info.clone());
-                load_wasm(info).unwrap_or_else(|e| eprintln!("{}", e));
+            let router = Arc::new(Router::new(serve_options.get_name(), serve_options.get_wasm_path()));
+            router.update(&config::current().services).unwrap_or_else(|e| eprintln!("{}", e));
+            let admin = Arc::new(Admin {
+                router: router.clone(),
+                auth: AdminAuth {
+                    token: serve_options.admin_token.clone(),
+                    tls: mtls.as_ref().map(|c| c.server.clone()),
+                    identities: serve_options.admin_identities.clone(),
+                },
+                worker_threads: serve_options.get_worker_threads(),
             });
+            if let Some(addr) = &serve_options.rpc {
+                rpc::subscribe(router.clone(), addr.clone(), serve_options.get_pod_id());
+            }
+            let reload_router = router.clone();
+            tokio::spawn(config::reload_on_hangup(move |config| reload_router.update(&config.services)));
             tokio::join!(
                    async {
                        match serve_options.parse_http_addr() {
-                           Ok(Some(addr))  => http::serve(&WASM_INFO, addr).await.map_err(|e|{
+                           Ok(Some(addr))  => http::serve(router.clone(), addr, mtls.map(|c| c.server)).await.map_err(|e|{
+                               eprintln!("{}", e);
+                           }).unwrap(),
+                           Err(e) => eprintln!("{}", e),
+                           _ => (),
+                       }
+                   },
+                   async {
+                       match serve_options.parse_admin_addr() {
+                           Ok(Some(addr))  => admin::serve(admin.clone(), addr).await.map_err(|e|{
                                eprintln!("{}", e);
                            }).unwrap(),
                            Err(e) => eprintln!("{}", e),
                            _ => (),
                        }
                    },
-                   // async {
-                   //     match serve_options.parse_rpc_addr() {
-                   //         Ok(Some(addr))  => RpcTransport::serve(addr).await.map_err(|e|{
-                   //             eprintln!("{}", e);
-                   //         }).unwrap(),
-                   //         Err(e) => eprintln!("{}", e),
-                   //         _ => (),
-                   //     }
-                   // },
                );
             Ok(())
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wasi_settings_are_refused() {
+        let opt = |args: &[&str]| ServeOpt::from_iter(["serve"].iter().chain(args));
+        assert!(opt(&["simple.wasm"]).check_wasi().is_ok());
+        let err = opt(&["--dir=/tmp", "simple.wasm", "--", "-k=v"]).check_wasi().unwrap_err();
+        assert_eq!(err.to_string(), "--dir and module arguments cannot be passed to a module by wasmesh-pod");
+    }
+}
@@ -1,58 +1,370 @@
-use std::thread;
-use std::time::Duration;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 
+use hdrhistogram::Histogram;
+use hyper::{Body, Client, Method, Request, Uri};
+use hyper::client::HttpConnector;
+use serde::Serialize;
 use structopt::{clap::AppSettings, StructOpt};
+use tokio::sync::mpsc;
 
 #[derive(StructOpt, Debug, Clone)]
 #[structopt(global_settings = & [AppSettings::VersionlessSubcommands, AppSettings::ColorAuto, AppSettings::ColoredHelp])]
 struct BenchArgs {
-    /// Number of requests to perform
+    /// Number of requests to perform, ignored with --duration
     #[structopt(long, short = "n", default_value = "10000")]
     requests: u64,
-    /// Number of multiple requests to make at a time
+    /// Number of multiple requests to make at a time, each on its own keep-alive connection
     #[structopt(long, short = "c", default_value = "10")]
     concurrency: u64,
+    /// Run for this long instead of a number of requests, e.g. 30s, 2m
+    #[structopt(long, short = "d", parse(try_from_str = parse_duration))]
+    duration: Option<Duration>,
+    /// Send this many requests per second in total instead of as fast as possible
+    #[structopt(long, short = "r", parse(try_from_str = parse_rate))]
+    rate: Option<f64>,
+    /// HTTP method
+    #[structopt(long, short = "m", default_value = "GET")]
+    method: String,
+    /// Request header, e.g. -H 'content-type: application/json'
+    #[structopt(long = "header", short = "H")]
+    headers: Vec<String>,
+    /// Request body
+    #[structopt(long, short = "b", default_value = "")]
+    body: String,
+    /// Timeout of each request, e.g. 500ms, 5s
+    #[structopt(long, default_value = "30s", parse(try_from_str = parse_duration))]
+    timeout: Duration,
+    /// Print the report as JSON
+    #[structopt(long)]
+    json: bool,
+    /// Exit with an error when more requests than this got no response, e.g. timed out
+    #[structopt(long, default_value = "0")]
+    max_errors: u64,
     /// HTTP URL: http://hostname:port/path
     url: String,
 }
 
-fn main() {
+fn parse_duration(s: &str) -> Result<Duration, String> {
+    let (num, unit) = s.find(|c: char| !c.is_ascii_digit() && c != '.').map_or((s, "s"), |i| s.split_at(i));
+    let num = f64::from_str(num).map_err(|e| format!("invalid duration {:?}: {}", s, e))?;
+    let secs = match unit {
+        "ms" => num / 1000.0,
+        "s" => num,
+        "m" => num * 60.0,
+        "h" => num * 3600.0,
+        _ => return Err(format!("invalid duration unit {:?}, expected ms, s, m or h", unit)),
+    };
+    Duration::try_from_secs_f64(secs).map_err(|e| format!("invalid duration {:?}: {}", s, e))
+}
+
+/// Highest `--rate`, one request per nanosecond, the resolution of the pacer.
+const MAX_RATE: f64 = 1e9;
+
+fn parse_rate(s: &str) -> Result<f64, String> {
+    let rate = f64::from_str(s).map_err(|e| format!("invalid rate {:?}: {}", s, e))?;
+    // a tiny rate overflows the interval, a huge one rounds it to zero
+    if !(rate <= MAX_RATE && Duration::try_from_secs_f64(1.0 / rate).is_ok_and(|d| !d.is_zero())) {
+        return Err(format!("invalid rate {:?}, expected more than 0 and at most {}", s, MAX_RATE));
+    }
+    Ok(rate)
+}
+
+/// Results of one worker, merged into the report.
+struct Stats {
+    latency: Histogram<u64>,
+    status_codes: BTreeMap<u16, u64>,
+    errors: BTreeMap<String, u64>,
+}
+
+impl Stats {
+    fn new() -> Self {
+        Stats {
+            // microseconds, up to one hour
+            latency: Histogram::new_with_bounds(1, 3_600_000_000, 3).unwrap(),
+            status_codes: BTreeMap::new(),
+            errors: BTreeMap::new(),
+        }
+    }
+    fn merge(&mut self, other: Stats) {
+        self.latency.add(other.latency).unwrap();
+        for (code, n) in other.status_codes {
+            *self.status_codes.entry(code).or_default() += n;
+        }
+        for (kind, n) in other.errors {
+            *self.errors.entry(kind).or_default() += n;
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Report {
+    url: String,
+    concurrency: u64,
+    target_rate: Option<f64>,
+    duration_secs: f64,
+    requests: u64,
+    succeeded: u64,
+    failed: u64,
+    requests_per_sec: f64,
+    latency_ms: Latency,
+    status_codes: BTreeMap<u16, u64>,
+    errors: BTreeMap<String, u64>,
+}
+
+#[derive(Serialize)]
+struct Latency {
+    min: f64,
+    mean: f64,
+    p50: f64,
+    p90: f64,
+    p99: f64,
+    max: f64,
+}
+
+impl Report {
+    fn new(args: &BenchArgs, elapsed: Duration, stats: &Stats) -> Self {
+        let requests = stats.latency.len();
+        let succeeded = stats.status_codes.iter().filter(|(code, _)| **code < 400).map(|(_, n)| n).sum();
+        let ms = |micros: u64| micros as f64 / 1000.0;
+        let h = &stats.latency;
+        Report {
+            url: args.url.clone(),
+            concurrency: args.concurrency,
+            target_rate: args.rate,
+            duration_secs: elapsed.as_secs_f64(),
+            requests,
+            succeeded,
+            failed: requests - succeeded,
+            requests_per_sec: requests as f64 / elapsed.as_secs_f64(),
+            latency_ms: Latency {
+                min: ms(h.min()),
+                mean: h.mean() / 1000.0,
+                p50: ms(h.value_at_quantile(0.5)),
+                p90: ms(h.value_at_quantile(0.9)),
+                p99: ms(h.value_at_quantile(0.99)),
+                max: ms(h.max()),
+            },
+            status_codes: stats.status_codes.clone(),
+            errors: stats.errors.clone(),
+        }
+    }
+    /// Requests that got no response.
+    fn error_count(&self) -> u64 {
+        self.errors.values().sum()
+    }
+    fn print(&self) {
+        println!("URL:\t\t\t{}", self.url);
+        println!("Concurrency Level:\t{}", self.concurrency);
+        if let Some(rate) = self.target_rate {
+            println!("Target rate:\t\t{:.1} [#/sec]", rate);
+        }
+        println!("Time taken for tests:\t{:.3} seconds", self.duration_secs);
+        println!("Complete requests:\t{}", self.requests);
+        println!("Failed requests:\t{}", self.failed);
+        println!("Requests per second:\t{:.3} [#/sec] (mean)", self.requests_per_sec);
+        println!();
+        println!("Latency (ms):");
+        let l = &self.latency_ms;
+        println!("  min {:.3}  mean {:.3}  p50 {:.3}  p90 {:.3}  p99 {:.3}  max {:.3}", l.min, l.mean, l.p50, l.p90, l.p99, l.max);
+        if !self.status_codes.is_empty() {
+            println!();
+            println!("Status codes:");
+            for (code, n) in &self.status_codes {
+                println!("  {}\t{}", code, n);
+            }
+        }
+        if !self.errors.is_empty() {
+            println!();
+            println!("Errors:");
+            for (kind, n) in &self.errors {
+                println!("  {}\t{}", kind, n);
+            }
+        }
+    }
+}
+
+/// What a worker should do next.
+enum Plan {
+    /// stop at the deadline
+    Duration(Instant),
+    /// stop once this many requests were taken
+    Count(Arc<AtomicU64>, u64),
+}
+
+impl Plan {
+    fn next(&self) -> bool {
+        match self {
+            Plan::Duration(deadline) => Instant::now() < *deadline,
+            Plan::Count(taken, total) => taken.fetch_add(1, Ordering::Relaxed) < *total,
+        }
+    }
+}
+
+struct Bench {
+    client: Client<HttpConnector>,
+    uri: Uri,
+    method: Method,
+    headers: Vec<(String, String)>,
+    body: hyper::body::Bytes,
+    timeout: Duration,
+}
+
+impl Bench {
+    async fn request(&self, stats: &mut Stats, start: Instant) {
+        let mut builder = Request::builder().method(self.method.clone()).uri(self.uri.clone());
+        for (name, value) in &self.headers {
+            builder = builder.header(name, value);
+        }
+        let req = builder.body(Body::from(self.body.clone())).unwrap();
+        let r = tokio::time::timeout(self.timeout, async {
+            let resp = self.client.request(req).await?;
+            let status = resp.status().as_u16();
+            // read the whole body so the connection can be reused
+            hyper::body::to_bytes(resp.into_body()).await?;
+            Ok::<_, hyper::Error>(status)
+        }).await;
+        let _ = stats.latency.record((start.elapsed().as_micros() as u64).max(1));
+        match r {
+            Ok(Ok(status)) => *stats.status_codes.entry(status).or_default() += 1,
+            Ok(Err(e)) => *stats.errors.entry(error_kind(&e).to_string()).or_default() += 1,
+            Err(_) => *stats.errors.entry("timeout".to_string()).or_default() += 1,
+        }
+    }
+}
+
+fn error_kind(e: &hyper::Error) -> &'static str {
+    if e.is_connect() {
+        "connect"
+    } else if e.is_incomplete_message() || e.is_closed() {
+        "closed"
+    } else {
+        "other"
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
     let args: BenchArgs = BenchArgs::from_args();
-    println!("{:?}", args);
-    let mut tasks = vec![];
-    for _ in 0..args.concurrency {
-        let args_clone = args.clone();
-        let task = thread::spawn(move || {
-            let mut fail_count = 0u64;
-            let t = std::time::Instant::now();
-            for _ in 0..args_clone.requests {
-                if !do_request(&args_clone) {
-                    fail_count += 1;
+    let uri: Uri = args.url.parse()?;
+    if uri.scheme_str() != Some("http") {
+        anyhow::bail!("only http:// urls are supported: {}", args.url);
+    }
+    let headers = args.headers.iter().map(|h| match h.split_once(':') {
+        Some((name, value)) => Ok((name.trim().to_string(), value.trim().to_string())),
+        None => Err(anyhow::anyhow!("invalid header {:?}, expected 'name: value'", h)),
+    }).collect::<anyhow::Result<Vec<_>>>()?;
+    let concurrency = args.concurrency.max(1);
+    let client = Client::builder()
+        .pool_max_idle_per_host(concurrency as usize)
+        .build_http();
+    let bench = Arc::new(Bench {
+        client,
+        uri,
+        method: Method::from_bytes(args.method.to_ascii_uppercase().as_bytes())?,
+        headers,
+        body: args.body.clone().into(),
+        timeout: args.timeout,
+    });
+
+    let start = Instant::now();
+    let plan = Arc::new(match args.duration {
+        Some(d) => Plan::Duration(start + d),
+        None => Plan::Count(Arc::new(AtomicU64::new(0)), args.requests),
+    });
+    // With a target rate, a pacer hands out send times and latency is measured
+    // from when a request was due, so a slow server cannot hide its queueing.
+    let ticks = args.rate.map(|rate| {
+        let (tx, rx) = mpsc::channel::<Instant>(concurrency as usize);
+        let plan = plan.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs_f64(1.0 / rate));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Burst);
+            while plan.next() {
+                let due = interval.tick().await.into_std();
+                if tx.send(due).await.is_err() {
+                    break;
                 }
             }
-            (fail_count, t.elapsed())
         });
-        tasks.push(task);
+        Arc::new(tokio::sync::Mutex::new(rx))
+    });
+
+    let total = Arc::new(Mutex::new(Stats::new()));
+    let mut workers = vec![];
+    for _ in 0..concurrency {
+        let (bench, plan, ticks, total) = (bench.clone(), plan.clone(), ticks.clone(), total.clone());
+        workers.push(tokio::spawn(async move {
+            let mut stats = Stats::new();
+            loop {
+                let due = match &ticks {
+                    Some(ticks) => match ticks.lock().await.recv().await {
+                        Some(due) => due,
+                        None => break,
+                    },
+                    None if plan.next() => Instant::now(),
+                    None => break,
+                };
+                bench.request(&mut stats, due).await;
+            }
+            total.lock().unwrap().merge(stats);
+        }));
+    }
+    for worker in workers {
+        worker.await?;
     }
 
-    let mut fail_count = 0u64;
-    let mut cast = Duration::new(0, 0);
-    for x in tasks {
-        let r = x.join().unwrap();
-        fail_count += r.0;
-        if r.1 > cast {
-            cast = r.1;
+    let elapsed = start.elapsed();
+    let stats = total.lock().unwrap();
+    let report = Report::new(&args, elapsed, &stats);
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        report.print();
+    }
+    if report.error_count() > args.max_errors {
+        anyhow::bail!("{} requests got no response, more than --max-errors={}", report.error_count(), args.max_errors);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_args() {
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("1.5m"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("2"), Ok(Duration::from_secs(2)));
+        assert!(parse_duration("5d").is_err());
+        assert!(parse_duration("-1s").is_err());
+
+        assert_eq!(parse_rate("100"), Ok(100.0));
+        assert_eq!(parse_rate("1e9"), Ok(MAX_RATE));
+        for rate in ["0", "-1", "1e10", "1e-300", "nan", "x"] {
+            assert!(parse_rate(rate).is_err(), "{}", rate);
         }
     }
-    let total_requests = args.requests * args.concurrency;
 
-    println!("Concurrency Level:\t{:}", args.concurrency);
-    println!("Time taken for tests:\t{:.3} seconds", cast.as_secs_f64());
-    println!("Complete requests:\t{}", total_requests);
-    println!("Failed requests:\t{}", fail_count);
-    println!("Requests per second:\t{:.3} [#/sec] (mean)", total_requests as f64 / cast.as_secs_f64());
-}
+    #[test]
+    fn report_counts_failures() {
+        let args = BenchArgs::from_iter(["wasmesh-bench", "http://127.0.0.1:9090/"]);
+        let mut stats = Stats::new();
+        for (status, micros) in [(200, 1000), (204, 2000), (404, 3000), (503, 4000)] {
+            stats.latency.record(micros).unwrap();
+            *stats.status_codes.entry(status).or_default() += 1;
+        }
+        stats.latency.record(5000).unwrap();
+        stats.errors.insert("timeout".to_string(), 1);
 
-fn do_request(args: &BenchArgs) -> bool {
-    false
+        let report = Report::new(&args, Duration::from_secs(1), &stats);
+        assert_eq!((report.requests, report.succeeded, report.failed), (5, 2, 3));
+        assert_eq!(report.error_count(), 1);
+        assert_eq!(report.requests_per_sec, 5.0);
+        assert_eq!(report.latency_ms.min, 1.0);
+    }
 }
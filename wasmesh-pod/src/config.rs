@@ -0,0 +1,153 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock, RwLock};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::http::{AuthzPolicy, LimitRule, LimitStore, ServiceConfig, validate_limits};
+use crate::signing::TrustedKey;
+
+static CONFIG: OnceLock<ConfigSource> = OnceLock::new();
+
+/// Whether the control plane owns the services, limits and authz policy.
+static PLANE_OWNED: AtomicBool = AtomicBool::new(false);
+
+/// Pod configuration, loaded from the TOML file given by `--config`.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct PodConfig {
+    /// authorization policy evaluated before the guest is invoked
+    pub(crate) authz: AuthzPolicy,
+    /// rate limits, every matching rule must grant a token
+    pub(crate) limits: Vec<LimitRule>,
+    /// storage of the rate limit buckets, only read at startup
+    pub(crate) limit_store: LimitStore,
+    /// services mounted in front of the default module, tried in order
+    pub(crate) services: Vec<ServiceConfig>,
+    /// keys modules must be signed with, any module loads when empty
+    pub(crate) trusted_keys: Vec<TrustedKey>,
+}
+
+impl PodConfig {
+    pub(crate) fn parse(text: &str) -> anyhow::Result<Self> {
+        let config: PodConfig = toml::from_str(text)?;
+        validate_limits(&config.limits)?;
+        for key in &config.trusted_keys {
+            key.verifying_key()?;
+        }
+        Ok(config)
+    }
+}
+
+struct ConfigSource {
+    path: Option<PathBuf>,
+    // rate limits of the module image, used when the file has none
+    image_limits: Vec<LimitRule>,
+    current: RwLock<Arc<PodConfig>>,
+}
+
+/// Loads the pod configuration, falling back to the defaults without a file.
+pub(crate) fn init(path: Option<PathBuf>, image_limits: Vec<LimitRule>) -> anyhow::Result<()> {
+    let config = read(path.as_ref(), &image_limits)?;
+    CONFIG.set(ConfigSource { path, image_limits, current: RwLock::new(Arc::new(config)) })
+          .map_err(|_| anyhow::anyhow!("pod config is already initialized"))
+}
+
+/// Returns a snapshot of the current pod configuration.
+pub(crate) fn current() -> Arc<PodConfig> {
+    match CONFIG.get() {
+        Some(source) => source.current.read().unwrap().clone(),
+        None => Arc::new(PodConfig::default()),
+    }
+}
+
+/// Changes the current configuration, until it is reloaded from the file.
+pub(crate) fn update<F: FnOnce(&mut PodConfig)>(f: F) {
+    let source = CONFIG.get_or_init(|| ConfigSource {
+        path: None,
+        image_limits: Vec::new(),
+        current: RwLock::new(Arc::new(PodConfig::default())),
+    });
+    let mut current = source.current.write().unwrap();
+    let mut config = PodConfig::clone(&current);
+    f(&mut config);
+    *current = Arc::new(config);
+}
+
+/// Hands the services, limits and authz policy over to the control plane,
+/// reloads leave them alone from then on.
+pub(crate) fn set_plane_owned() {
+    PLANE_OWNED.store(true, Ordering::Relaxed);
+}
+
+/// Whether the services come from the control plane rather than the file.
+pub(crate) fn plane_owned() -> bool {
+    PLANE_OWNED.load(Ordering::Relaxed)
+}
+
+/// Re-reads the configuration file, keeping the old configuration on error.
+pub(crate) fn reload() -> anyhow::Result<()> {
+    let source = match CONFIG.get() {
+        Some(source) => source,
+        None => return Ok(()),
+    };
+    if let Some(path) = &source.path {
+        let mut config = read(Some(path), &source.image_limits)?;
+        let mut current = source.current.write().unwrap();
+        if plane_owned() {
+            config.services = current.services.clone();
+            config.limits = current.limits.clone();
+            config.authz = current.authz.clone();
+        }
+        *current = Arc::new(config);
+    }
+    Ok(())
+}
+
+/// Reloads the configuration whenever the process receives `SIGHUP`,
+/// then passes the new configuration to `on_reload`, on the blocking
+/// threads. Services owned by the control plane are not passed, see
+/// [`plane_owned`].
+#[cfg(unix)]
+pub(crate) async fn reload_on_hangup<F>(on_reload: F)
+    where F: Fn(&PodConfig) -> anyhow::Result<()> + Send + Sync + 'static {
+    use tokio::signal::unix::{signal, SignalKind};
+    let mut hangup = match signal(SignalKind::hangup()) {
+        Ok(s) => s,
+        Err(e) => return eprintln!("config: cannot listen for SIGHUP: {}", e),
+    };
+    let on_reload = Arc::new(on_reload);
+    while hangup.recv().await.is_some() {
+        let on_reload = on_reload.clone();
+        let r = tokio::task::spawn_blocking(move || {
+            reload().and_then(|_| if plane_owned() { Ok(()) } else { on_reload(&current()) })
+        });
+        match r.await.map_err(anyhow::Error::from).and_then(|r| r) {
+            Ok(()) => log_info!("config: reloaded"),
+            Err(e) => log_error!("config: reload failed: {:#}", e),
+        }
+    }
+}
+
+#[cfg(not(unix))]
+pub(crate) async fn reload_on_hangup<F>(_on_reload: F)
+    where F: Fn(&PodConfig) -> anyhow::Result<()> + Send + Sync + 'static {}
+
+fn read(path: Option<&PathBuf>, image_limits: &[LimitRule]) -> anyhow::Result<PodConfig> {
+    let mut config = match path {
+        Some(path) => load(path)?,
+        None => PodConfig::default(),
+    };
+    if config.limits.is_empty() {
+        validate_limits(image_limits).context("image limits")?;
+        config.limits = image_limits.to_vec();
+    }
+    Ok(config)
+}
+
+fn load(path: &PathBuf) -> anyhow::Result<PodConfig> {
+    let text = fs::read_to_string(path).with_context(|| format!("read config {}", path.display()))?;
+    PodConfig::parse(&text).with_context(|| format!("parse config {}", path.display()))
+}
@@ -0,0 +1,553 @@
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use fs2::FileExt;
+use hyper::http::request::Parts;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::http::{glob_match, normalize_path};
+use crate::store::sha256_hex;
+
+static BACKEND: OnceLock<Box<dyn LimitBackend>> = OnceLock::new();
+
+/// Token-bucket rate limit applied before the guest is invoked.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct LimitRule {
+    /// what a bucket is keyed by
+    pub(crate) key: LimitKey,
+    /// only requests whose path matches this pattern are limited
+    #[serde(default)]
+    pub(crate) path: Option<String>,
+    /// tokens refilled per second
+    pub(crate) rate: f64,
+    /// bucket capacity, defaults to `rate`
+    #[serde(default)]
+    pub(crate) burst: Option<f64>,
+}
+
+impl LimitRule {
+    /// Rejects rates and bursts a bucket cannot be filled with.
+    pub(crate) fn validate(&self) -> anyhow::Result<()> {
+        if !(self.rate.is_finite() && self.rate > 0.0) {
+            anyhow::bail!("limit {:?}: rate must be a positive number, got {}", self.key, self.rate);
+        }
+        match self.burst {
+            Some(burst) if !(burst.is_finite() && burst > 0.0) => {
+                anyhow::bail!("limit {:?}: burst must be a positive number, got {}", self.key, burst)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Checks every rule, see [`LimitRule::validate`].
+pub(crate) fn validate_limits(rules: &[LimitRule]) -> anyhow::Result<()> {
+    rules.iter().try_for_each(LimitRule::validate)
+}
+
+/// Key of a token bucket: `module`, `route`, `client_ip` or `header:<name>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum LimitKey {
+    /// one bucket for the whole module
+    Module,
+    /// one bucket per matched path pattern
+    Route,
+    /// one bucket per client IP
+    ClientIp,
+    /// one bucket per value of the header, requests without it are not limited
+    Header(String),
+}
+
+impl<'de> Deserialize<'de> for LimitKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        match s.as_str() {
+            "module" => Ok(LimitKey::Module),
+            "route" => Ok(LimitKey::Route),
+            "client_ip" => Ok(LimitKey::ClientIp),
+            _ => match s.strip_prefix("header:") {
+                Some(name) if !name.is_empty() => Ok(LimitKey::Header(name.to_ascii_lowercase())),
+                _ => Err(serde::de::Error::custom(format!("unknown limit key: {}", s))),
+            }
+        }
+    }
+}
+
+impl Serialize for LimitKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            LimitKey::Module => serializer.serialize_str("module"),
+            LimitKey::Route => serializer.serialize_str("route"),
+            LimitKey::ClientIp => serializer.serialize_str("client_ip"),
+            LimitKey::Header(name) => serializer.serialize_str(&format!("header:{}", name)),
+        }
+    }
+}
+
+/// Where token buckets are stored.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(tag = "kind", rename_all = "lowercase", deny_unknown_fields)]
+pub(crate) enum LimitStore {
+    /// buckets live in this pod only
+    #[default]
+    Memory,
+    /// buckets are files in a directory shared by all pods of the host
+    File { dir: PathBuf },
+}
+
+/// A token wanted from a bucket.
+#[derive(Debug, Clone)]
+pub(crate) struct Acquire {
+    pub(crate) bucket: String,
+    pub(crate) rate: f64,
+    pub(crate) burst: f64,
+}
+
+/// Storage of token buckets.
+pub(crate) trait LimitBackend: Send + Sync {
+    /// Takes one token from every bucket, or none of them and returns how
+    /// long until all have one.
+    fn acquire(&self, wanted: &[Acquire]) -> Result<(), Duration>;
+    /// Whether `acquire` blocks on I/O, and must run off the async workers.
+    fn is_blocking(&self) -> bool {
+        false
+    }
+}
+
+/// Initializes the bucket storage, the store cannot be changed by a reload.
+pub(crate) fn init_backend(store: &LimitStore) -> anyhow::Result<()> {
+    let backend: Box<dyn LimitBackend> = match store {
+        LimitStore::Memory => Box::new(MemoryBackend::default()),
+        LimitStore::File { dir } => Box::new(FileBackend::new(dir.clone())?),
+    };
+    BACKEND.set(backend).map_err(|_| anyhow::anyhow!("rate limit store is already initialized"))
+}
+
+/// Checks the request against all matching rules, a token is taken from
+/// their buckets only when every one of them has one.
+///
+/// Returns the suggested `Retry-After` when any bucket is empty.
+pub(crate) async fn check_limits(rules: &[LimitRule], module: &str, remote_addr: Option<SocketAddr>, req: &Parts) -> Result<(), Duration> {
+    let backend = match BACKEND.get() {
+        Some(backend) => backend,
+        None => return Ok(()),
+    };
+    let wanted = wanted_tokens(rules, module, remote_addr, req);
+    if wanted.is_empty() {
+        return Ok(());
+    }
+    if backend.is_blocking() {
+        // fail open, like a broken store
+        return tokio::task::spawn_blocking(move || backend.acquire(&wanted)).await.unwrap_or(Ok(()));
+    }
+    backend.acquire(&wanted)
+}
+
+fn wanted_tokens(rules: &[LimitRule], module: &str, remote_addr: Option<SocketAddr>, req: &Parts) -> Vec<Acquire> {
+    let mut wanted = Vec::new();
+    // matched like the authz rules; a path that cannot be normalized is limited by every rule
+    let normalized = normalize_path(req.uri.path());
+    for (index, rule) in rules.iter().enumerate() {
+        if let (Some(pattern), Some(path)) = (&rule.path, &normalized) {
+            if !glob_match(pattern, path) {
+                continue;
+            }
+        }
+        let key = match &rule.key {
+            LimitKey::Module => module.to_string(),
+            LimitKey::Route => rule.path.clone().unwrap_or_default(),
+            LimitKey::ClientIp => match remote_addr {
+                Some(addr) => addr.ip().to_string(),
+                None => continue,
+            },
+            LimitKey::Header(name) => match req.headers.get(name).and_then(|v| v.to_str().ok()) {
+                Some(value) => value.to_string(),
+                None => continue,
+            },
+        };
+        let bucket = format!("{}/{}/{}", module, index, key);
+        wanted.push(Acquire { bucket, rate: rule.rate, burst: rule.burst.unwrap_or(rule.rate) });
+    }
+    wanted
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    // seconds since the UNIX epoch
+    updated: f64,
+}
+
+impl Bucket {
+    fn refill(&mut self, now: f64, rate: f64, burst: f64) {
+        self.tokens = (self.tokens + (now - self.updated).max(0.0) * rate).min(burst);
+        self.updated = now;
+    }
+
+    /// How long until the bucket has a token, `None` when it has one.
+    fn wait(&self, rate: f64) -> Option<Duration> {
+        if self.tokens >= 1.0 {
+            return None;
+        }
+        let wait = (1.0 - self.tokens) / rate;
+        if !(wait.is_finite() && wait >= 0.0) {
+            return Some(Duration::from_secs(u32::MAX as u64));
+        }
+        Some(Duration::try_from_secs_f64(wait).unwrap_or(Duration::from_secs(u32::MAX as u64)))
+    }
+
+    #[cfg(test)]
+    fn take(&mut self, now: f64, rate: f64, burst: f64) -> Result<(), Duration> {
+        take_all(now, &mut [(self, rate, burst)])
+    }
+}
+
+/// Refills the buckets, then takes a token from each if all have one.
+fn take_all(now: f64, buckets: &mut [(&mut Bucket, f64, f64)]) -> Result<(), Duration> {
+    for (bucket, rate, burst) in buckets.iter_mut() {
+        bucket.refill(now, *rate, *burst);
+    }
+    if let Some(wait) = buckets.iter().filter_map(|(bucket, rate, _)| bucket.wait(*rate)).max() {
+        return Err(wait);
+    }
+    for (bucket, _, _) in buckets.iter_mut() {
+        bucket.tokens -= 1.0;
+    }
+    Ok(())
+}
+
+fn now_secs() -> f64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs_f64()
+}
+
+/// Buckets a [`MemoryBackend`] keeps before evicting.
+const MAX_BUCKETS: usize = 100_000;
+
+pub(crate) struct MemoryBackend {
+    buckets: Mutex<HashMap<String, MemoryBucket>>,
+    max_buckets: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct MemoryBucket {
+    bucket: Bucket,
+    rate: f64,
+    burst: f64,
+}
+
+impl Default for MemoryBackend {
+    fn default() -> Self {
+        MemoryBackend { buckets: Mutex::default(), max_buckets: MAX_BUCKETS }
+    }
+}
+
+impl MemoryBucket {
+    /// A full bucket is no different from a new one.
+    fn is_full(&self, now: f64) -> bool {
+        self.bucket.tokens + (now - self.bucket.updated).max(0.0) * self.rate >= self.burst
+    }
+}
+
+/// Makes room for a bucket: drops the full ones, then, when too many
+/// clients are still limited, the least recently used half.
+fn evict(buckets: &mut HashMap<String, MemoryBucket>, max_buckets: usize, now: f64) {
+    buckets.retain(|_, b| !b.is_full(now));
+    if buckets.len() < max_buckets {
+        return;
+    }
+    let mut updated: Vec<f64> = buckets.values().map(|b| b.bucket.updated).collect();
+    let middle = updated.len() / 2;
+    let (_, median, _) = updated.select_nth_unstable_by(middle, f64::total_cmp);
+    let median = *median;
+    buckets.retain(|_, b| b.bucket.updated > median);
+}
+
+impl LimitBackend for MemoryBackend {
+    fn acquire(&self, wanted: &[Acquire]) -> Result<(), Duration> {
+        let now = now_secs();
+        let mut buckets = self.buckets.lock().unwrap();
+        if buckets.len() + wanted.len() > self.max_buckets {
+            evict(&mut buckets, self.max_buckets.saturating_sub(wanted.len()), now);
+        }
+        let mut states: Vec<Bucket> = wanted.iter()
+                                            .map(|w| buckets.get(&w.bucket).map_or(Bucket { tokens: w.burst, updated: now }, |b| b.bucket))
+                                            .collect();
+        let mut taking: Vec<_> = states.iter_mut().zip(wanted).map(|(b, w)| (b, w.rate, w.burst)).collect();
+        let r = take_all(now, &mut taking);
+        for (w, bucket) in wanted.iter().zip(states) {
+            buckets.insert(w.bucket.clone(), MemoryBucket { bucket, rate: w.rate, burst: w.burst });
+        }
+        r
+    }
+}
+
+/// How often a [`FileBackend`] removes the files of full buckets.
+const PRUNE_INTERVAL: f64 = 60.0;
+
+/// Keeps every bucket in its own file, guarded by an exclusive file lock.
+///
+/// A file holds `tokens updated rate burst`.
+pub(crate) struct FileBackend {
+    dir: PathBuf,
+    // when the files were last pruned, seconds since the UNIX epoch
+    pruned: Mutex<f64>,
+}
+
+impl FileBackend {
+    pub(crate) fn new(dir: PathBuf) -> anyhow::Result<Self> {
+        fs::create_dir_all(&dir)?;
+        Ok(FileBackend { dir, pruned: Mutex::new(now_secs()) })
+    }
+    fn path(&self, bucket: &str) -> PathBuf {
+        self.dir.join(format!("{}.bucket", sha256_hex(bucket.as_bytes())))
+    }
+    /// Removes the files of full buckets, which are no different from new
+    /// ones. Files locked by another pod are left for a later round.
+    fn prune(&self, now: f64) -> std::io::Result<()> {
+        {
+            let mut pruned = self.pruned.lock().unwrap();
+            if now - *pruned < PRUNE_INTERVAL {
+                return Ok(());
+            }
+            *pruned = now;
+        }
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().is_none_or(|ext| ext != "bucket") {
+                continue;
+            }
+            let mut file = match OpenOptions::new().read(true).write(true).open(&path) {
+                Ok(file) => file,
+                Err(_) => continue,
+            };
+            if file.try_lock_exclusive().is_err() || !is_linked(&file, &path) {
+                continue;
+            }
+            let mut text = String::new();
+            file.read_to_string(&mut text)?;
+            let full = match parse_bucket(&text) {
+                Some((bucket, Some((rate, burst)))) => bucket.tokens + (now - bucket.updated).max(0.0) * rate >= burst,
+                // written without its rate, or broken
+                _ => true,
+            };
+            if full {
+                fs::remove_file(&path)?;
+            }
+            file.unlock()?;
+        }
+        Ok(())
+    }
+    fn acquire_locked(&self, wanted: &[Acquire]) -> std::io::Result<Result<(), Duration>> {
+        // Lock in path order, so pods taking the same buckets cannot deadlock.
+        let mut order: Vec<(PathBuf, usize)> = wanted.iter().enumerate().map(|(i, w)| (self.path(&w.bucket), i)).collect();
+        order.sort();
+        if order.windows(2).any(|w| w[0].0 == w[1].0) {
+            return Err(std::io::Error::other("two buckets share a file"));
+        }
+        let mut files = Vec::with_capacity(order.len());
+        for (path, i) in order {
+            files.push((i, open_locked(&path)?));
+        }
+        files.sort_by_key(|(i, _)| *i);
+        let now = now_secs();
+        let mut states = Vec::with_capacity(files.len());
+        for ((_, file), w) in files.iter_mut().zip(wanted) {
+            let mut text = String::new();
+            file.read_to_string(&mut text)?;
+            states.push(parse_bucket(&text).map_or(Bucket { tokens: w.burst, updated: now }, |(bucket, _)| bucket));
+        }
+        let mut taking: Vec<_> = states.iter_mut().zip(wanted).map(|(b, w)| (b, w.rate, w.burst)).collect();
+        let r = take_all(now, &mut taking);
+        for (((_, file), state), w) in files.iter_mut().zip(&states).zip(wanted) {
+            file.seek(SeekFrom::Start(0))?;
+            file.set_len(0)?;
+            write!(file, "{} {} {} {}", state.tokens, state.updated, w.rate, w.burst)?;
+        }
+        for (_, file) in &files {
+            file.unlock()?;
+        }
+        Ok(r)
+    }
+}
+
+/// Opens and locks the file of a bucket, again when a prune removed it
+/// while waiting for the lock.
+fn open_locked(path: &Path) -> std::io::Result<File> {
+    loop {
+        let file = OpenOptions::new().read(true).write(true).create(true).truncate(false).open(path)?;
+        file.lock_exclusive()?;
+        if is_linked(&file, path) {
+            return Ok(file);
+        }
+    }
+}
+
+/// Whether `path` still names the open file.
+#[cfg(unix)]
+fn is_linked(file: &File, path: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (file.metadata(), fs::metadata(path)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+fn is_linked(_file: &File, path: &Path) -> bool {
+    path.exists()
+}
+
+/// Parses a bucket file, with the rate and burst it was last taken at if recorded.
+fn parse_bucket(text: &str) -> Option<(Bucket, Option<(f64, f64)>)> {
+    let v = text.split_whitespace().map(|v| v.parse::<f64>()).collect::<Result<Vec<_>, _>>().ok()?;
+    match v[..] {
+        [tokens, updated] => Some((Bucket { tokens, updated }, None)),
+        [tokens, updated, rate, burst] => Some((Bucket { tokens, updated }, Some((rate, burst)))),
+        _ => None,
+    }
+}
+
+impl LimitBackend for FileBackend {
+    fn acquire(&self, wanted: &[Acquire]) -> Result<(), Duration> {
+        // A broken store must not take the service down, so fail open.
+        let r = self.acquire_locked(wanted).unwrap_or_else(|e| {
+            log_error!("rate limit: file store error: {}", e);
+            Ok(())
+        });
+        if let Err(e) = self.prune(now_secs()) {
+            log_warn!("rate limit: pruning {}: {}", self.dir.display(), e);
+        }
+        r
+    }
+    fn is_blocking(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::PodConfig;
+
+    use super::*;
+
+    #[test]
+    fn parse_limits() {
+        let config = PodConfig::parse(r#"
+            limit_store = { kind = "file", dir = "/tmp/wasmesh-limits" }
+
+            [[limits]]
+            key = "client_ip"
+            rate = 10.0
+
+            [[limits]]
+            key = "header:X-Api-Key"
+            path = "/api/*"
+            rate = 100.0
+            burst = 200.0
+        "#).unwrap();
+        assert_eq!(config.limits[0].key, LimitKey::ClientIp);
+        assert_eq!(config.limits[1].key, LimitKey::Header("x-api-key".to_string()));
+        assert!(matches!(config.limit_store, LimitStore::File { .. }));
+        assert!(PodConfig::parse("[[limits]]\nkey = \"cookie\"\nrate = 1.0").is_err());
+        for rate in ["0.0", "-1.0", "nan", "inf"] {
+            assert!(PodConfig::parse(&format!("[[limits]]\nkey = \"module\"\nrate = {}", rate)).is_err(), "{}", rate);
+        }
+        assert!(PodConfig::parse("[[limits]]\nkey = \"module\"\nrate = 1.0\nburst = nan").is_err());
+    }
+
+    #[test]
+    fn bucket_refills_at_rate() {
+        let mut bucket = Bucket { tokens: 2.0, updated: 100.0 };
+        assert!(bucket.take(100.0, 1.0, 2.0).is_ok());
+        assert!(bucket.take(100.0, 1.0, 2.0).is_ok());
+        assert_eq!(bucket.take(100.0, 1.0, 2.0), Err(Duration::from_secs(1)));
+        assert!(bucket.take(101.0, 1.0, 2.0).is_ok());
+        // refill never exceeds the burst
+        assert!(bucket.take(200.0, 1.0, 2.0).is_ok());
+        assert!(bucket.take(200.0, 1.0, 2.0).is_ok());
+        assert!(bucket.take(200.0, 1.0, 2.0).is_err());
+        // never panics, even with a rate that slipped past validation
+        let _ = Bucket { tokens: 0.0, updated: 200.0 }.take(200.0, f64::NAN, 2.0);
+        let _ = Bucket { tokens: 0.0, updated: 200.0 }.take(200.0, 0.0, 2.0);
+    }
+
+    #[test]
+    fn file_backend_is_shared_between_instances() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = FileBackend::new(dir.path().to_path_buf()).unwrap();
+        let b = FileBackend::new(dir.path().to_path_buf()).unwrap();
+        let wanted = |bucket: &str| [Acquire { bucket: bucket.to_string(), rate: 0.001, burst: 2.0 }];
+        assert!(a.acquire(&wanted("orders/0/1.2.3.4")).is_ok());
+        assert!(b.acquire(&wanted("orders/0/1.2.3.4")).is_ok());
+        assert!(a.acquire(&wanted("orders/0/1.2.3.4")).is_err());
+        assert!(b.acquire(&wanted("orders/0/5.6.7.8")).is_ok());
+    }
+
+    #[test]
+    fn full_bucket_files_are_pruned() {
+        let dir = tempfile::tempdir().unwrap();
+        let backend = FileBackend::new(dir.path().to_path_buf()).unwrap();
+        let fast = Acquire { bucket: "orders/0/fast".to_string(), rate: 1e9, burst: 1.0 };
+        let slow = Acquire { bucket: "orders/0/slow".to_string(), rate: 0.001, burst: 1.0 };
+        assert!(backend.acquire(std::slice::from_ref(&fast)).is_ok());
+        assert!(backend.acquire(std::slice::from_ref(&slow)).is_ok());
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+        backend.prune(now_secs() + PRUNE_INTERVAL).unwrap();
+        assert!(!backend.path(&fast.bucket).exists());
+        assert!(backend.path(&slow.bucket).exists());
+        // the slow bucket is still empty
+        assert!(backend.acquire(&[slow]).is_err());
+    }
+
+    #[test]
+    fn path_rules_match_normalized_paths() {
+        let rules = [LimitRule { key: LimitKey::Route, path: Some("/admin/*".to_string()), rate: 1.0, burst: None }];
+        for path in ["/admin/users", "//admin/users", "/public/../admin/users", "/%61dmin/users"] {
+            let (parts, _) = hyper::Request::get(path).body(()).unwrap().into_parts();
+            assert_eq!(wanted_tokens(&rules, "orders", None, &parts).len(), 1, "{}", path);
+        }
+        let (parts, _) = hyper::Request::get("/public/admin").body(()).unwrap().into_parts();
+        assert!(wanted_tokens(&rules, "orders", None, &parts).is_empty());
+    }
+
+    #[test]
+    fn idle_buckets_are_evicted() {
+        let backend = MemoryBackend { buckets: Mutex::default(), max_buckets: 4 };
+        for i in 0..100 {
+            let wanted = Acquire { bucket: format!("orders/0/{}", i), rate: 0.001, burst: 2.0 };
+            assert!(backend.acquire(&[wanted]).is_ok());
+            assert!(backend.buckets.lock().unwrap().len() <= 4);
+        }
+        // full buckets go first
+        let fast = Acquire { bucket: "orders/1/fast".to_string(), rate: 1e9, burst: 1.0 };
+        let mut buckets = HashMap::new();
+        buckets.insert(fast.bucket.clone(), MemoryBucket { bucket: Bucket { tokens: 0.0, updated: 1.0 }, rate: fast.rate, burst: 1.0 });
+        buckets.insert("orders/0/slow".to_string(), MemoryBucket { bucket: Bucket { tokens: 0.0, updated: 1.0 }, rate: 0.001, burst: 1.0 });
+        evict(&mut buckets, 4, 2.0);
+        assert_eq!(buckets.keys().collect::<Vec<_>>(), ["orders/0/slow"]);
+    }
+
+    #[test]
+    fn rejected_requests_take_no_token() {
+        let dir = tempfile::tempdir().unwrap();
+        let backends: [Box<dyn LimitBackend>; 2] = [Box::new(MemoryBackend::default()),
+                                                    Box::new(FileBackend::new(dir.path().to_path_buf()).unwrap())];
+        for backend in backends {
+            let roomy = Acquire { bucket: "orders/0/orders".to_string(), rate: 0.001, burst: 2.0 };
+            let tight = Acquire { bucket: "orders/1/1.2.3.4".to_string(), rate: 0.001, burst: 1.0 };
+            assert!(backend.acquire(&[roomy.clone(), tight.clone()]).is_ok());
+            for _ in 0..3 {
+                assert!(backend.acquire(&[roomy.clone(), tight.clone()]).is_err());
+            }
+            // the rejections above left the module bucket its last token
+            assert!(backend.acquire(std::slice::from_ref(&roomy)).is_ok());
+            assert!(backend.acquire(&[roomy]).is_err());
+        }
+    }
+}
@@ -1,3 +1,9 @@
+pub(crate) use limit::*;
+pub(crate) use policy::*;
+pub(crate) use route::*;
 pub(crate) use server::*;
 
+mod limit;
+mod policy;
+mod route;
 mod server;
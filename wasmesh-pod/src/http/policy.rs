@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+use hyper::http::request::Parts;
+use serde::{Deserialize, Serialize};
+
+/// Decision of an authorization rule.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Action {
+    #[default]
+    Allow,
+    Deny,
+}
+
+/// Ordered authorization rules, the first matching rule decides.
+///
+/// Patterns ending with `*` match by prefix, all others match exactly.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct AuthzPolicy {
+    /// decision when no rule matches
+    pub(crate) default: Action,
+    pub(crate) rules: Vec<Rule>,
+}
+
+/// A rule matches when every non-empty condition matches.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct Rule {
+    pub(crate) action: Action,
+    /// caller identity patterns, never matched by unauthenticated callers
+    pub(crate) identities: Vec<String>,
+    /// request path patterns
+    pub(crate) paths: Vec<String>,
+    /// request methods, case-insensitive
+    pub(crate) methods: Vec<String>,
+    /// required header values, by header name
+    pub(crate) headers: HashMap<String, String>,
+}
+
+impl AuthzPolicy {
+    /// Decides whether the request may reach the guest; requests whose path
+    /// cannot be normalized are denied.
+    pub(crate) fn evaluate(&self, peer: Option<&str>, req: &Parts) -> Action {
+        let path = match normalize_path(req.uri.path()) {
+            Some(path) => path,
+            None => return Action::Deny,
+        };
+        self.rules
+            .iter()
+            .find(|rule| rule.matches(peer, &path, req))
+            .map_or(self.default, |rule| rule.action)
+    }
+}
+
+/// Path as matched by the rules: unreserved characters percent-decoded,
+/// repeated slashes collapsed and dot segments removed. `None` for invalid
+/// escapes, encoded separators and paths climbing above the root.
+pub(crate) fn normalize_path(path: &str) -> Option<String> {
+    let bytes = path.as_bytes();
+    let mut decoded = String::with_capacity(path.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'%' {
+            decoded.push(bytes[i] as char);
+            i += 1;
+            continue;
+        }
+        let hex = path.get(i + 1..i + 3)?;
+        let b = u8::from_str_radix(hex, 16).ok()?;
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => decoded.push(b as char),
+            b'/' | b'\\' => return None,
+            _ => {
+                decoded.push('%');
+                decoded.push_str(&hex.to_ascii_uppercase());
+            }
+        }
+        i += 3;
+    }
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in decoded.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop()?;
+            }
+            _ => segments.push(segment),
+        }
+    }
+    let mut normalized = format!("/{}", segments.join("/"));
+    if decoded.ends_with('/') && !segments.is_empty() {
+        normalized.push('/');
+    }
+    Some(normalized)
+}
+
+impl Rule {
+    fn matches(&self, peer: Option<&str>, path: &str, req: &Parts) -> bool {
+        if !self.identities.is_empty() {
+            match peer {
+                Some(peer) if self.identities.iter().any(|p| glob_match(p, peer)) => {}
+                _ => return false,
+            }
+        }
+        if !self.paths.is_empty() && !self.paths.iter().any(|p| glob_match(p, path)) {
+            return false;
+        }
+        if !self.methods.is_empty() && !self.methods.iter().any(|m| m.eq_ignore_ascii_case(req.method.as_str())) {
+            return false;
+        }
+        self.headers.iter().all(|(name, pattern)| {
+            req.headers
+               .get(name)
+               .and_then(|v| v.to_str().ok())
+               .is_some_and(|v| glob_match(pattern, v))
+        })
+    }
+}
+
+pub(crate) fn glob_match(pattern: &str, value: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => value.starts_with(prefix),
+        None => pattern == value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::Request;
+
+    use crate::config::PodConfig;
+
+    use super::*;
+
+    fn parts(method: &str, uri: &str, headers: &[(&str, &str)]) -> Parts {
+        let mut builder = Request::builder().method(method).uri(uri);
+        for (k, v) in headers {
+            builder = builder.header(*k, *v);
+        }
+        builder.body(()).unwrap().into_parts().0
+    }
+
+    #[test]
+    fn first_matching_rule_decides() {
+        let config = PodConfig::parse(r#"
+            [authz]
+            default = "deny"
+
+            [[authz.rules]]
+            action = "deny"
+            paths = ["/admin/*"]
+            methods = ["delete"]
+
+            [[authz.rules]]
+            identities = ["spiffe://wasmesh/ns/shop/*"]
+
+            [[authz.rules]]
+            paths = ["/public"]
+            headers = { "x-api-key" = "k-*" }
+        "#).unwrap();
+        let authz = &config.authz;
+        let orders = Some("spiffe://wasmesh/ns/shop/module/orders");
+        assert_eq!(authz.evaluate(orders, &parts("GET", "/admin/x", &[])), Action::Allow);
+        assert_eq!(authz.evaluate(orders, &parts("DELETE", "/admin/x", &[])), Action::Deny);
+        assert_eq!(authz.evaluate(None, &parts("GET", "/admin/x", &[])), Action::Deny);
+        assert_eq!(authz.evaluate(None, &parts("GET", "/public", &[])), Action::Deny);
+        assert_eq!(authz.evaluate(None, &parts("GET", "/public", &[("x-api-key", "k-1")])), Action::Allow);
+    }
+
+    #[test]
+    fn paths_are_normalized_before_matching() {
+        let config = PodConfig::parse(r#"
+            [[authz.rules]]
+            action = "deny"
+            paths = ["/admin", "/admin/*"]
+        "#).unwrap();
+        let authz = &config.authz;
+        for path in ["/admin", "/admin/x", "/admin/../admin/x", "/public/../admin/x", "//admin/x", "/./admin//x",
+                     "/%61dmin/x", "/%2e%2e/admin", "/admin%2fx", "/x/../../admin", "/%zz"] {
+            assert_eq!(authz.evaluate(None, &parts("GET", path, &[])), Action::Deny, "{}", path);
+        }
+        assert_eq!(authz.evaluate(None, &parts("GET", "/public/%20x", &[])), Action::Allow);
+        assert_eq!(normalize_path("//a/./b/../c/").as_deref(), Some("/a/c/"));
+        assert_eq!(normalize_path("/a%3fb").as_deref(), Some("/a%3Fb"));
+    }
+}
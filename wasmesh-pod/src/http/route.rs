@@ -0,0 +1,800 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex, RwLock};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use hyper::header::COOKIE;
+use hyper::http::request::Parts;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use wasmy_vm::{load_wasm, WasmInfo};
+
+use crate::config;
+use crate::http::glob_match;
+use crate::signing::{self, Verification};
+use crate::store;
+
+/// A mounted service, served by one or more module versions.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct ServiceConfig {
+    pub(crate) name: String,
+    /// request path pattern the service is mounted at
+    pub(crate) path: String,
+    pub(crate) versions: Vec<VersionConfig>,
+    /// candidate module receiving copies of the requests
+    #[serde(default)]
+    pub(crate) mirror: Option<MirrorConfig>,
+}
+
+/// Shadow traffic to a candidate module, its responses are compared and discarded.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct MirrorConfig {
+    /// wasm file path, or `sha256:<hex>` for a module of the store
+    pub(crate) wasm: String,
+    /// share of requests to mirror, from 0 to 100
+    #[serde(default = "MirrorConfig::default_percent")]
+    pub(crate) percent: f64,
+}
+
+impl MirrorConfig {
+    fn default_percent() -> f64 {
+        100.0
+    }
+}
+
+/// A module version of a service.
+///
+/// Requests matching all `headers` and `cookies` go to the first such version,
+/// the others are split between the versions by `weight`.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct VersionConfig {
+    pub(crate) name: String,
+    /// wasm file path, or `sha256:<hex>` for a module of the store
+    pub(crate) wasm: String,
+    #[serde(default)]
+    pub(crate) weight: u32,
+    #[serde(default)]
+    pub(crate) headers: HashMap<String, String>,
+    #[serde(default)]
+    pub(crate) cookies: HashMap<String, String>,
+}
+
+impl VersionConfig {
+    fn has_match_rules(&self) -> bool {
+        !self.headers.is_empty() || !self.cookies.is_empty()
+    }
+    fn matches(&self, req: &Parts) -> bool {
+        self.headers.iter().all(|(name, pattern)| {
+            req.headers
+               .get(name)
+               .and_then(|v| v.to_str().ok())
+               .is_some_and(|v| glob_match(pattern, v))
+        }) && self.cookies.iter().all(|(name, pattern)| {
+            cookie(req, name).is_some_and(|v| glob_match(pattern, v))
+        })
+    }
+}
+
+fn cookie<'a>(req: &'a Parts, name: &str) -> Option<&'a str> {
+    req.headers
+       .get_all(COOKIE)
+       .iter()
+       .filter_map(|v| v.to_str().ok())
+       .flat_map(|v| v.split(';'))
+       .filter_map(|pair| pair.split_once('='))
+       .find(|(k, _)| k.trim() == name)
+       .map(|(_, v)| v.trim())
+}
+
+/// Request counters of a module version.
+#[derive(Debug, Default)]
+pub(crate) struct VersionStats {
+    requests: AtomicU64,
+    errors: AtomicU64,
+    latency_micros: AtomicU64,
+    // responses of a mirror that differ from the primary's
+    diffs: AtomicU64,
+    // calls running now, and the most that ran at once
+    in_flight: AtomicU64,
+    peak_in_flight: AtomicU64,
+    // instances replaced after a trap
+    recycled: AtomicU64,
+    // mirrored requests dropped because too many were running
+    dropped: AtomicU64,
+}
+
+impl VersionStats {
+    pub(crate) fn record(&self, latency: Duration, ok: bool) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        if !ok {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+        }
+        self.latency_micros.fetch_add(latency.as_micros() as u64, Ordering::Relaxed);
+    }
+    pub(crate) fn record_diff(&self) {
+        self.diffs.fetch_add(1, Ordering::Relaxed);
+    }
+    pub(crate) fn record_dropped(&self) {
+        self.dropped.fetch_add(1, Ordering::Relaxed);
+    }
+    pub(crate) fn record_recycle(&self) {
+        self.recycled.fetch_add(1, Ordering::Relaxed);
+    }
+    /// Counts a call as running until the returned guard is dropped.
+    pub(crate) fn enter(&self) -> InFlight<'_> {
+        let n = self.in_flight.fetch_add(1, Ordering::Relaxed) + 1;
+        self.peak_in_flight.fetch_max(n, Ordering::Relaxed);
+        InFlight(self)
+    }
+    fn status(&self, name: &str, wasm: &str, weight: u32) -> VersionStatus {
+        let requests = self.requests.load(Ordering::Relaxed);
+        VersionStatus {
+            name: name.to_string(),
+            wasm: wasm.to_string(),
+            weight,
+            requests,
+            errors: self.errors.load(Ordering::Relaxed),
+            mean_latency_micros: self.latency_micros.load(Ordering::Relaxed).checked_div(requests).unwrap_or(0),
+        }
+    }
+}
+
+/// A running guest call, see [`VersionStats::enter`].
+pub(crate) struct InFlight<'a>(&'a VersionStats);
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        self.0.in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// A routable module version.
+#[derive(Debug)]
+pub(crate) struct Target {
+    pub(crate) service: String,
+    pub(crate) version: String,
+    /// module name, the wasm file stem
+    pub(crate) module: String,
+    pub(crate) wasm_info: WasmInfo,
+    pub(crate) stats: Arc<VersionStats>,
+}
+
+/// Where a request goes.
+#[derive(Debug)]
+pub(crate) struct Route {
+    pub(crate) target: Arc<Target>,
+    /// shadow target receiving a copy of the request
+    pub(crate) mirror: Option<Arc<Target>>,
+}
+
+#[derive(Debug)]
+struct Service {
+    config: ServiceConfig,
+    targets: Vec<Arc<Target>>,
+    mirror: Option<Arc<Target>>,
+}
+
+impl Service {
+    fn route(&self, req: &Parts) -> Option<Route> {
+        let target = self.pick(req)?;
+        let mirror = self.mirror.clone().filter(|_| {
+            let percent = self.config.mirror.as_ref().map_or(0.0, |m| m.percent);
+            percent >= 100.0 || rand::thread_rng().gen_range(0.0..100.0) < percent
+        });
+        Some(Route { target, mirror })
+    }
+
+    fn pick(&self, req: &Parts) -> Option<Arc<Target>> {
+        let versions = &self.config.versions;
+        if let Some(i) = versions.iter().position(|v| v.has_match_rules() && v.matches(req)) {
+            return Some(self.targets[i].clone());
+        }
+        let total: u64 = versions.iter().map(|v| v.weight as u64).sum();
+        if total == 0 {
+            return self.targets.first().cloned();
+        }
+        let mut n = rand::thread_rng().gen_range(0..total);
+        for (i, v) in versions.iter().enumerate() {
+            if n < v.weight as u64 {
+                return Some(self.targets[i].clone());
+            }
+            n -= v.weight as u64;
+        }
+        None
+    }
+}
+
+/// Routes requests to module versions.
+///
+/// Services are tried in order, the module given on the command line serves
+/// every request no configured service is mounted for.
+#[derive(Debug)]
+pub(crate) struct Router {
+    default: ServiceConfig,
+    services: RwLock<Vec<Service>>,
+    // held while the services are changed, so changes never interleave
+    overrides: Mutex<Overrides>,
+    stats: RwLock<HashMap<StatsKey, Arc<VersionStats>>>,
+    history: Mutex<HashMap<(String, String), Vec<Deployed>>>,
+    // loaded modules, by wasm path or digest
+    loaded: RwLock<HashMap<String, LoadedModule>>,
+    // why modules were refused, by wasm path or digest, until they load
+    refused: Mutex<HashMap<String, String>>,
+}
+
+/// What is known of a loaded module.
+#[derive(Serialize, Debug, Clone)]
+pub(crate) struct LoadedModule {
+    /// SHA-256 of the content that was verified and loaded, hex encoded
+    pub(crate) sha256: String,
+    pub(crate) signature: Verification,
+    /// copy of that content in the store, which the instances run
+    #[serde(skip)]
+    pub(crate) file: String,
+}
+
+/// Status of a service, as shown by the admin API.
+#[derive(Serialize, Debug, Clone)]
+pub(crate) struct ServiceStatus {
+    pub(crate) name: String,
+    pub(crate) path: String,
+    pub(crate) versions: Vec<VersionStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) mirror: Option<MirrorStatus>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub(crate) struct VersionStatus {
+    pub(crate) name: String,
+    pub(crate) wasm: String,
+    pub(crate) weight: u32,
+    pub(crate) requests: u64,
+    pub(crate) errors: u64,
+    pub(crate) mean_latency_micros: u64,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub(crate) struct MirrorStatus {
+    pub(crate) wasm: String,
+    pub(crate) percent: f64,
+    pub(crate) requests: u64,
+    pub(crate) errors: u64,
+    pub(crate) diffs: u64,
+    /// requests not mirrored because too many mirrors were running
+    pub(crate) dropped: u64,
+    pub(crate) mean_latency_micros: u64,
+}
+
+/// Guest calls of a module version, as shown by the admin API.
+#[derive(Serialize, Debug, Clone)]
+pub(crate) struct PoolStatus {
+    pub(crate) service: String,
+    pub(crate) version: String,
+    pub(crate) module: String,
+    pub(crate) in_flight: u64,
+    pub(crate) peak_in_flight: u64,
+    pub(crate) recycled: u64,
+}
+
+impl Router {
+    /// Creates a router whose fallback service runs the given module.
+    pub(crate) fn new(default_module: &str, default_wasm: &str) -> Self {
+        let default = ServiceConfig {
+            name: default_module.to_string(),
+            path: "*".to_string(),
+            versions: vec![VersionConfig {
+                name: "default".to_string(),
+                wasm: default_wasm.to_string(),
+                weight: 1,
+                headers: HashMap::new(),
+                cookies: HashMap::new(),
+            }],
+            mirror: None,
+        };
+        Router {
+            default,
+            services: RwLock::new(Vec::new()),
+            overrides: Mutex::new(Overrides::default()),
+            stats: RwLock::new(HashMap::new()),
+            history: Mutex::new(HashMap::new()),
+            loaded: RwLock::new(HashMap::new()),
+            refused: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Replaces the services, loading the modules of their versions first.
+    ///
+    /// Weights and deploys made at runtime are applied again over the new
+    /// services, and counters of versions that still exist are kept.
+    pub(crate) fn update(&self, services: &[ServiceConfig]) -> anyhow::Result<()> {
+        for s in services.iter().chain(Some(&self.default)) {
+            let mirror = s.mirror.as_ref().map(|m| &m.wasm);
+            for wasm in s.versions.iter().map(|v| &v.wasm).chain(mirror) {
+                self.load(wasm)?;
+            }
+        }
+        self.replace(services)
+    }
+
+    fn replace(&self, services: &[ServiceConfig]) -> anyhow::Result<()> {
+        let mut overrides = self.overrides.lock().unwrap();
+        let mut services = services.to_vec();
+        overrides.retain_services(&services);
+        overrides.apply(&mut services);
+        self.install(&services)?;
+        // deploys can only be rolled back on the versions still running them
+        self.history.lock().unwrap().retain(|(service, version), deploys| {
+            let running = services.iter().filter(|s| s.name == *service)
+                                  .flat_map(|s| &s.versions)
+                                  .find(|v| v.name == *version)
+                                  .map(|v| &v.wasm);
+            deploys.last().is_some_and(|last| Some(&last.wasm) == running)
+        });
+        Ok(())
+    }
+
+    fn install(&self, services: &[ServiceConfig]) -> anyhow::Result<()> {
+        let mut built = Vec::with_capacity(services.len() + 1);
+        for config in services.iter().chain(Some(&self.default)) {
+            if config.versions.is_empty() {
+                anyhow::bail!("service {} has no versions", config.name);
+            }
+            let targets = config.versions.iter().map(|v| {
+                self.target(&config.name, &v.name, &v.wasm, StatsKey::Version(config.name.clone(), v.name.clone()))
+            }).collect();
+            let mirror = config.mirror.as_ref().map(|m| {
+                self.target(&config.name, MIRROR_VERSION, &m.wasm, StatsKey::Mirror(config.name.clone()))
+            });
+            built.push(Service { config: config.clone(), targets, mirror });
+        }
+        *self.services.write().unwrap() = built;
+        Ok(())
+    }
+
+    fn target(&self, service: &str, version: &str, wasm: &str, stats: StatsKey) -> Arc<Target> {
+        Arc::new(Target {
+            service: service.to_string(),
+            version: version.to_string(),
+            module: module_name(wasm).to_string(),
+            wasm_info: WasmInfo { wasm_path: self.loaded(wasm).map_or_else(|| store::path(wasm), |m| m.file) },
+            stats: self.stats_of(stats),
+        })
+    }
+
+    pub(crate) fn route(&self, req: &Parts) -> Option<Route> {
+        let path = req.uri.path();
+        self.services
+            .read()
+            .unwrap()
+            .iter()
+            .find(|s| glob_match(&s.config.path, path))
+            .and_then(|s| s.route(req))
+    }
+
+    /// Changes the traffic weights of a service's versions at runtime.
+    ///
+    /// Versions missing from `weights` keep their weight.
+    pub(crate) fn set_weights(&self, service: &str, weights: &HashMap<String, u32>) -> Result<(), String> {
+        let mut overrides = self.overrides.lock().unwrap();
+        let mut services = self.services.write().unwrap();
+        let s = services.iter_mut()
+                        .find(|s| s.config.name == service)
+                        .ok_or_else(|| format!("service not found: {}", service))?;
+        if let Some(name) = weights.keys().find(|name| !s.config.versions.iter().any(|v| &v.name == *name)) {
+            return Err(format!("version not found: {}", name));
+        }
+        for v in s.config.versions.iter_mut() {
+            if let Some(w) = weights.get(&v.name) {
+                v.weight = *w;
+                overrides.weights.insert((service.to_string(), v.name.clone()), *w);
+            }
+        }
+        Ok(())
+    }
+
+    /// Points a version of a configured service at another module, adding the
+    /// version when it is new, with the given weight or else no traffic.
+    pub(crate) fn deploy(&self, service: &str, version: &str, wasm: &str, weight: Option<u32>) -> anyhow::Result<()> {
+        self.load(wasm)?;
+        self.set_version(service, version, wasm, weight)
+    }
+
+    fn set_version(&self, service: &str, version: &str, wasm: &str, weight: Option<u32>) -> anyhow::Result<()> {
+        let mut overrides = self.overrides.lock().unwrap();
+        let mut configs = self.configs();
+        let s = configs.iter_mut()
+                       .find(|s| s.name == service)
+                       .ok_or_else(|| anyhow::anyhow!("service not found: {}", service))?;
+        let previous = match s.versions.iter_mut().find(|v| v.name == version) {
+            Some(v) => {
+                let previous = std::mem::replace(&mut v.wasm, wasm.to_string());
+                if let Some(weight) = weight {
+                    v.weight = weight;
+                }
+                Some(previous)
+            }
+            None => {
+                s.versions.push(VersionConfig {
+                    name: version.to_string(),
+                    wasm: wasm.to_string(),
+                    weight: weight.unwrap_or(0),
+                    headers: HashMap::new(),
+                    cookies: HashMap::new(),
+                });
+                None
+            }
+        };
+        self.install(&configs)?;
+        let key = (service.to_string(), version.to_string());
+        overrides.deploys.insert(key.clone(), wasm.to_string());
+        if let Some(weight) = weight {
+            overrides.weights.insert(key, weight);
+        }
+        let mut history = self.history.lock().unwrap();
+        let deploys = history.entry((service.to_string(), version.to_string())).or_default();
+        if deploys.len() == HISTORY_LIMIT {
+            deploys.remove(0);
+        }
+        deploys.push(Deployed { wasm: wasm.to_string(), previous });
+        Ok(())
+    }
+
+    /// Undoes the last deploy of a version: its previous module is loaded
+    /// again, or the version is removed when the deploy added it.
+    pub(crate) fn rollback(&self, service: &str, version: &str) -> anyhow::Result<()> {
+        let key = (service.to_string(), version.to_string());
+        let last = self.history.lock().unwrap().get(&key).and_then(|deploys| deploys.last().cloned())
+                       .ok_or_else(|| anyhow::anyhow!("nothing to roll back for {}/{}", service, version))?;
+        if let Some(wasm) = &last.previous {
+            self.load(wasm)?;
+        }
+        self.restore_version(service, version, last)
+    }
+
+    /// Puts back the module a deploy replaced, then forgets the deploy.
+    fn restore_version(&self, service: &str, version: &str, last: Deployed) -> anyhow::Result<()> {
+        let mut overrides = self.overrides.lock().unwrap();
+        let key = (service.to_string(), version.to_string());
+        // another rollback or a reload may have come first
+        let deploys = self.history.lock().unwrap().get(&key).cloned().unwrap_or_default();
+        if deploys.last() != Some(&last) {
+            anyhow::bail!("{}/{} changed since the deploy, nothing rolled back", service, version);
+        }
+        let mut configs = self.configs();
+        let s = configs.iter_mut()
+                       .find(|s| s.name == service)
+                       .ok_or_else(|| anyhow::anyhow!("service not found: {}", service))?;
+        let i = s.versions.iter().position(|v| v.name == version && v.wasm == last.wasm)
+                 .ok_or_else(|| anyhow::anyhow!("{}/{} no longer runs {}", service, version, last.wasm))?;
+        let deployed = deploys.len() > 1;
+        match last.previous {
+            // back to the module of the configuration once no deploy is left
+            Some(wasm) if deployed => {
+                overrides.deploys.insert(key.clone(), wasm.clone());
+                s.versions[i].wasm = wasm;
+            }
+            Some(wasm) => {
+                overrides.deploys.remove(&key);
+                s.versions[i].wasm = wasm;
+            }
+            None => {
+                overrides.deploys.remove(&key);
+                overrides.weights.remove(&key);
+                s.versions.remove(i);
+            }
+        }
+        self.install(&configs)?;
+        if let Some(deploys) = self.history.lock().unwrap().get_mut(&key) {
+            deploys.pop();
+        }
+        Ok(())
+    }
+
+    /// Loads the module of a version again, e.g. after its file was replaced.
+    pub(crate) fn reload_module(&self, service: &str, version: &str) -> anyhow::Result<()> {
+        let wasm = self.services.read().unwrap().iter()
+                       .filter(|s| s.config.name == service)
+                       .flat_map(|s| {
+                           let mirror = s.config.mirror.as_ref().map(|m| (MIRROR_VERSION, &m.wasm));
+                           s.config.versions.iter().map(|v| (v.name.as_str(), &v.wasm)).chain(mirror)
+                       })
+                       .find(|(name, _)| *name == version)
+                       .map(|(_, wasm)| wasm.clone())
+                       .ok_or_else(|| anyhow::anyhow!("version not found: {}/{}", service, version))?;
+        self.load(&wasm)
+    }
+
+    /// Loads a module, fetching it into the store when referenced by digest,
+    /// once its signature is verified against the trusted keys.
+    fn load(&self, wasm: &str) -> anyhow::Result<()> {
+        let r = self.verify_and_load(wasm);
+        let mut refused = self.refused.lock().unwrap();
+        match &r {
+            Ok(_) => refused.remove(wasm),
+            Err(e) => refused.insert(wasm.to_string(), format!("{:#}", e)),
+        };
+        r
+    }
+
+    /// Reads the module once, then hashes, verifies and loads that content:
+    /// what runs is a copy of it in the store, never the file read.
+    fn verify_and_load(&self, wasm: &str) -> anyhow::Result<()> {
+        let (path, bytes) = store::resolve(wasm)?;
+        let sha256 = store::sha256_hex(&bytes);
+        if store::digest_of(wasm).is_some_and(|hex| hex != sha256) {
+            anyhow::bail!("load {}: content does not match the digest", wasm);
+        }
+        let signature = signing::verify(&config::current().trusted_keys, &path, &bytes)?;
+        let file = store::path(&store::put(&bytes)?);
+        // so that peers fetching the module from the store can verify it too
+        if matches!(signature, Verification::Verified { .. }) && store::digest_of(wasm).is_none() {
+            if let Ok(encoded) = fs::read(signing::signature_path(&path)) {
+                store::put_signature(&sha256, &encoded)?;
+            }
+        }
+        load_wasm(WasmInfo { wasm_path: file.clone() }).map_err(|e| anyhow::anyhow!("load {}: {}", wasm, e))?;
+        if let Verification::Verified { key } = &signature {
+            log_info!("module {} is signed by {}", wasm, key);
+        }
+        self.loaded.write().unwrap().insert(wasm.to_string(), LoadedModule { sha256, signature, file });
+        Ok(())
+    }
+
+    /// Module last loaded from a wasm path or digest.
+    pub(crate) fn loaded(&self, wasm: &str) -> Option<LoadedModule> {
+        self.loaded.read().unwrap().get(wasm).cloned()
+    }
+
+    /// Modules refused since they last loaded, with the reason.
+    pub(crate) fn refused(&self) -> HashMap<String, String> {
+        self.refused.lock().unwrap().clone()
+    }
+
+    /// Configured services as currently routed, without the fallback service.
+    pub(crate) fn configs(&self) -> Vec<ServiceConfig> {
+        let services = self.services.read().unwrap();
+        services[..services.len().saturating_sub(1)].iter().map(|s| s.config.clone()).collect()
+    }
+
+    pub(crate) fn status(&self) -> Vec<ServiceStatus> {
+        self.services.read().unwrap().iter().map(|s| ServiceStatus {
+            name: s.config.name.clone(),
+            path: s.config.path.clone(),
+            versions: s.config.versions.iter().zip(&s.targets).map(|(v, t)| {
+                t.stats.status(&v.name, &v.wasm, v.weight)
+            }).collect(),
+            mirror: s.config.mirror.as_ref().zip(s.mirror.as_ref()).map(|(m, t)| {
+                let status = t.stats.status(MIRROR_VERSION, &m.wasm, 0);
+                MirrorStatus {
+                    wasm: status.wasm,
+                    percent: m.percent,
+                    requests: status.requests,
+                    errors: status.errors,
+                    diffs: t.stats.diffs.load(Ordering::Relaxed),
+                    dropped: t.stats.dropped.load(Ordering::Relaxed),
+                    mean_latency_micros: status.mean_latency_micros,
+                }
+            }),
+        }).collect()
+    }
+
+    pub(crate) fn pool(&self) -> Vec<PoolStatus> {
+        self.services.read().unwrap().iter().flat_map(|s| s.targets.iter().chain(&s.mirror)).map(|t| PoolStatus {
+            service: t.service.clone(),
+            version: t.version.clone(),
+            module: t.module.clone(),
+            in_flight: t.stats.in_flight.load(Ordering::Relaxed),
+            peak_in_flight: t.stats.peak_in_flight.load(Ordering::Relaxed),
+            recycled: t.stats.recycled.load(Ordering::Relaxed),
+        }).collect()
+    }
+
+    fn stats_of(&self, key: StatsKey) -> Arc<VersionStats> {
+        self.stats
+            .write()
+            .unwrap()
+            .entry(key)
+            .or_default()
+            .clone()
+    }
+}
+
+/// A deploy of a version, as remembered for rollbacks.
+#[derive(Debug, Clone, PartialEq)]
+struct Deployed {
+    wasm: String,
+    /// module the deploy replaced, `None` when the deploy added the version
+    previous: Option<String>,
+}
+
+/// Changes made at runtime through the admin API, kept over reloads of the
+/// services, by service and version.
+#[derive(Debug, Default)]
+struct Overrides {
+    weights: BTreeMap<(String, String), u32>,
+    /// module deployed to a version, which is added when missing
+    deploys: BTreeMap<(String, String), String>,
+}
+
+impl Overrides {
+    /// Forgets the changes of services that are gone.
+    fn retain_services(&mut self, services: &[ServiceConfig]) {
+        let exists = |key: &(String, String)| services.iter().any(|s| s.name == key.0);
+        self.weights.retain(|key, _| exists(key));
+        self.deploys.retain(|key, _| exists(key));
+    }
+
+    fn apply(&self, services: &mut [ServiceConfig]) {
+        for s in services.iter_mut() {
+            for ((service, version), wasm) in &self.deploys {
+                if *service != s.name {
+                    continue;
+                }
+                match s.versions.iter_mut().find(|v| v.name == *version) {
+                    Some(v) => v.wasm = wasm.clone(),
+                    None => s.versions.push(VersionConfig {
+                        name: version.clone(),
+                        wasm: wasm.clone(),
+                        weight: 0,
+                        headers: HashMap::new(),
+                        cookies: HashMap::new(),
+                    }),
+                }
+            }
+            for v in s.versions.iter_mut() {
+                if let Some(weight) = self.weights.get(&(s.name.clone(), v.name.clone())) {
+                    v.weight = *weight;
+                }
+            }
+        }
+    }
+}
+
+/// Deploys remembered per version for rollbacks.
+const HISTORY_LIMIT: usize = 10;
+
+/// Version name the mirror of a service is shown under.
+const MIRROR_VERSION: &str = "mirror";
+
+/// Whose counters: the mirror of a service is counted apart from its
+/// versions, even one named like [`MIRROR_VERSION`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum StatsKey {
+    Version(String, String),
+    Mirror(String),
+}
+
+/// Module name of a wasm file, its file stem.
+pub(crate) fn module_name(wasm: &str) -> &str {
+    Path::new(wasm).file_stem().and_then(|s| s.to_str()).unwrap_or(wasm)
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::Request;
+
+    use crate::config::PodConfig;
+
+    use super::*;
+
+    fn parts(uri: &str, headers: &[(&str, &str)]) -> Parts {
+        let mut builder = Request::builder().uri(uri);
+        for (k, v) in headers {
+            builder = builder.header(*k, *v);
+        }
+        builder.body(()).unwrap().into_parts().0
+    }
+
+    #[test]
+    fn split_by_rules_and_weights() {
+        let config = PodConfig::parse(r#"
+            [[services]]
+            name = "orders"
+            path = "/orders*"
+
+            [[services.versions]]
+            name = "v1"
+            wasm = "orders-v1.wasm"
+            weight = 100
+
+            [[services.versions]]
+            name = "v2"
+            wasm = "orders-v2.wasm"
+            cookies = { canary = "1" }
+
+            [services.mirror]
+            wasm = "orders-v3.wasm"
+        "#).unwrap();
+        let router = Router::new("simple", "simple.wasm");
+        router.install(&config.services).unwrap();
+        let route = |uri, headers| router.route(&parts(uri, headers)).unwrap().target;
+
+        assert_eq!(route("/", &[]).service, "simple");
+        assert!(router.route(&parts("/", &[])).unwrap().mirror.is_none());
+        assert_eq!(router.route(&parts("/orders", &[])).unwrap().mirror.unwrap().module, "orders-v3");
+        assert_eq!(route("/orders/1", &[]).version, "v1");
+        assert_eq!(route("/orders/1", &[("cookie", "a=b; canary=1")]).version, "v2");
+
+        let weights = [("v1".to_string(), 0), ("v2".to_string(), 1)].into_iter().collect();
+        router.set_weights("orders", &weights).unwrap();
+        assert_eq!(route("/orders/1", &[]).module, "orders-v2");
+        assert!(router.set_weights("orders", &[("v3".to_string(), 1)].into_iter().collect()).is_err());
+
+        router.set_version("orders", "v3", "orders-v3.wasm", Some(1)).unwrap();
+        let status = router.status();
+        assert_eq!(status.len(), 2);
+        assert_eq!(status[0].versions.iter().map(|v| (v.name.as_str(), v.weight)).collect::<Vec<_>>(),
+                   [("v1", 0), ("v2", 1), ("v3", 1)]);
+        assert!(router.set_version("payments", "v1", "payments.wasm", None).is_err());
+
+        // the deploy added v3, so rolling it back needs no module load
+        router.rollback("orders", "v3").unwrap();
+        assert_eq!(router.status()[0].versions.iter().map(|v| v.name.as_str()).collect::<Vec<_>>(), ["v1", "v2"]);
+        assert!(router.rollback("orders", "v3").is_err());
+
+        // runtime changes are kept when the services are replaced
+        let weights = [("v1".to_string(), 0), ("v2".to_string(), 1)].into_iter().collect();
+        router.set_weights("orders", &weights).unwrap();
+        router.set_version("orders", "v3", "orders-v3.wasm", Some(5)).unwrap();
+        router.replace(&config.services).unwrap();
+        assert_eq!(router.status()[0].versions.iter().map(|v| (v.wasm.as_str(), v.weight)).collect::<Vec<_>>(),
+                   [("orders-v1.wasm", 0), ("orders-v2.wasm", 1), ("orders-v3.wasm", 5)]);
+        router.rollback("orders", "v3").unwrap();
+        router.replace(&config.services).unwrap();
+        assert_eq!(router.status()[0].versions.len(), 2);
+
+        // a rollback failing to load the previous module keeps the deploy
+        router.set_version("orders", "v1", "orders-v5.wasm", None).unwrap();
+        let deploys = |router: &Router| router.history.lock().unwrap().get(&("orders".to_string(), "v1".to_string())).map_or(0, Vec::len);
+        assert!(router.rollback("orders", "v1").is_err());
+        assert_eq!(deploys(&router), 1);
+        // and one of a service a reload removed is forgotten
+        router.replace(&[]).unwrap();
+        assert_eq!(deploys(&router), 0);
+        router.replace(&config.services).unwrap();
+        assert_eq!(router.status()[0].versions[0].wasm, "orders-v1.wasm");
+
+        // a version named like the mirror keeps its own counters
+        router.set_version("orders", MIRROR_VERSION, "orders-v4.wasm", None).unwrap();
+        let services = router.services.read().unwrap();
+        let version = services[0].targets.iter().find(|t| t.version == MIRROR_VERSION).unwrap();
+        assert!(!Arc::ptr_eq(&version.stats, &services[0].mirror.as_ref().unwrap().stats));
+    }
+
+    #[test]
+    fn modules_run_from_a_private_copy() {
+        let dir = tempfile::tempdir().unwrap();
+        store::with_store(&dir.path().join("store"), || {
+            let path = dir.path().join("orders.wasm");
+            let wasm = b"\0asm\x01\0\0\0orders".to_vec();
+            fs::write(&path, &wasm).unwrap();
+            let config = PodConfig::parse(&format!(r#"
+                [[services]]
+                name = "orders"
+                path = "/orders*"
+                versions = [{{ name = "v1", wasm = "{}", weight = 1 }}]
+            "#, path.display())).unwrap();
+            let simple = dir.path().join("simple.wasm");
+            fs::write(&simple, b"\0asm\x01\0\0\0").unwrap();
+            let router = Router::new("simple", simple.to_str().unwrap());
+            router.update(&config.services).unwrap();
+
+            // the file changes once verified, the copy that was verified runs
+            fs::write(&path, b"\0asm\x01\0\0\0swapped").unwrap();
+            let target = router.route(&parts("/orders", &[])).unwrap().target;
+            assert_ne!(Path::new(&target.wasm_info.wasm_path), path);
+            assert_eq!(fs::read(&target.wasm_info.wasm_path).unwrap(), wasm);
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                let mode = fs::metadata(&target.wasm_info.wasm_path).unwrap().permissions().mode();
+                assert_eq!(mode & 0o777, 0o600);
+            }
+        });
+    }
+}
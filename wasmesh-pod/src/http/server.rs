@@ -1,43 +1,263 @@
-use std::cell::RefCell;
+use std::collections::HashMap;
 use std::net::SocketAddr;
-use std::thread::LocalKey;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use hyper::{Body, Error, Response};
-use hyper::server::conn::AddrStream;
-use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request, Response, StatusCode};
+use hyper::header::{CONTENT_TYPE, HeaderValue, RETRY_AFTER};
+use hyper::server::conn::Http;
+use hyper::service::service_fn;
+use rustls::ServerConfig;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpListener;
+use tokio::sync::{oneshot, Semaphore};
+use tokio_rustls::TlsAcceptor;
 use wasmy_vm::*;
 
 use wasmesh_proto::*;
 
-pub(crate) async fn serve(wasm_info: &'static LocalKey<RefCell<WasmInfo>>, addr: SocketAddr) -> anyhow::Result<()> {
-    // The closure inside `make_service_fn` is run for each connection,
-    // creating a 'service' to handle requests for that specific connection.
-    let make_service = make_service_fn(move |_socket: &AddrStream| {
+use crate::config;
+use crate::runtime::{handle_trap, is_trap};
+use crate::http::{Action, check_limits, Router, Target};
+use crate::tls;
+
+/// Mirrored requests running at once, more are dropped so mirroring never
+/// takes the blocking threads the primary path needs.
+const MAX_MIRRORS: usize = 32;
+
+static MIRRORS: Semaphore = Semaphore::const_new(MAX_MIRRORS);
+
+pub(crate) async fn serve(router: Arc<Router>, addr: SocketAddr, tls_config: Option<Arc<ServerConfig>>) -> anyhow::Result<()> {
+    let listener = TcpListener::bind(&addr).await?;
+    let acceptor = tls_config.map(TlsAcceptor::from);
+    println!("Listening on {}://{}", if acceptor.is_some() { "https" } else { "http" }, addr);
+    loop {
+        let (stream, remote_addr) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                eprintln!("SERVER accept error: {}", e);
+                continue;
+            }
+        };
         #[cfg(debug_assertions)] {
-            let remote_addr = _socket.remote_addr();
             println!("HTTP remote_addr = {:?}", remote_addr.to_string());
         }
+        let acceptor = acceptor.clone();
+        let router = router.clone();
+        tokio::spawn(async move {
+            match acceptor {
+                Some(acceptor) => match acceptor.accept(stream).await {
+                    Ok(stream) => {
+                        let peer = tls::peer_identity(stream.get_ref().1.peer_certificates());
+                        let conn = ConnInfo { remote_addr: Some(remote_addr), tls: true, peer_identity: peer.map(|id| id.uri()) };
+                        serve_connection(router, stream, conn).await
+                    }
+                    Err(e) => log_warn!("TLS handshake error: remote_addr={}, {}", remote_addr, e),
+                },
+                None => serve_connection(router, stream, ConnInfo { remote_addr: Some(remote_addr), ..Default::default() }).await,
+            }
+        });
+    }
+}
+
+async fn serve_connection<S>(router: Arc<Router>, stream: S, conn: ConnInfo)
+    where S: AsyncRead + AsyncWrite + Unpin + Send + 'static {
+    let conn = Arc::new(conn);
+    // `service_fn` is a helper to convert a function that
+    // returns a Response into a `Service`.
+    let service = service_fn(move |req: Request<Body>| {
+        let conn = conn.clone();
+        let router = router.clone();
         async move {
-            // This is the `Service` that will handle the connection.
-            // `service_fn` is a helper to convert a function that
-            // returns a Response into a `Service`.
-            Ok::<_, Error>(service_fn(move |req| async move {
-                let data = HttpRequest::from(req).await;
-                let r: anyhow::Result<Response<Body>> = wasm_info.with(|wi| {
-                    let resp: HttpResponse = call_wasm(wi.borrow().clone(), WasmMethod::W_HTTP.into(), data)?;
-                    Ok(resp.into())
-                });
-                if let Err(ref e) = r {
-                    eprintln!("{}", e)
+            let (parts, body) = req.into_parts();
+            let config = config::current();
+            if config.authz.evaluate(conn.peer_identity.as_deref(), &parts) == Action::Deny {
+                return Ok(forbidden());
+            }
+            let (target, mirror) = match router.route(&parts) {
+                Some(route) => (route.target, route.mirror),
+                None => return Ok(not_found()),
+            };
+            if let Err(retry_after) = check_limits(&config.limits, &target.module, conn.remote_addr, &parts).await {
+                return Ok(too_many_requests(retry_after));
+            }
+            let mut data = HttpRequest::from_conn(Request::from_parts(parts, body), &conn).await;
+            if data.request_id.is_empty() {
+                data.set_request_id(new_request_id());
+            }
+            let request_id = data.request_id.clone();
+            let primary = mirror.and_then(|mirror| shadow(mirror, data.clone()));
+            let start = Instant::now();
+            let in_flight = target.stats.enter();
+            let r: Result<HttpResponse> = call_wasm(target.wasm_info.clone(), WasmMethod::W_HTTP.into(), data);
+            drop(in_flight);
+            if let Some(primary) = primary {
+                let _ = primary.send(r.as_ref().ok().cloned());
+            }
+            let mut resp: Response<Body> = match r {
+                Ok(resp) => Response::try_from(resp).unwrap_or_else(|e| {
+                    let e = MeshError::with_code(ErrorCode::INTERNAL, format!("invalid response: {}", e));
+                    log_error!("{}/{}: request_id={}, {}", target.service, target.version, request_id, e);
+                    problem(&e, &request_id)
+                }),
+                Err(e) if is_trap(&e) => {
+                    let label = format!("{}/{}", target.service, target.version);
+                    target.stats.record_recycle();
+                    problem(&handle_trap(&target.wasm_info, &label, &request_id, &e), &request_id)
+                }
+                Err(e) => {
+                    let e = MeshError::from_code_msg(&e);
+                    log_error!("{}/{}: request_id={}, {}", target.service, target.version, request_id, e);
+                    problem(&e, &request_id)
                 }
-                r
-            }))
+            };
+            if let Ok(id) = HeaderValue::from_str(&request_id) {
+                resp.headers_mut().entry(REQUEST_ID_HEADER).or_insert(id);
+            }
+            target.stats.record(start.elapsed(), !resp.status().is_server_error());
+            Ok::<_, anyhow::Error>(resp)
         }
     });
-    let srv = hyper::Server::bind(&addr).serve(make_service);
-    println!("Listening on http://{}", addr);
-    if let Err(e) = srv.await {
+    if let Err(e) = Http::new().serve_connection(stream, service).await {
         eprintln!("SERVER error: {}", e);
     }
-    Ok(())
+}
+
+/// Runs a copy of the request against the mirror on a blocking thread, so the
+/// primary response is never delayed, then compares the responses once the
+/// primary's is sent on the returned channel. The copy is dropped when
+/// [`MAX_MIRRORS`] are already running.
+fn shadow(mirror: Arc<Target>, req: HttpRequest) -> Option<oneshot::Sender<Option<HttpResponse>>> {
+    let permit = match MIRRORS.try_acquire() {
+        Ok(permit) => permit,
+        Err(_) => {
+            mirror.stats.record_dropped();
+            return None;
+        }
+    };
+    let (tx, rx) = oneshot::channel::<Option<HttpResponse>>();
+    tokio::task::spawn_blocking(move || {
+        let _permit = permit;
+        let start = Instant::now();
+        let request_id = req.request_id.clone();
+        let in_flight = mirror.stats.enter();
+        let r: Result<HttpResponse> = call_wasm(mirror.wasm_info.clone(), WasmMethod::W_HTTP.into(), req);
+        drop(in_flight);
+        if let Err(e) = &r {
+            if is_trap(e) {
+                mirror.stats.record_recycle();
+                handle_trap(&mirror.wasm_info, &format!("{}/{}", mirror.service, mirror.version), &request_id, e);
+            }
+        }
+        mirror.stats.record(start.elapsed(), r.as_ref().is_ok_and(|resp| status_of(resp) < 500));
+        // nothing to compare with when the primary failed
+        let primary = match rx.blocking_recv() {
+            Ok(Some(primary)) => primary,
+            _ => return,
+        };
+        match r {
+            Ok(shadow) => if status_of(&shadow) != status_of(&primary) || shadow.body != primary.body {
+                mirror.stats.record_diff();
+                log_warn!("mirror diff: service={}, module={}, status={}/{}, body_len={}/{}",
+                          mirror.service, mirror.module, status_of(&primary), status_of(&shadow),
+                          primary.body.len(), shadow.body.len());
+            },
+            Err(e) => {
+                mirror.stats.record_diff();
+                log_warn!("mirror diff: service={}, module={}, error: {}", mirror.service, mirror.module, e);
+            }
+        }
+    });
+    Some(tx)
+}
+
+fn new_request_id() -> String {
+    format!("{:032x}", rand::random::<u128>())
+}
+
+fn status_of(resp: &HttpResponse) -> i32 {
+    if resp.status <= 0 { 200 } else { resp.status }
+}
+
+/// Renders a guest error as an RFC 7807 `application/problem+json` response.
+/// The message and details of a 5xx are replaced, callers log them with the request id instead.
+fn problem(e: &MeshError, request_id: &str) -> Response<Body> {
+    let status = StatusCode::from_u16(e.http_status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    let empty = HashMap::new();
+    let (detail, details) = if status.is_server_error() {
+        ("the request failed, see the pod log for its request_id", &empty)
+    } else {
+        (e.get_message(), e.get_details())
+    };
+    let body = serde_json::json!({
+        "type": format!("urn:wasmesh:error:{}", e.get_code().as_str().to_ascii_lowercase()),
+        "title": status.canonical_reason().unwrap_or_default(),
+        "status": status.as_u16(),
+        "code": e.get_code().as_str(),
+        "detail": detail,
+        "retryable": e.get_retryable(),
+        "details": details,
+        "request_id": request_id,
+    });
+    Response::builder()
+        .status(status)
+        .header(CONTENT_TYPE, "application/problem+json")
+        .body(Body::from(serde_json::to_vec(&body).unwrap_or_default()))
+        .unwrap()
+}
+
+fn not_found() -> Response<Body> {
+    Response::builder()
+        .status(StatusCode::NOT_FOUND)
+        .body(Body::from("not found"))
+        .unwrap()
+}
+
+fn forbidden() -> Response<Body> {
+    Response::builder()
+        .status(StatusCode::FORBIDDEN)
+        .body(Body::from("forbidden"))
+        .unwrap()
+}
+
+fn too_many_requests(retry_after: Duration) -> Response<Body> {
+    let secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+    Response::builder()
+        .status(StatusCode::TOO_MANY_REQUESTS)
+        .header(RETRY_AFTER, secs.max(1))
+        .body(Body::from("too many requests"))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn body_of(resp: Response<Body>) -> serde_json::Value {
+        serde_json::from_slice(&hyper::body::to_bytes(resp.into_body()).await.unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn problem_hides_server_errors() {
+        let resp = problem(&MeshError::with_code(ErrorCode::NOT_FOUND, "no such order"), "r1");
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        assert_eq!(body_of(resp).await["detail"], "no such order");
+
+        // a rejected extraction returned through `?`
+        let e = MeshError::with_code(ErrorCode::INVALID_ARGUMENT, "expected content-type application/json")
+            .with_detail(HTTP_STATUS_DETAIL, 415);
+        let resp = problem(&MeshError::from_code_msg(&e.into()), "r1");
+        assert_eq!(resp.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(body_of(resp).await["status"], 415);
+        // an upstream status is not the one of the answer
+        let e = MeshError::with_code(ErrorCode::UPSTREAM_FAILURE, "upstream responded with status 404").with_detail("status", 404);
+        assert_eq!(problem(&e, "r1").status(), StatusCode::BAD_GATEWAY);
+
+        let e = MeshError::with_code(ErrorCode::INTERNAL, "db password rejected").with_detail("host", "10.0.0.7");
+        let resp = problem(&e, "r2");
+        assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        let body = body_of(resp).await;
+        assert!(!body.to_string().contains("password") && !body.to_string().contains("10.0.0.7"), "{}", body);
+        assert_eq!(body["request_id"], "r2");
+    }
 }
@@ -1,7 +1,17 @@
 pub use crate::app::*;
 
+#[macro_use]
+mod logs;
+mod admin;
 mod app;
+mod config;
 mod http;
 mod proto;
 mod ns;
+mod oci;
+mod rpc;
 mod runtime;
+mod signing;
+mod store;
+pub mod testing;
+mod tls;
@@ -0,0 +1,55 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+/// Number of records kept for the admin API.
+const CAPACITY: usize = 1000;
+
+static LOGS: Mutex<Logs> = Mutex::new(Logs { next_seq: 1, entries: VecDeque::new() });
+
+struct Logs {
+    next_seq: u64,
+    entries: VecDeque<LogEntry>,
+}
+
+/// A pod log record.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub(crate) struct LogEntry {
+    pub(crate) seq: u64,
+    /// milliseconds since the UNIX epoch
+    pub(crate) time_ms: u64,
+    pub(crate) level: &'static str,
+    pub(crate) message: String,
+}
+
+/// Prints a record to stderr and keeps it for `GET /logs`.
+pub(crate) fn record(level: &'static str, message: String) {
+    eprintln!("{}", message);
+    let time_ms = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64;
+    let mut logs = LOGS.lock().unwrap();
+    let seq = logs.next_seq;
+    logs.next_seq += 1;
+    if logs.entries.len() == CAPACITY {
+        logs.entries.pop_front();
+    }
+    logs.entries.push_back(LogEntry { seq, time_ms, level, message });
+}
+
+/// Returns up to `limit` records after `after`, oldest first.
+pub(crate) fn tail(after: u64, limit: usize) -> Vec<LogEntry> {
+    LOGS.lock().unwrap().entries.iter().filter(|e| e.seq > after).take(limit).cloned().collect()
+}
+
+macro_rules! log_error {
+    ($($arg:tt)*) => { $crate::logs::record("error", format!($($arg)*)) };
+}
+
+macro_rules! log_warn {
+    ($($arg:tt)*) => { $crate::logs::record("warn", format!($($arg)*)) };
+}
+
+macro_rules! log_info {
+    ($($arg:tt)*) => { $crate::logs::record("info", format!($($arg)*)) };
+}
@@ -0,0 +1,44 @@
+use std::fmt;
+
+/// Trust domain of all wasmesh workload identities.
+pub(crate) const TRUST_DOMAIN: &str = "wasmesh";
+
+/// Workload identity of a module within a namespace.
+///
+/// Rendered as a SPIFFE-style URI: `spiffe://wasmesh/ns/<namespace>/module/<module>`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Identity {
+    pub(crate) namespace: String,
+    pub(crate) module: String,
+}
+
+impl Identity {
+    pub(crate) fn new(namespace: &str, module: &str) -> Self {
+        Identity { namespace: namespace.to_string(), module: module.to_string() }
+    }
+    /// Parses an identity from its URI form.
+    pub(crate) fn parse(uri: &str) -> Option<Self> {
+        let rest = uri.strip_prefix("spiffe://")?.strip_prefix(TRUST_DOMAIN)?.strip_prefix('/')?;
+        let mut parts = rest.split('/');
+        match (parts.next(), parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some("ns"), Some(namespace), Some("module"), Some(module), None)
+            if !namespace.is_empty() && !module.is_empty() => {
+                Some(Identity::new(namespace, module))
+            }
+            _ => None,
+        }
+    }
+    pub(crate) fn uri(&self) -> String {
+        format!("spiffe://{}/ns/{}/module/{}", TRUST_DOMAIN, self.namespace, self.module)
+    }
+    /// File stem of the identity's certificate and key in a CA directory.
+    pub(crate) fn file_stem(&self) -> String {
+        format!("{}.{}", self.namespace, self.module)
+    }
+}
+
+impl fmt::Display for Identity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.uri())
+    }
+}
@@ -0,0 +1,500 @@
+//! Modules packaged as OCI artifacts.
+//!
+//! Instead of a wasm file, `serve` accepts:
+//! - an OCI image layout directory, `path` or `path:tag`,
+//! - a `.tar` or `.tar.gz` archive of one, `path` or `path:tag`,
+//! - a registry reference, `registry/repository[:tag][@sha256:<hex>]`.
+//!
+//! The wasm layer is found by its media type, verified and added to the
+//! module store, so the pod serves it as `sha256:<hex>`. A config of the media
+//! type [`CONFIG_MEDIA_TYPE`] carries the module's rate limits; other configs,
+//! like the one of `wasm-to-oci`, are ignored.
+
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::Context;
+use base64::Engine;
+use flate2::read::GzDecoder;
+use serde::{Deserialize, Serialize};
+
+use crate::http::{module_name, LimitRule};
+use crate::store::{self, DIGEST_PREFIX};
+
+/// Media types of a wasm module layer.
+pub(crate) const WASM_MEDIA_TYPES: &[&str] = &[
+    "application/wasm",
+    "application/vnd.wasm.content.layer.v1+wasm",
+    "application/vnd.module.wasm.content.layer.v1+wasm",
+];
+
+/// Media type of the config carrying a [`ModuleConfig`].
+pub(crate) const CONFIG_MEDIA_TYPE: &str = "application/vnd.wasmesh.module.config.v1+json";
+
+/// Manifest types asked from registries.
+const MANIFEST_MEDIA_TYPES: &str = "application/vnd.oci.image.index.v1+json, \
+    application/vnd.oci.image.manifest.v1+json, \
+    application/vnd.docker.distribution.manifest.list.v2+json, \
+    application/vnd.docker.distribution.manifest.v2+json";
+
+/// Annotation naming an image of a layout.
+const REF_NAME: &str = "org.opencontainers.image.ref.name";
+const TITLE: &str = "org.opencontainers.image.title";
+/// Annotation of a wasm layer carrying its detached signature, see [`crate::signing`].
+pub(crate) const SIGNATURE: &str = "dev.wasmesh.signature";
+
+/// Indexes followed before giving up on finding a manifest.
+const MAX_DEPTH: usize = 4;
+
+/// How a module image wants to be run.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct ModuleConfig {
+    /// refused, modules are instantiated without WASI arguments
+    pub(crate) args: Vec<String>,
+    /// refused, modules are instantiated without WASI environment
+    pub(crate) env: BTreeMap<String, String>,
+    /// refused, modules are instantiated without WASI pre-opened directories
+    pub(crate) preopens: Vec<String>,
+    /// rate limits, used when the pod config has none
+    pub(crate) limits: Vec<LimitRule>,
+}
+
+impl ModuleConfig {
+    /// Refuses the settings the pod cannot apply, rather than running the
+    /// module without them.
+    fn check(&self) -> anyhow::Result<()> {
+        let unsupported: Vec<&str> = [("args", self.args.is_empty()), ("env", self.env.is_empty()), ("preopens", self.preopens.is_empty())]
+            .into_iter()
+            .filter(|(_, empty)| !empty)
+            .map(|(name, _)| name)
+            .collect();
+        if !unsupported.is_empty() {
+            anyhow::bail!("module config sets {}, which wasmesh-pod cannot pass to a module", unsupported.join(", "));
+        }
+        Ok(())
+    }
+}
+
+/// A module image, its wasm layer added to the module store.
+#[derive(Debug, Clone)]
+pub(crate) struct Image {
+    /// module name, from the image title or where the image came from
+    pub(crate) name: String,
+    /// `sha256:<hex>` of the wasm layer
+    pub(crate) wasm: String,
+    pub(crate) config: ModuleConfig,
+}
+
+/// Opens the module image `reference` points to, `None` when it is a plain
+/// wasm file or names nothing that looks like an image.
+pub(crate) fn open(reference: &str) -> anyhow::Result<Option<Image>> {
+    let (path, tag) = split_tag(reference);
+    let context = || format!("open module image {}", reference);
+    if path.is_dir() {
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("module").to_string();
+        return pull(&mut Layout::Dir(path), tag, name).map(Some).with_context(context);
+    }
+    if path.is_file() {
+        let mut magic = [0u8; 4];
+        let n = fs::File::open(&path).and_then(|mut f| f.read(&mut magic)).with_context(context)?;
+        if magic[..n] == b"\0asm"[..n] {
+            return Ok(None);
+        }
+        let name = module_name(path.to_str().unwrap_or("module")).trim_end_matches(".tar").to_string();
+        let mut layout = Layout::archive(&path).with_context(context)?;
+        return pull(&mut layout, tag, name).map(Some).with_context(context);
+    }
+    match Registry::parse(reference) {
+        Some(mut registry) => {
+            let name = registry.repository.rsplit('/').next().unwrap_or("module").to_string();
+            pull(&mut registry, None, name).map(Some).with_context(context)
+        }
+        None => Ok(None),
+    }
+}
+
+/// Splits `path:tag` when `path` exists and the whole reference does not.
+fn split_tag(reference: &str) -> (PathBuf, Option<&str>) {
+    if !Path::new(reference).exists() {
+        if let Some((path, tag)) = reference.rsplit_once(':') {
+            if !tag.contains('/') && Path::new(path).exists() {
+                return (PathBuf::from(path), Some(tag));
+            }
+        }
+    }
+    (PathBuf::from(reference), None)
+}
+
+/// Where the manifests and blobs of an image are read from.
+trait Source {
+    /// Index or manifest the image starts from.
+    fn root(&mut self) -> anyhow::Result<Vec<u8>>;
+    fn manifest(&mut self, digest: &str) -> anyhow::Result<Vec<u8>>;
+    fn blob(&mut self, digest: &str) -> anyhow::Result<Vec<u8>>;
+    /// Tags of a root index select images by their ref name annotation.
+    fn tags_select_manifests(&self) -> bool {
+        true
+    }
+}
+
+/// An index or a manifest, told apart by their fields.
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+struct Document {
+    #[serde(default)]
+    manifests: Vec<Descriptor>,
+    #[serde(default)]
+    config: Option<Descriptor>,
+    #[serde(default)]
+    layers: Vec<Descriptor>,
+    #[serde(default)]
+    annotations: HashMap<String, String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+struct Descriptor {
+    media_type: String,
+    digest: String,
+    #[serde(default)]
+    annotations: HashMap<String, String>,
+    #[serde(default)]
+    platform: Option<Platform>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct Platform {
+    architecture: String,
+}
+
+fn pull(source: &mut dyn Source, tag: Option<&str>, name: String) -> anyhow::Result<Image> {
+    let mut doc: Document = serde_json::from_slice(&source.root()?).context("parse index")?;
+    let mut tag = tag.filter(|_| source.tags_select_manifests());
+    for _ in 0..MAX_DEPTH {
+        if doc.manifests.is_empty() {
+            return image(source, doc, name);
+        }
+        let next = select(&doc.manifests, tag.take())?;
+        doc = serde_json::from_slice(&source.manifest(&next.digest)?)
+            .with_context(|| format!("parse manifest {}", next.digest))?;
+    }
+    anyhow::bail!("indexes nested deeper than {}", MAX_DEPTH)
+}
+
+/// Picks the manifest of an index: the one tagged `tag`, the only one, or
+/// the one for the wasm architecture.
+fn select(manifests: &[Descriptor], tag: Option<&str>) -> anyhow::Result<Descriptor> {
+    if let Some(tag) = tag {
+        return manifests.iter()
+                        .find(|d| d.annotations.get(REF_NAME).map(String::as_str) == Some(tag))
+                        .cloned()
+                        .ok_or_else(|| anyhow::anyhow!("no image tagged {}", tag));
+    }
+    if let [only] = manifests {
+        return Ok(only.clone());
+    }
+    manifests.iter()
+             .find(|d| d.platform.as_ref().is_some_and(|p| p.architecture == "wasm"))
+             .cloned()
+             .ok_or_else(|| anyhow::anyhow!("{} images, name one with `:<tag>`", manifests.len()))
+}
+
+fn image(source: &mut dyn Source, manifest: Document, name: String) -> anyhow::Result<Image> {
+    let layer = manifest.layers.iter()
+                        .find(|l| WASM_MEDIA_TYPES.contains(&l.media_type.as_str()))
+                        .ok_or_else(|| anyhow::anyhow!("no wasm layer, expected one of {}", WASM_MEDIA_TYPES.join(", ")))?;
+    let hex = hex_of(&layer.digest)?;
+    // a layer is the module itself, so the store may already have it
+    if !store::contains(hex) {
+        store::put(&source.blob(&layer.digest)?)?;
+    }
+    if let Some(signature) = layer.annotations.get(SIGNATURE) {
+        store::put_signature(hex, signature.as_bytes())?;
+    }
+    let config = match &manifest.config {
+        Some(d) if d.media_type == CONFIG_MEDIA_TYPE => {
+            let config: ModuleConfig = serde_json::from_slice(&source.blob(&d.digest)?).context("parse module config")?;
+            config.check()?;
+            config
+        }
+        _ => ModuleConfig::default(),
+    };
+    let title = manifest.annotations.get(TITLE).or_else(|| layer.annotations.get(TITLE));
+    Ok(Image {
+        name: title.map_or(name, |t| module_name(t).to_string()),
+        wasm: layer.digest.clone(),
+        config,
+    })
+}
+
+fn hex_of(digest: &str) -> anyhow::Result<&str> {
+    digest.strip_prefix(DIGEST_PREFIX)
+          .filter(|hex| hex.len() == 64)
+          .ok_or_else(|| anyhow::anyhow!("unsupported digest {}, expected {}<hex>", digest, DIGEST_PREFIX))
+}
+
+/// Fails unless `bytes` have the given digest.
+fn verify(digest: &str, bytes: Vec<u8>) -> anyhow::Result<Vec<u8>> {
+    if store::sha256_hex(&bytes) != hex_of(digest)? {
+        anyhow::bail!("content of {} does not match its digest", digest);
+    }
+    Ok(bytes)
+}
+
+/// An OCI image layout, in a directory or read from an archive.
+enum Layout {
+    Dir(PathBuf),
+    Archive(HashMap<String, Vec<u8>>),
+}
+
+impl Layout {
+    fn archive(path: &Path) -> anyhow::Result<Self> {
+        let bytes = fs::read(path)?;
+        let reader: Box<dyn Read> = match bytes.starts_with(&[0x1f, 0x8b]) {
+            true => Box::new(GzDecoder::new(&bytes[..])),
+            false => Box::new(&bytes[..]),
+        };
+        let mut files = HashMap::new();
+        for entry in tar::Archive::new(reader).entries()? {
+            let mut entry = entry?;
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+            let name = entry.path()?.to_string_lossy().trim_start_matches("./").to_string();
+            let mut content = Vec::new();
+            entry.read_to_end(&mut content)?;
+            files.insert(name, content);
+        }
+        Ok(Layout::Archive(files))
+    }
+
+    fn read(&self, name: &str) -> anyhow::Result<Vec<u8>> {
+        match self {
+            Layout::Dir(dir) => fs::read(dir.join(name)).with_context(|| format!("read {}", name)),
+            Layout::Archive(files) => files.get(name).cloned().ok_or_else(|| anyhow::anyhow!("archive has no {}", name)),
+        }
+    }
+}
+
+impl Source for Layout {
+    fn root(&mut self) -> anyhow::Result<Vec<u8>> {
+        self.read("index.json")
+    }
+    fn manifest(&mut self, digest: &str) -> anyhow::Result<Vec<u8>> {
+        self.blob(digest)
+    }
+    fn blob(&mut self, digest: &str) -> anyhow::Result<Vec<u8>> {
+        verify(digest, self.read(&format!("blobs/sha256/{}", hex_of(digest)?))?)
+    }
+}
+
+/// An image of a registry speaking the OCI distribution API.
+///
+/// Pulls are anonymous unless `WASMESH_REGISTRY_USER` and
+/// `WASMESH_REGISTRY_PASSWORD` are set, which are only sent to a token realm
+/// on the registry host.
+struct Registry {
+    host: String,
+    base: String,
+    repository: String,
+    /// tag or digest
+    reference: String,
+    token: Option<String>,
+}
+
+impl Registry {
+    /// Parses `registry/repository[:tag][@digest]`, the first component must
+    /// name a host so missing files are not taken for images.
+    fn parse(reference: &str) -> Option<Self> {
+        let (host, rest) = reference.split_once('/')?;
+        if !(host.contains('.') || host.contains(':') || host == "localhost") {
+            return None;
+        }
+        let (repository, reference) = match rest.split_once('@') {
+            Some((repository, digest)) => (repository.split(':').next().unwrap_or(repository), digest.to_string()),
+            None => match rest.rsplit_once(':') {
+                Some((repository, tag)) if !tag.contains('/') => (repository, tag.to_string()),
+                _ => (rest, "latest".to_string()),
+            },
+        };
+        let scheme = if host.starts_with("localhost") || host.starts_with("127.") { "http" } else { "https" };
+        let (host, repository) = match host {
+            "docker.io" if !repository.contains('/') => ("registry-1.docker.io", format!("library/{}", repository)),
+            "docker.io" => ("registry-1.docker.io", repository.to_string()),
+            _ => (host, repository.to_string()),
+        };
+        Some(Registry {
+            host: host.to_string(),
+            base: format!("{}://{}/v2/{}", scheme, host, repository),
+            repository,
+            reference,
+            token: None,
+        })
+    }
+
+    fn get(&mut self, path: &str, accept: &str) -> anyhow::Result<Vec<u8>> {
+        let url = format!("{}/{}", self.base, path);
+        let mut retried = false;
+        loop {
+            let mut req = ureq::get(&url).timeout(Duration::from_secs(60)).set("accept", accept);
+            if let Some(token) = &self.token {
+                req = req.set("authorization", &format!("Bearer {}", token));
+            }
+            match req.call() {
+                Ok(resp) => {
+                    let mut bytes = Vec::new();
+                    resp.into_reader().read_to_end(&mut bytes)?;
+                    return Ok(bytes);
+                }
+                Err(ureq::Error::Status(401, resp)) if !retried => {
+                    let challenge = resp.header("www-authenticate").unwrap_or_default().to_string();
+                    self.token = Some(self.authenticate(&challenge)?);
+                    retried = true;
+                }
+                Err(e) => return Err(anyhow::anyhow!("GET {}: {}", url, e)),
+            }
+        }
+    }
+
+    /// Gets a token for a `Bearer realm="…",service="…",scope="…"` challenge.
+    fn authenticate(&self, challenge: &str) -> anyhow::Result<String> {
+        let params: HashMap<&str, &str> = challenge.strip_prefix("Bearer ")
+                                                   .ok_or_else(|| anyhow::anyhow!("unsupported challenge {:?}", challenge))?
+                                                   .split(',')
+                                                   .filter_map(|p| p.split_once('='))
+                                                   .map(|(k, v)| (k.trim(), v.trim().trim_matches('"')))
+                                                   .collect();
+        let realm = params.get("realm").ok_or_else(|| anyhow::anyhow!("challenge without realm"))?;
+        let mut req = ureq::get(realm).timeout(Duration::from_secs(30));
+        for key in ["service", "scope"] {
+            if let Some(value) = params.get(key) {
+                req = req.query(key, value);
+            }
+        }
+        let credentials = (env::var("WASMESH_REGISTRY_USER"), env::var("WASMESH_REGISTRY_PASSWORD"));
+        if let (Ok(user), Ok(password), true) = (credentials.0, credentials.1, self.is_own_realm(realm)) {
+            let basic = base64::engine::general_purpose::STANDARD.encode(format!("{}:{}", user, password));
+            req = req.set("authorization", &format!("Basic {}", basic));
+        }
+        let resp: serde_json::Value = req.call().context("get registry token")?.into_json()?;
+        resp.get("token").or_else(|| resp.get("access_token"))
+            .and_then(|t| t.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| anyhow::anyhow!("registry token response without token"))
+    }
+
+    /// Whether a token realm is on the registry host, a challenge may point
+    /// anywhere.
+    fn is_own_realm(&self, realm: &str) -> bool {
+        let authority = realm.split_once("://").map_or("", |(_, rest)| rest.split(['/', '?', '#']).next().unwrap_or(rest));
+        let host = authority.rsplit('@').next().unwrap_or(authority);
+        host.eq_ignore_ascii_case(&self.host)
+    }
+}
+
+impl Source for Registry {
+    fn root(&mut self) -> anyhow::Result<Vec<u8>> {
+        let reference = self.reference.clone();
+        let bytes = self.get(&format!("manifests/{}", reference), MANIFEST_MEDIA_TYPES)?;
+        match reference.starts_with(DIGEST_PREFIX) {
+            true => verify(&reference, bytes),
+            false => Ok(bytes),
+        }
+    }
+    fn manifest(&mut self, digest: &str) -> anyhow::Result<Vec<u8>> {
+        let bytes = self.get(&format!("manifests/{}", digest), MANIFEST_MEDIA_TYPES)?;
+        verify(digest, bytes)
+    }
+    fn blob(&mut self, digest: &str) -> anyhow::Result<Vec<u8>> {
+        let bytes = self.get(&format!("blobs/{}", digest), "*/*")?;
+        verify(digest, bytes)
+    }
+    fn tags_select_manifests(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blob(dir: &Path, bytes: &[u8]) -> String {
+        let hex = store::sha256_hex(bytes);
+        fs::write(dir.join("blobs/sha256").join(&hex), bytes).unwrap();
+        format!("{}{}", DIGEST_PREFIX, hex)
+    }
+
+    #[test]
+    fn credentials_stay_on_the_registry_host() {
+        let registry = Registry::parse("ghcr.io/acme/orders:v3").unwrap();
+        assert!(registry.is_own_realm("https://ghcr.io/token"));
+        assert!(!registry.is_own_realm("https://evil.example/token"));
+        assert!(!registry.is_own_realm("https://ghcr.io@evil.example/token"));
+        assert!(!registry.is_own_realm("https://ghcr.io.evil.example/token"));
+    }
+
+    #[test]
+    fn unsupported_settings_are_refused() {
+        let config: ModuleConfig = serde_json::from_str(r#"{"args": ["--verbose"], "env": {"REGION": "eu"}}"#).unwrap();
+        assert_eq!(config.check().unwrap_err().to_string(), "module config sets args, env, which wasmesh-pod cannot pass to a module");
+        let config: ModuleConfig = serde_json::from_str(r#"{"limits": [{"key": "module", "rate": 10.0}]}"#).unwrap();
+        assert!(config.check().is_ok());
+    }
+
+    #[test]
+    fn layout_and_archive() {
+        let dir = tempfile::tempdir().unwrap();
+        store::with_store(&dir.path().join("store"), || layout_and_archive_in(dir.path()));
+    }
+
+    fn layout_and_archive_in(dir: &Path) {
+        let layout = dir.join("orders");
+        fs::create_dir_all(layout.join("blobs/sha256")).unwrap();
+        let wasm = b"\0asm\x01\0\0\0orders".to_vec();
+        let layer = blob(&layout, &wasm);
+        let config = blob(&layout, br#"{"limits": [{"key": "module", "rate": 10.0}]}"#);
+        let manifest = serde_json::json!({
+            "schemaVersion": 2,
+            "mediaType": "application/vnd.oci.image.manifest.v1+json",
+            "config": { "mediaType": CONFIG_MEDIA_TYPE, "digest": config, "size": 0 },
+            "layers": [{ "mediaType": "application/vnd.wasm.content.layer.v1+wasm", "digest": layer, "size": wasm.len() }],
+        });
+        let manifest = blob(&layout, manifest.to_string().as_bytes());
+        let index = serde_json::json!({
+            "schemaVersion": 2,
+            "manifests": [{
+                "mediaType": "application/vnd.oci.image.manifest.v1+json",
+                "digest": manifest,
+                "size": 0,
+                "annotations": { REF_NAME: "v1" },
+            }],
+        });
+        fs::write(layout.join("index.json"), index.to_string()).unwrap();
+        fs::write(layout.join("oci-layout"), r#"{"imageLayoutVersion": "1.0.0"}"#).unwrap();
+
+        let image = open(layout.to_str().unwrap()).unwrap().unwrap();
+        assert_eq!(image.name, "orders");
+        assert_eq!(image.wasm, layer);
+        assert_eq!(image.config.limits.len(), 1);
+        assert_eq!(fs::read(store::path(&layer)).unwrap(), wasm);
+        let e = open(&format!("{}:v2", layout.display())).unwrap_err();
+        assert_eq!(format!("{:#}", e).rsplit(": ").next(), Some("no image tagged v2"));
+
+        let archive = dir.join("orders.tar");
+        let mut builder = tar::Builder::new(fs::File::create(&archive).unwrap());
+        builder.append_dir_all(".", &layout).unwrap();
+        builder.finish().unwrap();
+        let image = open(&format!("{}:v1", archive.display())).unwrap().unwrap();
+        assert_eq!((image.name.as_str(), image.wasm.as_str()), ("orders", layer.as_str()));
+
+        let plain = dir.join("plain.wasm");
+        fs::write(&plain, &wasm).unwrap();
+        assert!(open(plain.to_str().unwrap()).unwrap().is_none());
+        assert!(open("missing.wasm").unwrap().is_none());
+    }
+}
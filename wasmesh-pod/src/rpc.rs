@@ -0,0 +1,245 @@
+//! Subscription of a pod to a control plane over the RPC transport.
+//!
+//! The pod dials the control plane given by `--rpc`, subscribes, then applies
+//! every update it receives and acknowledges it, or rejects it with the reason.
+//! While subscribed, the control plane owns the services, limits and authz
+//! policy of the pod. A pod with an mTLS identity (`--tls-ca-dir`) dials the
+//! control plane over mutual TLS.
+
+use std::collections::BTreeMap;
+use std::io;
+use std::net::{Shutdown, TcpStream};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::Context;
+use rustls::{ClientConnection, StreamOwned};
+use rustls::pki_types::ServerName;
+use wasmesh_proto::*;
+use wasmesh_proto::rpc::{write_frame, FrameReader, Stream};
+
+use crate::config;
+use crate::http::{AuthzPolicy, LimitRule, Router, ServiceConfig, validate_limits};
+use crate::tls;
+
+/// How often the pod reports its status while subscribed.
+const STATUS_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Wait for the control plane to answer while connecting.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Wait before the first reconnection, doubled up to `MAX_BACKOFF`.
+const MIN_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Keeps the pod subscribed to the control plane at `addr`, reconnecting
+/// with backoff when the stream breaks.
+pub(crate) fn subscribe(router: Arc<Router>, addr: String, pod_id: String) {
+    config::set_plane_owned();
+    thread::spawn(move || {
+        let mut subscription = Subscription::default();
+        let mut backoff = MIN_BACKOFF;
+        loop {
+            match subscription.stream(&router, &addr, &pod_id, &mut backoff) {
+                Ok(()) => log_warn!("control plane {}: stream closed", addr),
+                Err(e) => log_warn!("control plane {}: {}", addr, e),
+            }
+            thread::sleep(backoff);
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+    });
+}
+
+/// Dials the control plane at `addr`, over mutual TLS when the pod has an
+/// identity. Returns the socket too, for its timeouts, and reads time out
+/// after `timeout`.
+pub(crate) fn connect(addr: &str, timeout: Duration) -> io::Result<(TcpStream, Box<dyn Stream>)> {
+    let socket = TcpStream::connect(addr)?;
+    socket.set_read_timeout(Some(timeout))?;
+    let conn: Box<dyn Stream> = match tls::mesh_client() {
+        Some(client) => {
+            let host = addr.rsplit_once(':').map_or(addr, |(host, _)| host).trim_start_matches('[').trim_end_matches(']');
+            let name = ServerName::try_from(host.to_string()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+            let tls = ClientConnection::new(client.config(), name).map_err(io::Error::other)?;
+            Box::new(StreamOwned::new(tls, socket.try_clone()?))
+        }
+        None => Box::new(socket.try_clone()?),
+    };
+    Ok((socket, conn))
+}
+
+/// Desired state received from the control plane.
+#[derive(Default)]
+struct Subscription {
+    version: String,
+    // why the last update was rejected
+    error: String,
+    resources: BTreeMap<String, Resource>,
+}
+
+impl Subscription {
+    fn stream(&mut self, router: &Router, addr: &str, pod_id: &str, backoff: &mut Duration) -> io::Result<()> {
+        let (socket, mut conn) = connect(addr, CONNECT_TIMEOUT)?;
+        let mut subscribe = Subscribe::new();
+        subscribe.set_pod_id(pod_id.to_string());
+        subscribe.set_version(self.version.clone());
+        let mut msg = PodMessage::new();
+        msg.set_subscribe(subscribe);
+        write_frame(&mut conn, &msg)?;
+        *backoff = MIN_BACKOFF;
+        log_info!("control plane {}: subscribed as {}", addr, pod_id);
+
+        // a read times out when no update came for a while, then the status goes out
+        socket.set_read_timeout(Some(STATUS_INTERVAL))?;
+        let mut frames = FrameReader::new();
+        let r = loop {
+            let msg = match frames.read::<PlaneMessage, _>(&mut conn) {
+                Ok(Some(msg)) => msg,
+                Ok(None) => {
+                    if let Err(e) = write_frame(&mut conn, &self.status(router)) {
+                        break Err(e);
+                    }
+                    continue;
+                }
+                Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => break Ok(()),
+                Err(e) => break Err(e),
+            };
+            let update = match msg.kind {
+                Some(PlaneMessage_oneof_kind::update(update)) => update,
+                _ => continue,
+            };
+            let mut ack = Ack::new();
+            ack.set_version(update.version.clone());
+            ack.set_nonce(update.nonce.clone());
+            match self.apply(router, &update) {
+                Ok(()) => {
+                    self.error.clear();
+                    log_info!("control plane: applied version {}", update.version);
+                }
+                Err(e) => {
+                    self.error = format!("{:#}", e);
+                    log_error!("control plane: rejected version {}: {}", update.version, self.error);
+                    ack.set_error(self.error.clone());
+                }
+            }
+            let mut msg = PodMessage::new();
+            msg.set_ack(ack);
+            if let Err(e) = write_frame(&mut conn, &msg).and_then(|_| write_frame(&mut conn, &self.status(router))) {
+                break Err(e);
+            }
+        };
+        let _ = socket.shutdown(Shutdown::Both);
+        r
+    }
+
+    /// Applies an update, keeping the current state when it cannot be.
+    fn apply(&mut self, router: &Router, update: &ConfigUpdate) -> anyhow::Result<()> {
+        let mut resources = if update.full { BTreeMap::new() } else { self.resources.clone() };
+        for name in &update.removed {
+            resources.remove(name);
+        }
+        for r in &update.resources {
+            resources.insert(r.name.clone(), r.clone());
+        }
+        let desired = Desired::parse(&resources)?;
+        // only services load modules, leave them alone when they did not change
+        let services = |resources: &BTreeMap<String, Resource>| {
+            resources.iter().filter(|(name, _)| name.starts_with("service/")).map(|(_, r)| r.clone()).collect::<Vec<_>>()
+        };
+        if services(&resources) != services(&self.resources) {
+            router.update(&desired.services)?;
+        }
+        config::update(|config| {
+            config.limits = desired.limits;
+            config.authz = desired.authz;
+        });
+        self.resources = resources;
+        self.version = update.version.clone();
+        Ok(())
+    }
+
+    fn status(&self, router: &Router) -> PodMessage {
+        let mut status = PodStatus::new();
+        status.set_version(self.version.clone());
+        status.set_error(self.error.clone());
+        status.set_services(router.configs().len() as u32);
+        for v in router.status().iter().flat_map(|s| &s.versions) {
+            status.requests += v.requests;
+            status.errors += v.errors;
+        }
+        let mut msg = PodMessage::new();
+        msg.set_status(status);
+        msg
+    }
+}
+
+/// Resources of the desired state, decoded.
+struct Desired {
+    services: Vec<ServiceConfig>,
+    limits: Vec<LimitRule>,
+    authz: AuthzPolicy,
+}
+
+impl Desired {
+    fn parse(resources: &BTreeMap<String, Resource>) -> anyhow::Result<Self> {
+        let mut services = Vec::new();
+        let mut desired = Desired { services: Vec::new(), limits: Vec::new(), authz: AuthzPolicy::default() };
+        for (name, r) in resources {
+            let context = || format!("resource {}", name);
+            match name.as_str() {
+                "limits" => {
+                    desired.limits = serde_json::from_str(&r.json).with_context(context)?;
+                    validate_limits(&desired.limits).with_context(context)?;
+                }
+                "authz" => desired.authz = serde_json::from_str(&r.json).with_context(context)?,
+                _ if name.starts_with("service/") => {
+                    let service: ServiceConfig = serde_json::from_str(&r.json).with_context(context)?;
+                    services.push((r.order, service));
+                }
+                _ => anyhow::bail!("unknown resource {}", name),
+            }
+        }
+        services.sort_by_key(|(order, _)| *order);
+        desired.services = services.into_iter().map(|(_, s)| s).collect();
+        Ok(desired)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resource(name: &str, json: &str) -> Resource {
+        let mut r = Resource::new();
+        r.set_name(name.to_string());
+        r.set_json(json.to_string());
+        r
+    }
+
+    #[test]
+    fn updates_are_incremental() {
+        let router = Router::new("default", "default.wasm");
+        let mut subscription = Subscription::default();
+        let mut update = ConfigUpdate::new();
+        update.set_version("1".to_string());
+        update.set_full(true);
+        update.resources.push(resource("limits", r#"[{"key": "client_ip", "rate": 5.0}]"#));
+        update.resources.push(resource("authz", r#"{"rules": [{"paths": ["/public/*"]}]}"#));
+        subscription.apply(&router, &update).unwrap();
+        assert_eq!(config::current().limits.len(), 1);
+
+        let mut update = ConfigUpdate::new();
+        update.set_version("2".to_string());
+        update.removed.push("limits".to_string());
+        subscription.apply(&router, &update).unwrap();
+        assert!(config::current().limits.is_empty());
+        assert_eq!(subscription.resources.keys().collect::<Vec<_>>(), ["authz"]);
+
+        let mut update = ConfigUpdate::new();
+        update.set_version("3".to_string());
+        update.resources.push(resource("limits", r#"[{"key": "nope", "rate": 5.0}]"#));
+        assert!(subscription.apply(&router, &update).is_err());
+        assert_eq!(subscription.version, "2");
+    }
+}
@@ -1,23 +1,65 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::Duration;
+
 use wasmy_vm::*;
 
 use wasmesh_proto::*;
 
-thread_local! {static AGENT: ureq::Agent = ureq::builder().build();}
+use crate::ns::Identity;
+use crate::tls;
+
+thread_local! {
+    static AGENT: ureq::Agent = ureq::builder().tls_config(tls::public_client_config()).build();
+    // by the identity the server must prove
+    static MESH_AGENTS: RefCell<HashMap<Identity, ureq::Agent>> = RefCell::new(HashMap::new());
+    static OUTBOUND_STUB: RefCell<Option<OutboundStub>> = RefCell::new(None);
+}
+
+/// Answers the outbound requests of guests instead of the network.
+pub(crate) type OutboundStub = Rc<dyn Fn(&HttpRequest) -> std::result::Result<HttpResponse, MeshError>>;
+
+/// Runs `f` with the outbound requests made on this thread answered by `stub`.
+///
+/// The previous stub is restored afterwards, also when `f` panics.
+pub(crate) fn with_outbound_stub<R>(stub: OutboundStub, f: impl FnOnce() -> R) -> R {
+    let _restore = RestoreStub(OUTBOUND_STUB.with(|s| s.borrow_mut().replace(stub)));
+    f()
+}
+
+struct RestoreStub(Option<OutboundStub>);
+
+impl Drop for RestoreStub {
+    fn drop(&mut self) {
+        let prev = self.0.take();
+        OUTBOUND_STUB.with(|s| *s.borrow_mut() = prev);
+    }
+}
 
 // wasmesh_pod::VmMethod::V_HTTP
 #[vm_handler(0)]
 fn request(req: HttpRequest) -> Result<HttpResponse> {
     #[cfg(debug_assertions)]  println!("http: got request = {:?}", req);
-    let mut builder = AGENT.with(|agent| {
-        agent.request(
-            req.get_method().as_str(),
-            req.get_url(),
-        )
-    });
+    if let Some(stub) = OUTBOUND_STUB.with(|s| s.borrow().clone()) {
+        return stub(&req).map_err(Into::into);
+    }
+    let method = req.http_method().map_err(|_| {
+        MeshError::with_code(ErrorCode::INVALID_ARGUMENT, format!("invalid method {:?}", req.method_str()))
+    })?;
+    let mut builder = agent(&req)?.request(method.as_str(), req.get_url());
+    if req.timeout_ms > 0 {
+        builder = builder.timeout(Duration::from_millis(req.timeout_ms as u64));
+    }
     for (header, value) in req.get_headers() {
         builder = builder.set(header, value);
     }
-    let resp = builder.send(req.body.as_ref()).map_err(|e| ERR_CODE_UNKNOWN.to_code_msg(e))?;
+    let resp = match builder.send(req.body.as_ref()) {
+        Ok(resp) => resp,
+        // an error status is still a response the guest should see
+        Err(ureq::Error::Status(_, resp)) => resp,
+        Err(ureq::Error::Transport(e)) => return Err(transport_error(req.get_url(), &e).into()),
+    };
     let mut r = HttpResponse::new();
     r.set_status(resp.status() as i32);
     r.set_headers(resp.headers_names().iter().map(|name| (name.clone(), resp.header(&name).unwrap().to_string())).collect());
@@ -25,3 +67,99 @@ fn request(req: HttpRequest) -> Result<HttpResponse> {
     #[cfg(debug_assertions)]  println!("http: got response = {:?}", r);
     Ok(r)
 }
+
+/// Picks the agent of the call: public endpoints are reached with the web
+/// roots and no client certificate, mesh peers named by `peer_identity` over
+/// mutual TLS with the CA only.
+fn agent(req: &HttpRequest) -> std::result::Result<ureq::Agent, MeshError> {
+    if req.get_peer_identity().is_empty() {
+        return Ok(AGENT.with(|agent| agent.clone()));
+    }
+    let invalid = |message: String| MeshError::with_code(ErrorCode::INVALID_ARGUMENT, message)
+        .with_detail("peer_identity", req.get_peer_identity());
+    let peer = Identity::parse(req.get_peer_identity())
+        .ok_or_else(|| invalid("peer_identity is not a workload identity".to_string()))?;
+    if !req.get_url().starts_with("https://") {
+        return Err(invalid(format!("mesh call to {} must use https", req.get_url())));
+    }
+    let client = tls::mesh_client().ok_or_else(|| invalid("mesh calls need the pod to run with --tls-ca-dir".to_string()))?;
+    Ok(MESH_AGENTS.with(|agents| {
+        agents.borrow_mut()
+              .entry(peer.clone())
+              .or_insert_with(|| ureq::builder().tls_config(client.pinned(peer)).build())
+              .clone()
+    }))
+}
+
+/// Classifies a failed outbound call so the guest can tell what went wrong.
+fn transport_error(url: &str, e: &ureq::Transport) -> MeshError {
+    let code = match e.kind() {
+        ureq::ErrorKind::Dns => ErrorCode::DNS_FAILURE,
+        ureq::ErrorKind::InvalidUrl | ureq::ErrorKind::UnknownScheme => ErrorCode::INVALID_ARGUMENT,
+        _ => {
+            let mut code = ErrorCode::UPSTREAM_FAILURE;
+            let mut source = std::error::Error::source(e);
+            while let Some(cause) = source {
+                if cause.downcast_ref::<rustls::Error>().is_some() {
+                    code = ErrorCode::TLS_FAILURE;
+                    break;
+                }
+                if let Some(io) = cause.downcast_ref::<std::io::Error>() {
+                    match io.kind() {
+                        std::io::ErrorKind::ConnectionRefused => code = ErrorCode::CONNECTION_REFUSED,
+                        std::io::ErrorKind::TimedOut | std::io::ErrorKind::WouldBlock => code = ErrorCode::TIMEOUT,
+                        _ => {}
+                    }
+                }
+                source = cause.source();
+            }
+            code
+        }
+    };
+    let retryable = matches!(code, ErrorCode::DNS_FAILURE | ErrorCode::CONNECTION_REFUSED | ErrorCode::TIMEOUT);
+    MeshError::with_code(code, e)
+        .with_retryable(retryable)
+        .with_detail("url", url)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic::{AssertUnwindSafe, catch_unwind};
+
+    use super::*;
+
+    fn status_of_stub() -> Option<i32> {
+        let stub = OUTBOUND_STUB.with(|s| s.borrow().clone())?;
+        stub(&HttpRequest::new()).ok().map(|resp| resp.status)
+    }
+
+    fn answer(status: i32) -> OutboundStub {
+        Rc::new(move |_| {
+            let mut resp = HttpResponse::new();
+            resp.set_status(status);
+            Ok(resp)
+        })
+    }
+
+    #[test]
+    fn stubs_are_restored_after_a_panic() {
+        with_outbound_stub(answer(200), || {
+            let r = catch_unwind(AssertUnwindSafe(|| with_outbound_stub(answer(503), || panic!("handler panicked"))));
+            assert!(r.is_err());
+            assert_eq!(status_of_stub(), Some(200));
+        });
+        assert_eq!(status_of_stub(), None);
+    }
+
+    #[test]
+    fn mesh_calls_need_https_and_an_identity() {
+        let mut req = HttpRequest::new();
+        req.set_url("http://orders/".to_string());
+        req.set_peer_identity("orders".to_string());
+        assert_eq!(agent(&req).unwrap_err().get_code(), ErrorCode::INVALID_ARGUMENT);
+        req.set_peer_identity("spiffe://wasmesh/ns/shop/module/orders".to_string());
+        assert!(agent(&req).unwrap_err().get_message().contains("https"));
+        req.set_peer_identity(String::new());
+        assert!(agent(&req).is_ok());
+    }
+}
@@ -1,2 +1,5 @@
-mod http;
+pub(crate) use http::{OutboundStub, with_outbound_stub};
+pub(crate) use trap::*;
 
+mod http;
+mod trap;
@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use wasmparser::{Name, NameSectionReader, Parser, Payload};
+use wasmy_vm::*;
+
+use wasmesh_proto::*;
+
+use crate::store;
+
+/// Function names from the `name` custom section of a module.
+#[derive(Debug, Default)]
+pub(crate) struct Symbols {
+    functions: HashMap<u32, String>,
+}
+
+impl Symbols {
+    pub(crate) fn load(wasm_path: &str) -> anyhow::Result<Self> {
+        Self::parse(&fs::read(wasm_path)?)
+    }
+
+    pub(crate) fn parse(wasm: &[u8]) -> anyhow::Result<Self> {
+        let mut functions = HashMap::new();
+        for payload in Parser::new(0).parse_all(wasm) {
+            let section = match payload? {
+                Payload::CustomSection(section) if section.name() == "name" => section,
+                _ => continue,
+            };
+            for name in NameSectionReader::new(section.data(), section.data_offset()) {
+                if let Name::Function(map) = name? {
+                    for naming in map {
+                        let naming = naming?;
+                        functions.insert(naming.index, naming.name.to_string());
+                    }
+                }
+            }
+        }
+        Ok(Symbols { functions })
+    }
+
+    /// Names the functions of a trap backtrace, whose frames look like
+    /// `at <unnamed> (orders.wasm[12]:0x1c2)` or `0x1c2 - <wasm function 12>`.
+    pub(crate) fn symbolicate(&self, trace: &str) -> String {
+        trace.lines().map(|line| {
+            let index = function_index(line);
+            match index.and_then(|i| self.functions.get(&i).map(|name| (i, name))) {
+                Some((i, name)) => {
+                    let wasm_function = format!("<wasm function {}>", i);
+                    if line.contains("<unnamed>") {
+                        line.replacen("<unnamed>", name, 1)
+                    } else if line.contains(&wasm_function) {
+                        line.replacen(&wasm_function, name, 1)
+                    } else {
+                        format!("{} = {}", line, name)
+                    }
+                }
+                None => line.to_string(),
+            }
+        }).collect::<Vec<_>>().join("\n")
+    }
+}
+
+fn function_index(line: &str) -> Option<u32> {
+    if let Some(pos) = line.find("<wasm function ") {
+        let rest = &line[pos + "<wasm function ".len()..];
+        return rest.split('>').next()?.parse().ok();
+    }
+    let pos = line.find("]:0x")?;
+    let start = line[..pos].rfind('[')? + 1;
+    line[start..pos].parse().ok()
+}
+
+/// Messages of wasmer's `TrapCode`s, and of the VM running out of memory.
+const TRAP_MESSAGES: &[&str] = &[
+    "call stack exhausted",
+    "out of bounds memory access",
+    "misaligned heap",
+    "undefined element: out of bounds table access",
+    "out of bounds",
+    "uninitialized element",
+    "indirect call type mismatch",
+    "integer overflow",
+    "integer divide by zero",
+    "invalid conversion to integer",
+    "unreachable",
+    "unaligned atomic access",
+    "Wasmer VM out of memory",
+];
+
+/// Symbols of the modules that trapped, by path.
+static SYMBOLS: OnceLock<Mutex<HashMap<String, Arc<Symbols>>>> = OnceLock::new();
+
+/// Whether a `call_wasm` error is a trap of the instance rather than an error
+/// returned by the handler.
+///
+/// A trap is a wasmer `RuntimeError`, whose first line reads
+/// `RuntimeError: <trap code message>`; handler errors are encoded
+/// `MeshError`s or messages of their own.
+pub(crate) fn is_trap(e: &CodeMsg) -> bool {
+    let first = e.msg.lines().next().unwrap_or_default();
+    match first.strip_prefix("RuntimeError: ") {
+        Some(message) => TRAP_MESSAGES.contains(&message),
+        None => false,
+    }
+}
+
+/// Reloads the module in the background so that the next requests do not run
+/// on the state the trap left behind, and logs the symbolicated trap there.
+pub(crate) fn handle_trap(wasm_info: &WasmInfo, label: &str, request_id: &str, e: &CodeMsg) -> MeshError {
+    let (wasm_info, label, request_id, msg) = (wasm_info.clone(), label.to_string(), request_id.to_string(), e.msg.clone());
+    tokio::task::spawn_blocking(move || {
+        let trace = match symbols(&wasm_info.wasm_path) {
+            Ok(symbols) => symbols.symbolicate(&msg),
+            Err(err) => {
+                log_warn!("{}: failed to read symbols: {}", label, err);
+                msg
+            }
+        };
+        log_error!("{}: request_id={}, guest trapped:\n{}", label, request_id, trace);
+        if let Err(err) = reload(wasm_info) {
+            log_error!("{}: failed to recycle instance: {:#}", label, err);
+        }
+    });
+    MeshError::with_code(ErrorCode::INTERNAL, "guest trapped")
+        .with_detail("trap", e.msg.lines().next().unwrap_or_default())
+}
+
+/// Loads a module of the store again, once its file still holds the content
+/// its name says: a file swapped since it was verified is not loaded.
+fn reload(wasm_info: WasmInfo) -> anyhow::Result<()> {
+    let path = Path::new(&wasm_info.wasm_path);
+    let bytes = fs::read(path)?;
+    if path.file_stem().and_then(|stem| stem.to_str()) != Some(store::sha256_hex(&bytes).as_str()) {
+        anyhow::bail!("{} does not hold the module that was verified", path.display());
+    }
+    load_wasm(wasm_info).map_err(|e| anyhow::anyhow!("{}", e))
+}
+
+/// Returns the symbols of the module, read once per path.
+fn symbols(wasm_path: &str) -> anyhow::Result<Arc<Symbols>> {
+    let cache = SYMBOLS.get_or_init(Default::default);
+    if let Some(symbols) = cache.lock().unwrap().get(wasm_path) {
+        return Ok(symbols.clone());
+    }
+    let symbols = Arc::new(Symbols::load(wasm_path)?);
+    cache.lock().unwrap().insert(wasm_path.to_string(), symbols.clone());
+    Ok(symbols)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn symbolicate_frames() {
+        let symbols = Symbols { functions: HashMap::from([(12, "orders::create".to_string()), (3, "core::panicking::panic".to_string())]) };
+        let trace = "RuntimeError: unreachable\n    at <unnamed> (orders.wasm[3]:0x1c2)\n    0x2d0 - <wasm function 12>\n    at main (orders.wasm[40]:0x99)";
+        assert_eq!(symbols.symbolicate(trace),
+                   "RuntimeError: unreachable\n    at core::panicking::panic (orders.wasm[3]:0x1c2)\n    0x2d0 - orders::create\n    at main (orders.wasm[40]:0x99)");
+        assert!(is_trap(&CodeMsg::new(-1, trace)));
+        assert!(is_trap(&CodeMsg::new(-1, "RuntimeError: integer divide by zero")));
+        assert!(!is_trap(&CodeMsg::new(-1, "order not found")));
+        // handler messages that merely mention a trap
+        assert!(!is_trap(&CodeMsg::new(-1, "unreachable branch in pricing")));
+        assert!(!is_trap(&CodeMsg::new(-1, "RuntimeError: payment declined")));
+        assert!(!is_trap(&CodeMsg::new(-1, "order failed: RuntimeError: unreachable")));
+    }
+}
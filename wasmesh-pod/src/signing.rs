@@ -0,0 +1,107 @@
+//! Signature verification of modules before they are loaded.
+//!
+//! With `trusted_keys` in the pod config, no module runs unless its file has
+//! a detached ed25519 signature of its content, `<file>.sig`, made by one of
+//! those keys. A signature file holds the 64-byte signature, base64 encoded.
+
+use std::fs;
+use std::io;
+
+use anyhow::Context;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use ed25519_dalek::{Signature, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+/// An ed25519 public key modules may be signed with.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct TrustedKey {
+    /// shown in the admin API for the modules it signed
+    pub(crate) name: String,
+    /// 32-byte public key, base64 encoded
+    pub(crate) key: String,
+}
+
+impl TrustedKey {
+    pub(crate) fn verifying_key(&self) -> anyhow::Result<VerifyingKey> {
+        let bytes = STANDARD.decode(self.key.trim()).with_context(|| format!("trusted key {}", self.name))?;
+        let bytes: [u8; 32] = bytes.try_into()
+                                   .map_err(|_| anyhow::anyhow!("trusted key {}: expected 32 bytes", self.name))?;
+        VerifyingKey::from_bytes(&bytes).with_context(|| format!("trusted key {}", self.name))
+    }
+}
+
+/// Outcome of the signature check of a loaded module.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub(crate) enum Verification {
+    /// no trusted key is configured
+    NotRequired,
+    Verified { key: String },
+}
+
+/// Detached signature file of a module file.
+pub(crate) fn signature_path(file: &str) -> String {
+    format!("{}.sig", file)
+}
+
+/// Decodes the content of a signature file.
+pub(crate) fn parse_signature(encoded: &[u8]) -> anyhow::Result<Signature> {
+    let text = std::str::from_utf8(encoded).context("expected base64")?;
+    let bytes = STANDARD.decode(text.trim()).context("expected base64")?;
+    Signature::from_slice(&bytes).map_err(|_| anyhow::anyhow!("expected a 64-byte ed25519 signature"))
+}
+
+/// Checks the content of the module file `file` against the trusted keys.
+pub(crate) fn verify(keys: &[TrustedKey], file: &str, wasm: &[u8]) -> anyhow::Result<Verification> {
+    if keys.is_empty() {
+        return Ok(Verification::NotRequired);
+    }
+    let path = signature_path(file);
+    let encoded = match fs::read(&path) {
+        Ok(encoded) => encoded,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            anyhow::bail!("refused unsigned module {}: no signature file {}", file, path)
+        }
+        Err(e) => return Err(e).with_context(|| format!("read signature {}", path)),
+    };
+    let signature = parse_signature(&encoded).with_context(|| format!("refused module {}: invalid signature file {}", file, path))?;
+    for key in keys {
+        if key.verifying_key()?.verify_strict(wasm, &signature).is_ok() {
+            return Ok(Verification::Verified { key: key.name.clone() });
+        }
+    }
+    anyhow::bail!("refused badly signed module {}: {} matches no trusted key", file, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use ed25519_dalek::{Signer, SigningKey};
+
+    use super::*;
+
+    #[test]
+    fn modules_need_a_trusted_signature() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("orders.wasm").to_string_lossy().into_owned();
+        let wasm = b"\0asm\x01\0\0\0".to_vec();
+        let signer = SigningKey::from_bytes(&[7; 32]);
+        let other = SigningKey::from_bytes(&[8; 32]);
+        let trusted = |name: &str, key: &SigningKey| TrustedKey {
+            name: name.to_string(),
+            key: STANDARD.encode(key.verifying_key().to_bytes()),
+        };
+
+        assert_eq!(verify(&[], &file, &wasm).unwrap(), Verification::NotRequired);
+        let keys = [trusted("other", &other), trusted("release", &signer)];
+        let e = verify(&keys, &file, &wasm).unwrap_err().to_string();
+        assert!(e.starts_with("refused unsigned module"), "{}", e);
+
+        fs::write(signature_path(&file), STANDARD.encode(signer.sign(&wasm).to_bytes())).unwrap();
+        assert_eq!(verify(&keys, &file, &wasm).unwrap(), Verification::Verified { key: "release".to_string() });
+        let e = verify(&keys[..1], &file, &wasm).unwrap_err().to_string();
+        assert!(e.starts_with("refused badly signed module"), "{}", e);
+        assert!(verify(&keys, &file, b"\0asm\x01\0\0\x01").is_err());
+    }
+}
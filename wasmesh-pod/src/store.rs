@@ -0,0 +1,337 @@
+//! Content-addressed module store.
+//!
+//! Wherever a wasm path is expected, a module may be referenced by the SHA-256
+//! of its content instead, `sha256:<hex>`. Such modules are kept in
+//! `<dir>/sha256/<hex>.wasm`; a module missing there is fetched from the
+//! sources in order, peer pods and the control plane, and verified before it
+//! is written, along with its signature file when the source has one. A
+//! stored module never changes, so deploys and rollbacks of a digest always
+//! run the same code. The store and its files are only open to the user of
+//! the pod, and a stored module is checked against its digest again whenever
+//! it is loaded.
+
+use std::env;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use anyhow::Context;
+use sha2::{Digest, Sha256};
+use wasmesh_proto::{FetchModule, PlaneMessage, PlaneMessage_oneof_kind, PodMessage};
+use wasmesh_proto::rpc::{read_frame, write_frame, MAX_FRAME_LEN};
+
+use crate::{rpc, signing, tls};
+
+static STORE: OnceLock<ModuleStore> = OnceLock::new();
+
+/// Prefix of a module reference by content.
+pub(crate) const DIGEST_PREFIX: &str = "sha256:";
+
+/// Where modules missing from the store are fetched from.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Source {
+    /// admin API of another pod, e.g. `http://10.0.0.2:9190`
+    Peer(String),
+    /// RPC address of the control plane
+    Plane(String),
+}
+
+struct ModuleStore {
+    dir: PathBuf,
+    sources: Vec<Source>,
+    /// bearer token sent to peers
+    token: Option<String>,
+}
+
+/// Opens the store, `dir` defaults to `wasmesh/modules` in the user's cache directory.
+pub(crate) fn init(dir: Option<PathBuf>, sources: Vec<Source>, token: Option<String>) -> anyhow::Result<()> {
+    let dir = dir.unwrap_or_else(default_dir);
+    create_dir(&dir)?;
+    STORE.set(ModuleStore { dir, sources, token })
+         .map_err(|_| anyhow::anyhow!("module store is already initialized"))
+}
+
+#[cfg(test)]
+thread_local! {
+    // store of the running test, see `with_store`
+    static TEST_STORE: std::cell::Cell<Option<&'static ModuleStore>> = const { std::cell::Cell::new(None) };
+}
+
+/// Runs `f` with a store in `dir` on this thread, so tests do not share one.
+#[cfg(test)]
+pub(crate) fn with_store<R>(dir: &Path, f: impl FnOnce() -> R) -> R {
+    let store = Box::leak(Box::new(ModuleStore { dir: dir.to_path_buf(), sources: Vec::new(), token: None }));
+    TEST_STORE.with(|s| s.set(Some(store)));
+    let r = f();
+    TEST_STORE.with(|s| s.set(None));
+    r
+}
+
+fn store() -> &'static ModuleStore {
+    #[cfg(test)]
+    if let Some(store) = TEST_STORE.with(|s| s.get()) {
+        return store;
+    }
+    STORE.get_or_init(|| ModuleStore { dir: default_dir(), sources: Vec::new(), token: None })
+}
+
+fn default_dir() -> PathBuf {
+    let cache = env::var_os("XDG_CACHE_HOME").map(PathBuf::from)
+                                              .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")));
+    match cache {
+        Some(cache) => cache.join("wasmesh").join("modules"),
+        None => env::temp_dir().join("wasmesh-modules"),
+    }
+}
+
+/// Creates the store directory, open to the user of the pod only: anyone
+/// else able to write there could swap a module for another.
+fn create_dir(dir: &Path) -> anyhow::Result<()> {
+    let context = || format!("create module store {}", dir.display());
+    let mut builder = fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    builder.create(dir.join("sha256")).with_context(context)?;
+    #[cfg(unix)]
+    for dir in [dir.to_path_buf(), dir.join("sha256")] {
+        use std::os::unix::fs::PermissionsExt;
+        let mut permissions = fs::metadata(&dir).with_context(context)?.permissions();
+        if permissions.mode() & 0o077 != 0 {
+            // only the owner may change them
+            permissions.set_mode(0o700);
+            fs::set_permissions(&dir, permissions)
+                .with_context(|| format!("module store {} is open to other users", dir.display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes a file of the store through a temporary one, so a reader never sees
+/// it half written, readable by the user of the pod only.
+fn write_file(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let tmp = path.with_extension(format!("tmp-{}", std::process::id()));
+    let _ = fs::remove_file(&tmp);
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(&tmp)
+           .and_then(|mut file| file.write_all(bytes))
+           .and_then(|_| fs::rename(&tmp, path))
+}
+
+/// Hex SHA-256 of a reference by content, `None` for a path.
+pub(crate) fn digest_of(wasm: &str) -> Option<&str> {
+    wasm.strip_prefix(DIGEST_PREFIX)
+}
+
+/// Local file of a module reference, without fetching it.
+pub(crate) fn path(wasm: &str) -> String {
+    match digest_of(wasm) {
+        Some(hex) => store().path(hex).to_string_lossy().into_owned(),
+        None => wasm.to_string(),
+    }
+}
+
+/// Local file and content of a module reference, fetching the module into
+/// the store when it is referenced by content and missing, or no longer
+/// matches its digest.
+pub(crate) fn resolve(wasm: &str) -> anyhow::Result<(String, Vec<u8>)> {
+    let hex = match digest_of(wasm) {
+        Some(hex) => hex,
+        None => {
+            let bytes = fs::read(wasm).with_context(|| format!("load {}", wasm))?;
+            return Ok((wasm.to_string(), bytes));
+        }
+    };
+    check_hex(hex)?;
+    let store = store();
+    let path = store.path(hex);
+    let bytes = match fs::read(&path) {
+        Ok(bytes) if sha256_hex(&bytes) == hex => bytes,
+        stored => {
+            if stored.is_ok() {
+                log_warn!("module store: {} does not match its digest, fetching it again", path.display());
+            }
+            let (wasm, signature) = store.fetch(hex)?;
+            if let Some(signature) = signature {
+                put_signature(hex, &signature)?;
+            }
+            store.write(hex, &wasm)?;
+            wasm
+        }
+    };
+    Ok((path.to_string_lossy().into_owned(), bytes))
+}
+
+/// Adds a module to the store, returns its reference.
+///
+/// A stored file that differs from the module is replaced.
+pub(crate) fn put(wasm: &[u8]) -> anyhow::Result<String> {
+    let hex = sha256_hex(wasm);
+    let store = store();
+    if fs::read(store.path(&hex)).map_or(true, |stored| stored != wasm) {
+        store.write(&hex, wasm)?;
+    }
+    Ok(format!("{}{}", DIGEST_PREFIX, hex))
+}
+
+/// Whether the store has a module.
+pub(crate) fn contains(hex: &str) -> bool {
+    check_hex(hex).is_ok() && store().path(hex).exists()
+}
+
+/// Stores the detached signature of a module, see [`signing`].
+pub(crate) fn put_signature(hex: &str, signature: &[u8]) -> anyhow::Result<()> {
+    check_hex(hex)?;
+    signing::parse_signature(signature).context("invalid signature")?;
+    let path = signing::signature_path(&store().path(hex).to_string_lossy());
+    create_dir(&store().dir)?;
+    write_file(Path::new(&path), signature).with_context(|| format!("store signature {}", path))
+}
+
+/// Signature file of a stored module.
+pub(crate) fn read_signature(hex: &str) -> Option<Vec<u8>> {
+    check_hex(hex).ok()?;
+    fs::read(signing::signature_path(&store().path(hex).to_string_lossy())).ok()
+}
+
+/// Content of a stored module.
+pub(crate) fn read(hex: &str) -> Option<Vec<u8>> {
+    check_hex(hex).ok()?;
+    fs::read(store().path(hex)).ok()
+}
+
+pub(crate) fn sha256_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+fn check_hex(hex: &str) -> anyhow::Result<()> {
+    if hex.len() != 64 || !hex.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f')) {
+        anyhow::bail!("invalid module digest {}{}, expected 64 lowercase hex digits", DIGEST_PREFIX, hex);
+    }
+    Ok(())
+}
+
+impl ModuleStore {
+    fn path(&self, hex: &str) -> PathBuf {
+        self.dir.join("sha256").join(format!("{}.wasm", hex))
+    }
+
+    /// Writes a verified module.
+    fn write(&self, hex: &str, wasm: &[u8]) -> anyhow::Result<()> {
+        let path = self.path(hex);
+        create_dir(&self.dir)?;
+        write_file(&path, wasm).with_context(|| format!("store module {}", path.display()))
+    }
+
+    /// Fetches a module and its signature from the first source that has it.
+    fn fetch(&self, hex: &str) -> anyhow::Result<(Vec<u8>, Option<Vec<u8>>)> {
+        let mut errors = Vec::new();
+        for source in &self.sources {
+            let r = match source {
+                Source::Peer(base) => self.fetch_from_peer(base, hex),
+                Source::Plane(addr) => fetch_from_plane(addr, hex),
+            };
+            match r {
+                Ok((wasm, signature)) if sha256_hex(&wasm) == hex => {
+                    log_info!("module store: fetched {}{} from {:?}", DIGEST_PREFIX, hex, source);
+                    return Ok((wasm, signature));
+                }
+                Ok(_) => errors.push(format!("{:?}: content does not match the digest", source)),
+                Err(e) => errors.push(format!("{:?}: {:#}", source, e)),
+            }
+        }
+        if errors.is_empty() {
+            anyhow::bail!("module {}{} is not in the store and no source is configured", DIGEST_PREFIX, hex);
+        }
+        anyhow::bail!("module {}{} is not in the store: {}", DIGEST_PREFIX, hex, errors.join("; "))
+    }
+
+    fn fetch_from_peer(&self, base: &str, hex: &str) -> anyhow::Result<(Vec<u8>, Option<Vec<u8>>)> {
+        // peers are pods, reached with the mesh identity when the pod has one
+        let config = tls::mesh_client().map_or_else(tls::public_client_config, |client| client.config());
+        let agent = ureq::builder().timeout(Duration::from_secs(60)).tls_config(config).build();
+        let url = format!("{}/modules/{}{}", base.trim_end_matches('/'), DIGEST_PREFIX, hex);
+        let wasm = self.get(&agent, &url)?;
+        let signature = match self.get(&agent, &format!("{}/signature", url)) {
+            Ok(signature) => Some(signature),
+            Err(e) if matches!(e.downcast_ref(), Some(ureq::Error::Status(404, _))) => None,
+            Err(e) => return Err(e.context("fetch signature")),
+        };
+        Ok((wasm, signature))
+    }
+
+    fn get(&self, agent: &ureq::Agent, url: &str) -> anyhow::Result<Vec<u8>> {
+        let mut req = agent.get(url);
+        if let Some(token) = &self.token {
+            req = req.set("authorization", &format!("Bearer {}", token));
+        }
+        let mut bytes = Vec::new();
+        req.call()?.into_reader().take(MAX_FRAME_LEN as u64).read_to_end(&mut bytes)?;
+        Ok(bytes)
+    }
+}
+
+fn fetch_from_plane(addr: &str, hex: &str) -> anyhow::Result<(Vec<u8>, Option<Vec<u8>>)> {
+    let (_, mut conn) = rpc::connect(addr, Duration::from_secs(60))?;
+    let mut fetch = FetchModule::new();
+    fetch.set_digest(format!("{}{}", DIGEST_PREFIX, hex));
+    let mut msg = PodMessage::new();
+    msg.set_fetch_module(fetch);
+    write_frame(&mut conn, &msg)?;
+    match read_frame::<PlaneMessage, _>(&mut conn)?.kind {
+        Some(PlaneMessage_oneof_kind::module(m)) if m.error.is_empty() => {
+            let signature = Some(m.signature.to_vec()).filter(|s| !s.is_empty());
+            Ok((m.wasm.to_vec(), signature))
+        }
+        Some(PlaneMessage_oneof_kind::module(m)) => anyhow::bail!("{}", m.error),
+        _ => anyhow::bail!("unexpected answer"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fetched_modules_are_verified() {
+        let dir = tempfile::tempdir().unwrap();
+        let other = tempfile::tempdir().unwrap();
+        let wasm = b"\0asm\x01\0\0\0".to_vec();
+        let hex = sha256_hex(&wasm);
+        // a plane that serves the module, and one that lies about it
+        let serve = |body: Vec<u8>| {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            let addr = listener.local_addr().unwrap().to_string();
+            std::thread::spawn(move || {
+                for mut conn in listener.incoming().flatten() {
+                    let fetch = read_frame::<PodMessage, _>(&mut conn).unwrap();
+                    let mut module = wasmesh_proto::Module::new();
+                    module.set_digest(fetch.get_fetch_module().digest.clone());
+                    module.set_wasm(body.clone().into());
+                    let mut msg = PlaneMessage::new();
+                    msg.set_module(module);
+                    write_frame(&mut conn, &msg).unwrap();
+                }
+            });
+            addr
+        };
+        let liar = Source::Plane(serve(b"\0asm\x01\0\0\x01".to_vec()));
+        let plane = Source::Plane(serve(wasm.clone()));
+
+        let store = ModuleStore { dir: other.path().to_path_buf(), sources: vec![liar.clone()], token: None };
+        let e = store.fetch(&hex).unwrap_err().to_string();
+        assert!(e.contains("content does not match"), "{}", e);
+
+        let store = ModuleStore { dir: dir.path().to_path_buf(), sources: vec![liar, plane], token: None };
+        let (fetched, signature) = store.fetch(&hex).unwrap();
+        assert_eq!(signature, None);
+        store.write(&hex, &fetched).unwrap();
+        assert_eq!(fs::read(store.path(&hex)).unwrap(), wasm);
+        assert!(check_hex("ABC").is_err());
+    }
+}
@@ -0,0 +1,128 @@
+//! In-process harness for testing guest modules with `cargo test`.
+//!
+//! ```ignore
+//! use wasmesh_pod::testing::TestModule;
+//!
+//! #[test]
+//! fn create_order() {
+//!     let mut module = TestModule::load("target/wasm32-wasi/release/orders.wasm").unwrap();
+//!     module.stub_url("http://inventory/", TestModule::json_response(200, r#"{"in_stock":true}"#));
+//!     let resp = module.call(TestModule::request("POST", "/orders", r#"{"item":"tea"}"#)).unwrap();
+//!     assert_eq!(resp.status, 201);
+//!     assert_eq!(module.outbound()[0].url, "http://inventory/items/tea");
+//! }
+//! ```
+
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+
+use wasmy_vm::*;
+
+use wasmesh_proto::*;
+
+use crate::runtime::{OutboundStub, with_outbound_stub};
+
+type Stub = Box<dyn Fn(&HttpRequest) -> Option<std::result::Result<HttpResponse, MeshError>>>;
+
+/// A guest module loaded in this process, with its outbound requests stubbed.
+///
+/// Outbound requests no stub answers fail with `ErrorCode::UNAVAILABLE`, so
+/// tests never reach the network.
+pub struct TestModule {
+    wasm_info: WasmInfo,
+    host: Rc<TestHost>,
+}
+
+#[derive(Default)]
+struct TestHost {
+    stubs: RefCell<Vec<Stub>>,
+    outbound: RefCell<Vec<HttpRequest>>,
+}
+
+impl TestHost {
+    fn respond(&self, req: &HttpRequest) -> std::result::Result<HttpResponse, MeshError> {
+        self.outbound.borrow_mut().push(req.clone());
+        self.stubs.borrow().iter()
+            .find_map(|stub| stub(req))
+            .unwrap_or_else(|| Err(MeshError::with_code(ErrorCode::UNAVAILABLE, format!("no stub for {} {}", req.method_str(), req.url))))
+    }
+}
+
+impl TestModule {
+    pub fn load<P: AsRef<Path>>(wasm_path: P) -> anyhow::Result<Self> {
+        let wasm_info = WasmInfo { wasm_path: wasm_path.as_ref().to_string_lossy().to_string() };
+        load_wasm(wasm_info.clone()).map_err(|e| anyhow::anyhow!("failed to load {}: {}", wasm_info.wasm_path, e))?;
+        Ok(TestModule { wasm_info, host: Rc::default() })
+    }
+
+    /// Invokes the module's `W_HTTP` handler.
+    pub fn call(&self, req: HttpRequest) -> std::result::Result<HttpResponse, MeshError> {
+        let host = self.host.clone();
+        let stub: OutboundStub = Rc::new(move |req| host.respond(req));
+        with_outbound_stub(stub, || call_wasm(self.wasm_info.clone(), WasmMethod::W_HTTP.into(), req))
+            .map_err(|e| MeshError::from_code_msg(&e))
+    }
+
+    /// Adds a stub for outbound requests, the first one returning `Some` answers.
+    pub fn stub<F>(&mut self, stub: F)
+        where F: Fn(&HttpRequest) -> Option<std::result::Result<HttpResponse, MeshError>> + 'static {
+        self.host.stubs.borrow_mut().push(Box::new(stub));
+    }
+
+    /// Answers outbound requests whose url starts with `prefix`.
+    pub fn stub_url(&mut self, prefix: &str, resp: HttpResponse) {
+        let prefix = prefix.to_string();
+        self.stub(move |req| req.url.starts_with(&prefix).then(|| Ok(resp.clone())));
+    }
+
+    /// Outbound requests made so far, in order.
+    pub fn outbound(&self) -> Vec<HttpRequest> {
+        self.host.outbound.borrow().clone()
+    }
+
+    /// Builds a request as the pod would pass it to the guest.
+    pub fn request(method: &str, url: &str, body: &str) -> HttpRequest {
+        let mut req = HttpRequest::new();
+        req.set_method_str(method).expect("invalid method");
+        req.set_url(url.to_string());
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        req.set_path(path.to_string());
+        req.set_query(query.to_string());
+        req.set_scheme("http".to_string());
+        req.set_version("HTTP/1.1".to_string());
+        req.set_request_id("test".to_string());
+        req.set_body(Bytes::from(body.to_string()));
+        req
+    }
+
+    pub fn json_response(status: i32, body: &str) -> HttpResponse {
+        let mut resp = HttpResponse::new();
+        resp.set_status(status);
+        resp.headers.insert("content-type".to_string(), "application/json".to_string());
+        resp.set_body(Bytes::from(body.to_string()));
+        resp
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stubs_answer_in_order() {
+        let host = TestHost::default();
+        host.stubs.borrow_mut().push(Box::new(|req| req.url.starts_with("http://inventory/").then(|| Ok(TestModule::json_response(200, "{}")))));
+        host.stubs.borrow_mut().push(Box::new(|_| Some(Err(MeshError::with_code(ErrorCode::TIMEOUT, "slow")))));
+
+        let resp = host.respond(&TestModule::request("GET", "http://inventory/items/tea", "")).unwrap();
+        assert_eq!(resp.status, 200);
+        let e = host.respond(&TestModule::request("GET", "http://payments/", "")).unwrap_err();
+        assert_eq!(e.get_code(), ErrorCode::TIMEOUT);
+        assert_eq!(host.outbound.borrow().len(), 2);
+
+        host.stubs.borrow_mut().clear();
+        let e = host.respond(&TestModule::request("GET", "http://payments/", "")).unwrap_err();
+        assert_eq!(e.get_code(), ErrorCode::UNAVAILABLE);
+    }
+}
@@ -0,0 +1,260 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+
+use anyhow::{anyhow, Context};
+use rustls::{ClientConfig, DigitallySignedStruct, RootCertStore, ServerConfig, SignatureScheme};
+use rustls::client::WebPkiServerVerifier;
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::ring;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+use rustls::server::WebPkiClientVerifier;
+use x509_parser::prelude::*;
+
+use crate::ns::Identity;
+
+/// File name of the trust root inside a CA directory.
+pub(crate) const CA_FILE: &str = "ca.pem";
+
+static MESH_CLIENT: OnceLock<MeshClient> = OnceLock::new();
+static PUBLIC_CLIENT: OnceLock<Arc<ClientConfig>> = OnceLock::new();
+
+/// Mutual TLS settings of a pod, loaded from a local CA directory.
+///
+/// The directory holds the trust root `ca.pem` and, for every identity,
+/// a certificate chain `<namespace>.<module>.pem` with its key `<namespace>.<module>.key`.
+#[derive(Clone)]
+pub(crate) struct MtlsConfig {
+    pub(crate) identity: Identity,
+    pub(crate) server: Arc<ServerConfig>,
+    /// Trusts the CA only and presents the workload certificate.
+    pub(crate) client: MeshClient,
+}
+
+/// Client side of the mesh, for calls to other pods and to the control plane.
+#[derive(Clone)]
+pub(crate) struct MeshClient {
+    config: Arc<ClientConfig>,
+    verifier: Arc<WebPkiServerVerifier>,
+}
+
+impl MtlsConfig {
+    pub(crate) fn load(ca_dir: &Path, identity: Identity) -> anyhow::Result<Self> {
+        let provider = Arc::new(ring::default_provider());
+        let mut roots = RootCertStore::empty();
+        for cert in load_certs(&ca_dir.join(CA_FILE))? {
+            roots.add(cert)?;
+        }
+        let roots = Arc::new(roots);
+        let stem = identity.file_stem();
+        let certs = load_certs(&ca_dir.join(format!("{}.pem", stem)))?;
+        let key = load_key(&ca_dir.join(format!("{}.key", stem)))?;
+
+        let verifier = WebPkiClientVerifier::builder_with_provider(roots.clone(), provider.clone()).build()?;
+        let mut server = ServerConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()?
+            .with_client_cert_verifier(verifier)
+            .with_single_cert(certs.clone(), key.clone_key())?;
+        server.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+        let verifier = WebPkiServerVerifier::builder_with_provider(roots.clone(), provider.clone()).build()?;
+        let client = ClientConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()?
+            .with_root_certificates(roots)
+            .with_client_auth_cert(certs, key)?;
+
+        Ok(MtlsConfig {
+            identity,
+            server: Arc::new(server),
+            client: MeshClient { config: Arc::new(client), verifier },
+        })
+    }
+}
+
+impl MeshClient {
+    pub(crate) fn config(&self) -> Arc<ClientConfig> {
+        self.config.clone()
+    }
+
+    /// Returns a config that also requires the server to prove `peer`.
+    pub(crate) fn pinned(&self, peer: Identity) -> Arc<ClientConfig> {
+        let mut config = (*self.config).clone();
+        config.dangerous().set_certificate_verifier(Arc::new(PinnedVerifier { inner: self.verifier.clone(), peer }));
+        Arc::new(config)
+    }
+}
+
+/// Installs the mesh client used by outbound calls to pods and the control plane.
+///
+/// Must be called before the first outbound request, later calls are ignored.
+pub(crate) fn install_mesh_client(client: MeshClient) {
+    let _ = MESH_CLIENT.set(client);
+}
+
+pub(crate) fn mesh_client() -> Option<&'static MeshClient> {
+    MESH_CLIENT.get()
+}
+
+/// Returns the config of outbound calls to public endpoints: the web roots
+/// and no client certificate.
+pub(crate) fn public_client_config() -> Arc<ClientConfig> {
+    PUBLIC_CLIENT.get_or_init(|| {
+        let roots = RootCertStore { roots: webpki_roots::TLS_SERVER_ROOTS.to_vec() };
+        let config = ClientConfig::builder_with_provider(Arc::new(ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .expect("ring supports the default protocol versions")
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        Arc::new(config)
+    }).clone()
+}
+
+/// Verifies the server certificate against the CA, then its workload identity.
+#[derive(Debug)]
+struct PinnedVerifier {
+    inner: Arc<WebPkiServerVerifier>,
+    peer: Identity,
+}
+
+impl ServerCertVerifier for PinnedVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let verified = self.inner.verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now)?;
+        match peer_identity(Some(std::slice::from_ref(end_entity))) {
+            Some(identity) if identity == self.peer => Ok(verified),
+            Some(identity) => Err(rustls::Error::General(format!("server is {}, expected {}", identity, self.peer))),
+            None => Err(rustls::Error::General(format!("server has no workload identity, expected {}", self.peer))),
+        }
+    }
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+/// Returns the verified workload identity of the peer, if it presented one.
+pub(crate) fn peer_identity(certs: Option<&[CertificateDer<'_>]>) -> Option<Identity> {
+    let (_, cert) = X509Certificate::from_der(certs?.first()?.as_ref()).ok()?;
+    let san = cert.subject_alternative_name().ok()??;
+    san.value.general_names.iter().find_map(|name| match name {
+        GeneralName::URI(uri) => Identity::parse(uri),
+        _ => None,
+    })
+}
+
+fn load_certs(path: &Path) -> anyhow::Result<Vec<CertificateDer<'static>>> {
+    let mut reader = BufReader::new(File::open(path).with_context(|| format!("open {}", path.display()))?);
+    let certs = rustls_pemfile::certs(&mut reader).collect::<Result<Vec<_>, _>>()?;
+    if certs.is_empty() {
+        return Err(anyhow!("no certificate found in {}", path.display()));
+    }
+    Ok(certs)
+}
+
+fn load_key(path: &Path) -> anyhow::Result<PrivateKeyDer<'static>> {
+    let mut reader = BufReader::new(File::open(path).with_context(|| format!("open {}", path.display()))?);
+    rustls_pemfile::private_key(&mut reader)?.ok_or_else(|| anyhow!("no private key found in {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use rcgen::{BasicConstraints, CertificateParams, IsCa, KeyPair, SanType};
+    use rustls::pki_types::ServerName;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio_rustls::{TlsAcceptor, TlsConnector};
+
+    use super::*;
+
+    fn issue_ca(dir: &Path, identities: &[&Identity]) {
+        let ca_key = KeyPair::generate().unwrap();
+        let mut params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let ca = params.self_signed(&ca_key).unwrap();
+        fs::write(dir.join(CA_FILE), ca.pem()).unwrap();
+        for identity in identities {
+            let key = KeyPair::generate().unwrap();
+            let mut params = CertificateParams::new(vec!["localhost".to_string()]).unwrap();
+            params.subject_alt_names.push(SanType::URI(identity.uri().try_into().unwrap()));
+            let cert = params.signed_by(&key, &ca, &ca_key).unwrap();
+            fs::write(dir.join(format!("{}.pem", identity.file_stem())), cert.pem()).unwrap();
+            fs::write(dir.join(format!("{}.key", identity.file_stem())), key.serialize_pem()).unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn mutual_handshake_exposes_peer_identity() {
+        let dir = tempfile::tempdir().unwrap();
+        let orders = Identity::new("shop", "orders");
+        let billing = Identity::new("shop", "billing");
+        issue_ca(dir.path(), &[&orders, &billing]);
+        let server = MtlsConfig::load(dir.path(), orders).unwrap();
+        let client = MtlsConfig::load(dir.path(), billing.clone()).unwrap();
+
+        let (client_io, server_io) = tokio::io::duplex(4096);
+        let accept = tokio::spawn(async move {
+            let mut stream = TlsAcceptor::from(server.server).accept(server_io).await.unwrap();
+            let peer = peer_identity(stream.get_ref().1.peer_certificates());
+            stream.write_all(b"ok").await.unwrap();
+            stream.shutdown().await.unwrap();
+            peer
+        });
+        let name = ServerName::try_from("localhost").unwrap();
+        let mut stream = TlsConnector::from(client.client.config()).connect(name, client_io).await.unwrap();
+        let mut buf = Vec::new();
+        stream.read_to_end(&mut buf).await.unwrap();
+        assert_eq!(buf, b"ok");
+        assert_eq!(accept.await.unwrap(), Some(billing));
+    }
+
+    #[tokio::test]
+    async fn mesh_calls_are_pinned_to_the_peer_identity() {
+        let dir = tempfile::tempdir().unwrap();
+        let orders = Identity::new("shop", "orders");
+        let billing = Identity::new("shop", "billing");
+        issue_ca(dir.path(), &[&orders, &billing]);
+        let server = MtlsConfig::load(dir.path(), orders.clone()).unwrap();
+        let client = MtlsConfig::load(dir.path(), billing.clone()).unwrap();
+
+        for (peer, ok) in [(orders, true), (billing, false)] {
+            let (client_io, server_io) = tokio::io::duplex(4096);
+            let acceptor = TlsAcceptor::from(server.server.clone());
+            tokio::spawn(async move { acceptor.accept(server_io).await });
+            let name = ServerName::try_from("localhost").unwrap();
+            let r = TlsConnector::from(client.client.pinned(peer)).connect(name, client_io).await;
+            assert_eq!(r.is_ok(), ok);
+        }
+    }
+
+    #[test]
+    fn identity_uri_round_trip() {
+        let identity = Identity::new("shop", "orders");
+        assert_eq!(identity.uri(), "spiffe://wasmesh/ns/shop/module/orders");
+        assert_eq!(Identity::parse(&identity.uri()), Some(identity));
+        assert_eq!(Identity::parse("spiffe://other/ns/shop/module/orders"), None);
+        assert_eq!(Identity::parse("spiffe://wasmeshevil/ns/shop/module/orders"), None);
+    }
+}
@@ -0,0 +1,29 @@
+use std::path::PathBuf;
+
+use wasmesh_pod::testing::TestModule;
+use wasmesh_proto::*;
+
+fn echo_wasm() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../examples/echo/target/wasm32-wasi/release/echo.wasm")
+}
+
+// needs the guest built for wasm32-wasi with `cargo build-echo`
+#[test]
+#[ignore]
+fn calls_a_real_handler() {
+    let mut module = TestModule::load(echo_wasm()).unwrap();
+    module.stub_url("http://upstream/", TestModule::json_response(204, ""));
+
+    let resp = module.call(TestModule::request("POST", "/orders?id=1", "tea")).unwrap();
+    assert_eq!(resp.status, 200);
+    assert_eq!(resp.headers["x-upstream-status"], "204");
+    assert_eq!(&resp.body[..], b"tea");
+    let outbound = module.outbound();
+    assert_eq!(outbound.len(), 1);
+    assert_eq!(outbound[0].url, "http://upstream/orders");
+
+    // without a stub the outbound request fails, and so does the handler
+    let module = TestModule::load(echo_wasm()).unwrap();
+    let e = module.call(TestModule::request("GET", "/", "")).unwrap_err();
+    assert_eq!(e.get_code(), ErrorCode::UNAVAILABLE);
+}
@@ -0,0 +1,101 @@
+use std::fmt;
+
+use protobuf::{Message, ProtobufEnum};
+use wasmy_abi::CodeMsg;
+
+use crate::proto::{ErrorCode, MeshError};
+
+/// Prefix of a `CodeMsg` message carrying an encoded `MeshError`.
+const MESH_ERROR_PREFIX: &str = "wasmesh-error:";
+
+/// Detail overriding the 400 of an `INVALID_ARGUMENT`, see [`MeshError::http_status`].
+pub const HTTP_STATUS_DETAIL: &str = "http_status";
+
+impl ErrorCode {
+    /// HTTP status code a guest error is answered with.
+    pub fn http_status(&self) -> u16 {
+        match self {
+            ErrorCode::INVALID_ARGUMENT => 400,
+            ErrorCode::UNAUTHENTICATED => 401,
+            ErrorCode::PERMISSION_DENIED => 403,
+            ErrorCode::NOT_FOUND => 404,
+            ErrorCode::CONFLICT => 409,
+            ErrorCode::RESOURCE_EXHAUSTED => 429,
+            ErrorCode::UNKNOWN | ErrorCode::INTERNAL => 500,
+            ErrorCode::UNIMPLEMENTED => 501,
+            ErrorCode::DNS_FAILURE
+            | ErrorCode::CONNECTION_REFUSED
+            | ErrorCode::TLS_FAILURE
+            | ErrorCode::UPSTREAM_FAILURE => 502,
+            ErrorCode::UNAVAILABLE => 503,
+            ErrorCode::DEADLINE_EXCEEDED | ErrorCode::TIMEOUT => 504,
+        }
+    }
+    pub fn as_str(&self) -> &'static str {
+        ProtobufEnum::descriptor(self).name()
+    }
+}
+
+impl MeshError {
+    pub fn with_code<S: ToString>(code: ErrorCode, message: S) -> Self {
+        let mut e = MeshError::new();
+        e.set_code(code);
+        e.set_message(message.to_string());
+        e
+    }
+    pub fn with_retryable(mut self, retryable: bool) -> Self {
+        self.set_retryable(retryable);
+        self
+    }
+    pub fn with_detail<K: ToString, V: ToString>(mut self, key: K, value: V) -> Self {
+        self.details.insert(key.to_string(), value.to_string());
+        self
+    }
+    /// HTTP status code the error is answered with: the 4xx `http_status`
+    /// detail of an `INVALID_ARGUMENT`, such as the 415 of a rejected
+    /// extraction, else the one of its code.
+    pub fn http_status(&self) -> u16 {
+        if self.code != ErrorCode::INVALID_ARGUMENT {
+            return self.code.http_status();
+        }
+        match self.details.get(HTTP_STATUS_DETAIL).and_then(|s| s.parse::<u16>().ok()) {
+            Some(status) if (400..500).contains(&status) => status,
+            _ => self.code.http_status(),
+        }
+    }
+    /// Recovers the error a guest or host handler returned.
+    ///
+    /// Plain `CodeMsg`s keep their message and get `ErrorCode::UNKNOWN`.
+    pub fn from_code_msg(cm: &CodeMsg) -> Self {
+        if let Some(hex) = cm.msg.strip_prefix(MESH_ERROR_PREFIX) {
+            if let Some(e) = decode_hex(hex).and_then(|b| MeshError::parse_from_bytes(&b).ok()) {
+                return e;
+            }
+        }
+        MeshError::with_code(ErrorCode::UNKNOWN, &cm.msg).with_detail("abi_code", cm.code)
+    }
+}
+
+impl From<MeshError> for CodeMsg {
+    fn from(e: MeshError) -> Self {
+        let bytes = e.write_to_bytes().unwrap_or_default();
+        CodeMsg::new(e.code.value(), format!("{}{}", MESH_ERROR_PREFIX, encode_hex(&bytes)))
+    }
+}
+
+impl fmt::Display for MeshError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.code.as_str(), self.message)
+    }
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    s.as_bytes()
+     .chunks(2)
+     .map(|c| std::str::from_utf8(c).ok().filter(|h| h.len() == 2).and_then(|h| u8::from_str_radix(h, 16).ok()))
+     .collect()
+}
@@ -1,20 +1,40 @@
 use std::ops::Deref;
+use std::sync::OnceLock;
 
-use bytes::Bytes;
 use protobuf::ProtobufEnum;
 
 use crate::proto::{HttpMethod, HttpRequest, HttpResponse};
 
 impl HttpMethod {
+    /// Name of a standard method, `EXTENSION` for extension methods,
+    /// whose name is only known to the request, see [`HttpRequest::method_str`].
     pub fn as_str(&self) -> &'static str {
         ProtobufEnum::descriptor(self).name()
     }
+    /// The standard method, `None` for `EXTENSION`, see [`HttpRequest::http_method`].
+    pub fn to_method(&self) -> Option<hyper::Method> {
+        match self {
+            HttpMethod::GET => Some(hyper::Method::GET),
+            HttpMethod::HEAD => Some(hyper::Method::HEAD),
+            HttpMethod::POST => Some(hyper::Method::POST),
+            HttpMethod::PUT => Some(hyper::Method::PUT),
+            HttpMethod::DELETE => Some(hyper::Method::DELETE),
+            HttpMethod::CONNECT => Some(hyper::Method::CONNECT),
+            HttpMethod::OPTIONS => Some(hyper::Method::OPTIONS),
+            HttpMethod::TRACE => Some(hyper::Method::TRACE),
+            HttpMethod::PATCH => Some(hyper::Method::PATCH),
+            HttpMethod::EXTENSION => None,
+        }
+    }
 }
 
+/// Derefs `EXTENSION` to a method named `EXTENSION`, the actual name is
+/// only known to the request, see [`HttpRequest::http_method`].
 impl Deref for HttpMethod {
     type Target = hyper::Method;
 
     fn deref(&self) -> &Self::Target {
+        static EXTENSION: OnceLock<hyper::Method> = OnceLock::new();
         match self {
             HttpMethod::GET => { &hyper::Method::GET }
             HttpMethod::HEAD => { &hyper::Method::HEAD }
@@ -25,6 +45,9 @@ impl Deref for HttpMethod {
             HttpMethod::OPTIONS => { &hyper::Method::OPTIONS }
             HttpMethod::TRACE => { &hyper::Method::TRACE }
             HttpMethod::PATCH => { &hyper::Method::PATCH }
+            HttpMethod::EXTENSION => {
+                EXTENSION.get_or_init(|| hyper::Method::from_bytes(b"EXTENSION").expect("EXTENSION is a valid token"))
+            }
         }
     }
 }
@@ -41,41 +64,58 @@ impl From<hyper::Method> for HttpMethod {
             hyper::Method::OPTIONS => { HttpMethod::OPTIONS }
             hyper::Method::TRACE => { HttpMethod::TRACE }
             hyper::Method::PATCH => { HttpMethod::PATCH }
-            _ => { HttpMethod::GET }
+            _ => { HttpMethod::EXTENSION }
         }
     }
 }
 
 impl HttpRequest {
-    pub async fn from(req: hyper::Request<hyper::Body>) -> Self {
-        let mut msg = HttpRequest::new();
-        msg.set_url(req.uri().to_string());
-        msg.set_method(req.method().clone().into());
-        let (parts, body) = req.into_parts();
-        let body = hyper::body::to_bytes(body).await.map_or_else(|_| Bytes::new(), |v| v);
-        for x in parts.headers.iter() {
-            msg.headers.insert(
-                x.0.to_string(),
-                x.1
-                 .to_str()
-                 .map_or_else(|_| String::new(), |s| s.to_string()),
-            );
+    /// Returns the request method, including extension methods such as `PROPFIND`;
+    /// fails for an empty or invalid extension method name.
+    pub fn http_method(&self) -> Result<hyper::Method, hyper::http::method::InvalidMethod> {
+        match self.method.to_method() {
+            Some(method) => Ok(method),
+            None => hyper::Method::from_bytes(self.extension_method.as_bytes()),
+        }
+    }
+    /// Returns the method name as sent on the wire.
+    pub fn method_str(&self) -> &str {
+        match self.method {
+            HttpMethod::EXTENSION => &self.extension_method,
+            method => method.as_str(),
         }
-        msg.set_body(body);
-        msg
+    }
+    /// Sets the request method, keeping the name of extension methods.
+    pub fn set_http_method(&mut self, method: hyper::Method) {
+        let m = HttpMethod::from(method.clone());
+        if m == HttpMethod::EXTENSION {
+            self.set_extension_method(method.as_str().to_string());
+        } else {
+            self.clear_extension_method();
+        }
+        self.set_method(m);
+    }
+    /// Sets the request method by name, e.g. `GET` or `PROPFIND`.
+    pub fn set_method_str(&mut self, method: &str) -> Result<(), hyper::http::method::InvalidMethod> {
+        self.set_http_method(hyper::Method::from_bytes(method.as_bytes())?);
+        Ok(())
     }
 }
 
-impl From<HttpResponse> for hyper::Response<hyper::Body> {
-    fn from(mut msg: HttpResponse) -> Self {
+/// Fails for a status or header a guest set that HTTP cannot carry.
+impl TryFrom<HttpResponse> for hyper::Response<hyper::Body> {
+    type Error = hyper::http::Error;
+
+    fn try_from(msg: HttpResponse) -> Result<Self, Self::Error> {
         let mut resp = hyper::Response::builder();
         for x in msg.headers.iter() {
             resp = resp.header(x.0, x.1);
         }
-        if msg.status <= 0 {
-            msg.set_status(200)
-        }
-        resp = resp.status(msg.status as u16);
-        resp.body(hyper::Body::from(msg.body)).unwrap()
+        let status = match msg.status {
+            s if s <= 0 => 200,
+            // out of range, rejected by the builder
+            s => u16::try_from(s).unwrap_or(0),
+        };
+        resp.status(status).body(hyper::Body::from(msg.body))
     }
 }
@@ -0,0 +1,56 @@
+use std::net::SocketAddr;
+
+use bytes::Bytes;
+
+use crate::proto::HttpRequest;
+
+/// Header carrying the request id.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Connection a request arrived on.
+#[derive(Debug, Clone, Default)]
+pub struct ConnInfo {
+    pub remote_addr: Option<SocketAddr>,
+    pub tls: bool,
+    /// verified workload identity of the caller
+    pub peer_identity: Option<String>,
+}
+
+impl HttpRequest {
+    pub async fn from(req: hyper::Request<hyper::Body>) -> Self {
+        Self::from_conn(req, &ConnInfo::default()).await
+    }
+
+    /// Converts a request, filling in the metadata of the connection it arrived on.
+    pub async fn from_conn(req: hyper::Request<hyper::Body>, conn: &ConnInfo) -> Self {
+        let mut msg = HttpRequest::new();
+        msg.set_url(req.uri().to_string());
+        msg.set_http_method(req.method().clone());
+        msg.set_path(req.uri().path().to_string());
+        msg.set_query(req.uri().query().unwrap_or_default().to_string());
+        msg.set_version(format!("{:?}", req.version()));
+        msg.set_scheme(req.uri().scheme_str().unwrap_or(if conn.tls { "https" } else { "http" }).to_string());
+        msg.set_tls(conn.tls);
+        if let Some(addr) = conn.remote_addr {
+            msg.set_remote_addr(addr.to_string());
+        }
+        if let Some(peer) = &conn.peer_identity {
+            msg.set_peer_identity(peer.clone());
+        }
+        if let Some(id) = req.headers().get(REQUEST_ID_HEADER).and_then(|v| v.to_str().ok()) {
+            msg.set_request_id(id.to_string());
+        }
+        let (parts, body) = req.into_parts();
+        let body = hyper::body::to_bytes(body).await.map_or_else(|_| Bytes::new(), |v| v);
+        for x in parts.headers.iter() {
+            msg.headers.insert(
+                x.0.to_string(),
+                x.1
+                 .to_str()
+                 .map_or_else(|_| String::new(), |s| s.to_string()),
+            );
+        }
+        msg.set_body(body);
+        msg
+    }
+}
@@ -4,12 +4,16 @@ extern crate protobuf;
 pub use bytes::Bytes;
 use protobuf::ProtobufEnum;
 
+pub use error::HTTP_STATUS_DETAIL;
+pub use http_request::{ConnInfo, REQUEST_ID_HEADER};
 pub use proto::*;
 pub use wasmy_abi::*;
 
 mod proto;
 mod http_method;
 mod http_request;
+mod error;
+pub mod rpc;
 
 impl From<WasmMethod> for wasmy_abi::Method {
     fn from(m: WasmMethod) -> Self {
@@ -25,9 +29,134 @@ impl From<VmMethod> for wasmy_abi::Method {
 
 #[cfg(test)]
 mod tests {
+    use crate::{Ack, CodeMsg, ConnInfo, ErrorCode, HttpMethod, HttpRequest, HttpResponse, MeshError, PodMessage, REQUEST_ID_HEADER, rpc};
+
     #[test]
     fn it_works() {
         let result = 2 + 2;
         assert_eq!(result, 4);
     }
+
+    #[test]
+    fn extension_method_round_trip() {
+        let mut req = HttpRequest::new();
+        let propfind = hyper::Method::from_bytes(b"PROPFIND").unwrap();
+        req.set_http_method(propfind.clone());
+        assert_eq!(req.get_method(), HttpMethod::EXTENSION);
+        assert_eq!(req.method_str(), "PROPFIND");
+        assert_eq!(req.http_method().unwrap(), propfind);
+
+        req.set_http_method(hyper::Method::DELETE);
+        assert_eq!(req.method_str(), "DELETE");
+        assert_eq!(req.http_method().unwrap(), hyper::Method::DELETE);
+        assert!(req.get_extension_method().is_empty());
+        assert_eq!(*req.get_method(), hyper::Method::DELETE);
+        assert_eq!(*HttpMethod::EXTENSION, hyper::Method::from_bytes(b"EXTENSION").unwrap());
+
+        // names a guest may send that HTTP cannot carry
+        for name in ["", "BAD METHOD"] {
+            req.set_method(HttpMethod::EXTENSION);
+            req.set_extension_method(name.to_string());
+            assert!(req.http_method().is_err(), "{:?}", name);
+        }
+        let mut resp = HttpResponse::new();
+        resp.set_status(1000);
+        assert!(hyper::Response::try_from(resp.clone()).is_err());
+        resp.set_status(0);
+        resp.headers.insert("bad header".to_string(), "x".to_string());
+        assert!(hyper::Response::try_from(resp).is_err());
+    }
+
+    #[tokio::test]
+    async fn guests_see_the_connection() {
+        let conn = ConnInfo {
+            remote_addr: Some("10.0.0.7:51234".parse().unwrap()),
+            tls: true,
+            peer_identity: Some("spiffe://wasmesh/ns/shop/module/billing".to_string()),
+        };
+        let req = hyper::Request::builder()
+            .uri("/orders?id=1")
+            .header(REQUEST_ID_HEADER, "r-1")
+            .body(hyper::Body::from("{}"))
+            .unwrap();
+        let msg = HttpRequest::from_conn(req, &conn).await;
+        assert_eq!(msg.get_remote_addr(), "10.0.0.7:51234");
+        assert!(msg.get_tls());
+        assert_eq!(msg.get_scheme(), "https");
+        assert_eq!(msg.get_peer_identity(), "spiffe://wasmesh/ns/shop/module/billing");
+        assert_eq!(msg.get_request_id(), "r-1");
+        assert_eq!((msg.get_path(), msg.get_query()), ("/orders", "id=1"));
+        assert_eq!(msg.get_body(), b"{}");
+
+        let plain = HttpRequest::from_conn(hyper::Request::new(hyper::Body::empty()), &ConnInfo::default()).await;
+        assert!(plain.get_remote_addr().is_empty() && plain.get_peer_identity().is_empty());
+        assert_eq!(plain.get_scheme(), "http");
+    }
+
+    #[test]
+    fn mesh_error_round_trip() {
+        let e = MeshError::with_code(ErrorCode::TIMEOUT, "upstream timed out")
+            .with_retryable(true)
+            .with_detail("url", "http://orders/");
+        let cm: CodeMsg = e.clone().into();
+        assert_eq!(cm.code, ErrorCode::TIMEOUT as i32);
+        assert_eq!(MeshError::from_code_msg(&cm), e);
+        assert_eq!(e.get_code().http_status(), 504);
+
+        let plain = MeshError::from_code_msg(&CodeMsg::new(-1, "boom"));
+        assert_eq!(plain.get_code(), ErrorCode::UNKNOWN);
+        assert_eq!(plain.get_message(), "boom");
+    }
+
+    #[test]
+    fn rpc_frames_round_trip() {
+        let mut ack = Ack::new();
+        ack.set_version("v1".to_string());
+        let mut msg = PodMessage::new();
+        msg.set_ack(ack);
+        let mut buf = Vec::new();
+        rpc::write_frame(&mut buf, &msg).unwrap();
+        rpc::write_frame(&mut buf, &PodMessage::new()).unwrap();
+        let mut r = buf.as_slice();
+        assert_eq!(rpc::read_frame::<PodMessage, _>(&mut r).unwrap(), msg);
+        assert_eq!(rpc::read_frame::<PodMessage, _>(&mut r).unwrap(), PodMessage::new());
+        assert_eq!(rpc::read_frame::<PodMessage, _>(&mut r).unwrap_err().kind(), std::io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn frames_are_kept_over_read_timeouts() {
+        /// Returns a few bytes per read, timing out in between.
+        struct Trickle(Vec<u8>, bool);
+        impl std::io::Read for Trickle {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                self.1 = !self.1;
+                if self.1 {
+                    return Err(std::io::ErrorKind::WouldBlock.into());
+                }
+                let n = self.0.len().min(3).min(buf.len());
+                buf[..n].copy_from_slice(&self.0[..n]);
+                self.0.drain(..n);
+                Ok(n)
+            }
+        }
+        let mut ack = Ack::new();
+        ack.set_version("v1".to_string());
+        let mut msg = PodMessage::new();
+        msg.set_ack(ack);
+        let mut buf = Vec::new();
+        rpc::write_frame(&mut buf, &msg).unwrap();
+        rpc::write_frame(&mut buf, &msg).unwrap();
+        let mut r = Trickle(buf, false);
+        let mut frames = rpc::FrameReader::new();
+        let mut read = Vec::new();
+        let e = loop {
+            match frames.read::<PodMessage, _>(&mut r) {
+                Ok(Some(msg)) => read.push(msg),
+                Ok(None) => continue,
+                Err(e) => break e,
+            }
+        };
+        assert_eq!(read, [msg.clone(), msg]);
+        assert_eq!(e.kind(), std::io::ErrorKind::UnexpectedEof);
+    }
 }
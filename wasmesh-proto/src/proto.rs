@@ -31,6 +31,16 @@ pub struct HttpRequest {
     pub method: HttpMethod,
     pub headers: ::std::collections::HashMap<::std::string::String, ::std::string::String>,
     pub body: ::bytes::Bytes,
+    pub peer_identity: ::std::string::String,
+    pub remote_addr: ::std::string::String,
+    pub version: ::std::string::String,
+    pub scheme: ::std::string::String,
+    pub tls: bool,
+    pub request_id: ::std::string::String,
+    pub path: ::std::string::String,
+    pub query: ::std::string::String,
+    pub extension_method: ::std::string::String,
+    pub timeout_ms: u32,
     // special fields
     #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
@@ -140,10 +150,4032 @@ impl HttpRequest {
     pub fn take_body(&mut self) -> ::bytes::Bytes {
         ::std::mem::replace(&mut self.body, ::bytes::Bytes::new())
     }
+
+    // string peer_identity = 5;
+
+
+    pub fn get_peer_identity(&self) -> &str {
+        &self.peer_identity
+    }
+    pub fn clear_peer_identity(&mut self) {
+        self.peer_identity.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_peer_identity(&mut self, v: ::std::string::String) {
+        self.peer_identity = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_peer_identity(&mut self) -> &mut ::std::string::String {
+        &mut self.peer_identity
+    }
+
+    // Take field
+    pub fn take_peer_identity(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.peer_identity, ::std::string::String::new())
+    }
+
+    // string remote_addr = 6;
+
+
+    pub fn get_remote_addr(&self) -> &str {
+        &self.remote_addr
+    }
+    pub fn clear_remote_addr(&mut self) {
+        self.remote_addr.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_remote_addr(&mut self, v: ::std::string::String) {
+        self.remote_addr = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_remote_addr(&mut self) -> &mut ::std::string::String {
+        &mut self.remote_addr
+    }
+
+    // Take field
+    pub fn take_remote_addr(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.remote_addr, ::std::string::String::new())
+    }
+
+    // string version = 7;
+
+
+    pub fn get_version(&self) -> &str {
+        &self.version
+    }
+    pub fn clear_version(&mut self) {
+        self.version.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_version(&mut self, v: ::std::string::String) {
+        self.version = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_version(&mut self) -> &mut ::std::string::String {
+        &mut self.version
+    }
+
+    // Take field
+    pub fn take_version(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.version, ::std::string::String::new())
+    }
+
+    // string scheme = 8;
+
+
+    pub fn get_scheme(&self) -> &str {
+        &self.scheme
+    }
+    pub fn clear_scheme(&mut self) {
+        self.scheme.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_scheme(&mut self, v: ::std::string::String) {
+        self.scheme = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_scheme(&mut self) -> &mut ::std::string::String {
+        &mut self.scheme
+    }
+
+    // Take field
+    pub fn take_scheme(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.scheme, ::std::string::String::new())
+    }
+
+    // bool tls = 9;
+
+
+    pub fn get_tls(&self) -> bool {
+        self.tls
+    }
+    pub fn clear_tls(&mut self) {
+        self.tls = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_tls(&mut self, v: bool) {
+        self.tls = v;
+    }
+
+    // string request_id = 10;
+
+
+    pub fn get_request_id(&self) -> &str {
+        &self.request_id
+    }
+    pub fn clear_request_id(&mut self) {
+        self.request_id.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_request_id(&mut self, v: ::std::string::String) {
+        self.request_id = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_request_id(&mut self) -> &mut ::std::string::String {
+        &mut self.request_id
+    }
+
+    // Take field
+    pub fn take_request_id(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.request_id, ::std::string::String::new())
+    }
+
+    // string path = 11;
+
+
+    pub fn get_path(&self) -> &str {
+        &self.path
+    }
+    pub fn clear_path(&mut self) {
+        self.path.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_path(&mut self, v: ::std::string::String) {
+        self.path = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_path(&mut self) -> &mut ::std::string::String {
+        &mut self.path
+    }
+
+    // Take field
+    pub fn take_path(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.path, ::std::string::String::new())
+    }
+
+    // string query = 12;
+
+
+    pub fn get_query(&self) -> &str {
+        &self.query
+    }
+    pub fn clear_query(&mut self) {
+        self.query.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_query(&mut self, v: ::std::string::String) {
+        self.query = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_query(&mut self) -> &mut ::std::string::String {
+        &mut self.query
+    }
+
+    // Take field
+    pub fn take_query(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.query, ::std::string::String::new())
+    }
+
+    // string extension_method = 13;
+
+
+    pub fn get_extension_method(&self) -> &str {
+        &self.extension_method
+    }
+    pub fn clear_extension_method(&mut self) {
+        self.extension_method.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_extension_method(&mut self, v: ::std::string::String) {
+        self.extension_method = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_extension_method(&mut self) -> &mut ::std::string::String {
+        &mut self.extension_method
+    }
+
+    // Take field
+    pub fn take_extension_method(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.extension_method, ::std::string::String::new())
+    }
+
+    // uint32 timeout_ms = 14;
+
+
+    pub fn get_timeout_ms(&self) -> u32 {
+        self.timeout_ms
+    }
+    pub fn clear_timeout_ms(&mut self) {
+        self.timeout_ms = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_timeout_ms(&mut self, v: u32) {
+        self.timeout_ms = v;
+    }
+}
+
+impl ::protobuf::Message for HttpRequest {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.url)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_proto3_enum_with_unknown_fields_into(wire_type, is, &mut self.method, 2, &mut self.unknown_fields)?
+                },
+                3 => {
+                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(wire_type, is, &mut self.headers)?;
+                },
+                4 => {
+                    ::protobuf::rt::read_singular_proto3_carllerche_bytes_into(wire_type, is, &mut self.body)?;
+                },
+                5 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.peer_identity)?;
+                },
+                6 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.remote_addr)?;
+                },
+                7 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.version)?;
+                },
+                8 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.scheme)?;
+                },
+                9 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.tls = tmp;
+                },
+                10 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.request_id)?;
+                },
+                11 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.path)?;
+                },
+                12 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.query)?;
+                },
+                13 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.extension_method)?;
+                },
+                14 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.timeout_ms = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.url.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.url);
+        }
+        if self.method != HttpMethod::GET {
+            my_size += ::protobuf::rt::enum_size(2, self.method);
+        }
+        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(3, &self.headers);
+        if !self.body.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(4, &self.body);
+        }
+        if !self.peer_identity.is_empty() {
+            my_size += ::protobuf::rt::string_size(5, &self.peer_identity);
+        }
+        if !self.remote_addr.is_empty() {
+            my_size += ::protobuf::rt::string_size(6, &self.remote_addr);
+        }
+        if !self.version.is_empty() {
+            my_size += ::protobuf::rt::string_size(7, &self.version);
+        }
+        if !self.scheme.is_empty() {
+            my_size += ::protobuf::rt::string_size(8, &self.scheme);
+        }
+        if self.tls != false {
+            my_size += 2;
+        }
+        if !self.request_id.is_empty() {
+            my_size += ::protobuf::rt::string_size(10, &self.request_id);
+        }
+        if !self.path.is_empty() {
+            my_size += ::protobuf::rt::string_size(11, &self.path);
+        }
+        if !self.query.is_empty() {
+            my_size += ::protobuf::rt::string_size(12, &self.query);
+        }
+        if !self.extension_method.is_empty() {
+            my_size += ::protobuf::rt::string_size(13, &self.extension_method);
+        }
+        if self.timeout_ms != 0 {
+            my_size += ::protobuf::rt::value_size(14, self.timeout_ms, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.url.is_empty() {
+            os.write_string(1, &self.url)?;
+        }
+        if self.method != HttpMethod::GET {
+            os.write_enum(2, ::protobuf::ProtobufEnum::value(&self.method))?;
+        }
+        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(3, &self.headers, os)?;
+        if !self.body.is_empty() {
+            os.write_bytes(4, &self.body)?;
+        }
+        if !self.peer_identity.is_empty() {
+            os.write_string(5, &self.peer_identity)?;
+        }
+        if !self.remote_addr.is_empty() {
+            os.write_string(6, &self.remote_addr)?;
+        }
+        if !self.version.is_empty() {
+            os.write_string(7, &self.version)?;
+        }
+        if !self.scheme.is_empty() {
+            os.write_string(8, &self.scheme)?;
+        }
+        if self.tls != false {
+            os.write_bool(9, self.tls)?;
+        }
+        if !self.request_id.is_empty() {
+            os.write_string(10, &self.request_id)?;
+        }
+        if !self.path.is_empty() {
+            os.write_string(11, &self.path)?;
+        }
+        if !self.query.is_empty() {
+            os.write_string(12, &self.query)?;
+        }
+        if !self.extension_method.is_empty() {
+            os.write_string(13, &self.extension_method)?;
+        }
+        if self.timeout_ms != 0 {
+            os.write_uint32(14, self.timeout_ms)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> HttpRequest {
+        HttpRequest::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "url",
+                |m: &HttpRequest| { &m.url },
+                |m: &mut HttpRequest| { &mut m.url },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<HttpMethod>>(
+                "method",
+                |m: &HttpRequest| { &m.method },
+                |m: &mut HttpRequest| { &mut m.method },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(
+                "headers",
+                |m: &HttpRequest| { &m.headers },
+                |m: &mut HttpRequest| { &mut m.headers },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeCarllercheBytes>(
+                "body",
+                |m: &HttpRequest| { &m.body },
+                |m: &mut HttpRequest| { &mut m.body },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "peer_identity",
+                |m: &HttpRequest| { &m.peer_identity },
+                |m: &mut HttpRequest| { &mut m.peer_identity },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "remote_addr",
+                |m: &HttpRequest| { &m.remote_addr },
+                |m: &mut HttpRequest| { &mut m.remote_addr },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "version",
+                |m: &HttpRequest| { &m.version },
+                |m: &mut HttpRequest| { &mut m.version },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "scheme",
+                |m: &HttpRequest| { &m.scheme },
+                |m: &mut HttpRequest| { &mut m.scheme },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                "tls",
+                |m: &HttpRequest| { &m.tls },
+                |m: &mut HttpRequest| { &mut m.tls },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "request_id",
+                |m: &HttpRequest| { &m.request_id },
+                |m: &mut HttpRequest| { &mut m.request_id },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "path",
+                |m: &HttpRequest| { &m.path },
+                |m: &mut HttpRequest| { &mut m.path },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "query",
+                |m: &HttpRequest| { &m.query },
+                |m: &mut HttpRequest| { &mut m.query },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "extension_method",
+                |m: &HttpRequest| { &m.extension_method },
+                |m: &mut HttpRequest| { &mut m.extension_method },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "timeout_ms",
+                |m: &HttpRequest| { &m.timeout_ms },
+                |m: &mut HttpRequest| { &mut m.timeout_ms },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<HttpRequest>(
+                "HttpRequest",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static HttpRequest {
+        static instance: ::protobuf::rt::LazyV2<HttpRequest> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(HttpRequest::new)
+    }
+}
+
+impl ::protobuf::Clear for HttpRequest {
+    fn clear(&mut self) {
+        self.url.clear();
+        self.method = HttpMethod::GET;
+        self.headers.clear();
+        self.body.clear();
+        self.peer_identity.clear();
+        self.remote_addr.clear();
+        self.version.clear();
+        self.scheme.clear();
+        self.tls = false;
+        self.request_id.clear();
+        self.path.clear();
+        self.query.clear();
+        self.extension_method.clear();
+        self.timeout_ms = 0;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for HttpRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for HttpRequest {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct HttpResponse {
+    // message fields
+    pub status: i32,
+    pub headers: ::std::collections::HashMap<::std::string::String, ::std::string::String>,
+    pub body: ::bytes::Bytes,
+    // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
+    pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a HttpResponse {
+    fn default() -> &'a HttpResponse {
+        <HttpResponse as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl HttpResponse {
+    pub fn new() -> HttpResponse {
+        ::std::default::Default::default()
+    }
+
+    // int32 status = 1;
+
+
+    pub fn get_status(&self) -> i32 {
+        self.status
+    }
+    pub fn clear_status(&mut self) {
+        self.status = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_status(&mut self, v: i32) {
+        self.status = v;
+    }
+
+    // repeated .proto.HttpResponse.HeadersEntry headers = 2;
+
+
+    pub fn get_headers(&self) -> &::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &self.headers
+    }
+    pub fn clear_headers(&mut self) {
+        self.headers.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_headers(&mut self, v: ::std::collections::HashMap<::std::string::String, ::std::string::String>) {
+        self.headers = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_headers(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &mut self.headers
+    }
+
+    // Take field
+    pub fn take_headers(&mut self) -> ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        ::std::mem::replace(&mut self.headers, ::std::collections::HashMap::new())
+    }
+
+    // bytes body = 3;
+
+
+    pub fn get_body(&self) -> &[u8] {
+        &self.body
+    }
+    pub fn clear_body(&mut self) {
+        self.body.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_body(&mut self, v: ::bytes::Bytes) {
+        self.body = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_body(&mut self) -> &mut ::bytes::Bytes {
+        &mut self.body
+    }
+
+    // Take field
+    pub fn take_body(&mut self) -> ::bytes::Bytes {
+        ::std::mem::replace(&mut self.body, ::bytes::Bytes::new())
+    }
+}
+
+impl ::protobuf::Message for HttpResponse {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int32()?;
+                    self.status = tmp;
+                },
+                2 => {
+                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(wire_type, is, &mut self.headers)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_proto3_carllerche_bytes_into(wire_type, is, &mut self.body)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.status != 0 {
+            my_size += ::protobuf::rt::value_size(1, self.status, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(2, &self.headers);
+        if !self.body.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(3, &self.body);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if self.status != 0 {
+            os.write_int32(1, self.status)?;
+        }
+        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(2, &self.headers, os)?;
+        if !self.body.is_empty() {
+            os.write_bytes(3, &self.body)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> HttpResponse {
+        HttpResponse::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt32>(
+                "status",
+                |m: &HttpResponse| { &m.status },
+                |m: &mut HttpResponse| { &mut m.status },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(
+                "headers",
+                |m: &HttpResponse| { &m.headers },
+                |m: &mut HttpResponse| { &mut m.headers },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeCarllercheBytes>(
+                "body",
+                |m: &HttpResponse| { &m.body },
+                |m: &mut HttpResponse| { &mut m.body },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<HttpResponse>(
+                "HttpResponse",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static HttpResponse {
+        static instance: ::protobuf::rt::LazyV2<HttpResponse> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(HttpResponse::new)
+    }
+}
+
+impl ::protobuf::Clear for HttpResponse {
+    fn clear(&mut self) {
+        self.status = 0;
+        self.headers.clear();
+        self.body.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for HttpResponse {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for HttpResponse {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct MeshError {
+    // message fields
+    pub code: ErrorCode,
+    pub message: ::std::string::String,
+    pub retryable: bool,
+    pub details: ::std::collections::HashMap<::std::string::String, ::std::string::String>,
+    // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
+    pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a MeshError {
+    fn default() -> &'a MeshError {
+        <MeshError as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl MeshError {
+    pub fn new() -> MeshError {
+        ::std::default::Default::default()
+    }
+
+    // .proto.ErrorCode code = 1;
+
+
+    pub fn get_code(&self) -> ErrorCode {
+        self.code
+    }
+    pub fn clear_code(&mut self) {
+        self.code = ErrorCode::UNKNOWN;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_code(&mut self, v: ErrorCode) {
+        self.code = v;
+    }
+
+    // string message = 2;
+
+
+    pub fn get_message(&self) -> &str {
+        &self.message
+    }
+    pub fn clear_message(&mut self) {
+        self.message.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_message(&mut self, v: ::std::string::String) {
+        self.message = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_message(&mut self) -> &mut ::std::string::String {
+        &mut self.message
+    }
+
+    // Take field
+    pub fn take_message(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.message, ::std::string::String::new())
+    }
+
+    // bool retryable = 3;
+
+
+    pub fn get_retryable(&self) -> bool {
+        self.retryable
+    }
+    pub fn clear_retryable(&mut self) {
+        self.retryable = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_retryable(&mut self, v: bool) {
+        self.retryable = v;
+    }
+
+    // repeated .proto.MeshError.DetailsEntry details = 4;
+
+
+    pub fn get_details(&self) -> &::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &self.details
+    }
+    pub fn clear_details(&mut self) {
+        self.details.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_details(&mut self, v: ::std::collections::HashMap<::std::string::String, ::std::string::String>) {
+        self.details = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_details(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &mut self.details
+    }
+
+    // Take field
+    pub fn take_details(&mut self) -> ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        ::std::mem::replace(&mut self.details, ::std::collections::HashMap::new())
+    }
+}
+
+impl ::protobuf::Message for MeshError {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_proto3_enum_with_unknown_fields_into(wire_type, is, &mut self.code, 1, &mut self.unknown_fields)?
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.message)?;
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.retryable = tmp;
+                },
+                4 => {
+                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(wire_type, is, &mut self.details)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.code != ErrorCode::UNKNOWN {
+            my_size += ::protobuf::rt::enum_size(1, self.code);
+        }
+        if !self.message.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.message);
+        }
+        if self.retryable != false {
+            my_size += 2;
+        }
+        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(4, &self.details);
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if self.code != ErrorCode::UNKNOWN {
+            os.write_enum(1, ::protobuf::ProtobufEnum::value(&self.code))?;
+        }
+        if !self.message.is_empty() {
+            os.write_string(2, &self.message)?;
+        }
+        if self.retryable != false {
+            os.write_bool(3, self.retryable)?;
+        }
+        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(4, &self.details, os)?;
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> MeshError {
+        MeshError::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<ErrorCode>>(
+                "code",
+                |m: &MeshError| { &m.code },
+                |m: &mut MeshError| { &mut m.code },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "message",
+                |m: &MeshError| { &m.message },
+                |m: &mut MeshError| { &mut m.message },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                "retryable",
+                |m: &MeshError| { &m.retryable },
+                |m: &mut MeshError| { &mut m.retryable },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(
+                "details",
+                |m: &MeshError| { &m.details },
+                |m: &mut MeshError| { &mut m.details },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<MeshError>(
+                "MeshError",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static MeshError {
+        static instance: ::protobuf::rt::LazyV2<MeshError> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(MeshError::new)
+    }
+}
+
+impl ::protobuf::Clear for MeshError {
+    fn clear(&mut self) {
+        self.code = ErrorCode::UNKNOWN;
+        self.message.clear();
+        self.retryable = false;
+        self.details.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for MeshError {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for MeshError {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct PodMessage {
+    // message oneof groups
+    pub kind: ::std::option::Option<PodMessage_oneof_kind>,
+    // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
+    pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a PodMessage {
+    fn default() -> &'a PodMessage {
+        <PodMessage as ::protobuf::Message>::default_instance()
+    }
+}
+
+#[derive(Clone,PartialEq,Debug)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum PodMessage_oneof_kind {
+    subscribe(Subscribe),
+    ack(Ack),
+    status(PodStatus),
+    status_request(StatusRequest),
+    fetch_module(FetchModule),
+}
+
+impl PodMessage {
+    pub fn new() -> PodMessage {
+        ::std::default::Default::default()
+    }
+
+    // .proto.Subscribe subscribe = 1;
+
+
+    pub fn get_subscribe(&self) -> &Subscribe {
+        match self.kind {
+            ::std::option::Option::Some(PodMessage_oneof_kind::subscribe(ref v)) => v,
+            _ => <Subscribe as ::protobuf::Message>::default_instance(),
+        }
+    }
+    pub fn clear_subscribe(&mut self) {
+        self.kind = ::std::option::Option::None;
+    }
+
+    pub fn has_subscribe(&self) -> bool {
+        match self.kind {
+            ::std::option::Option::Some(PodMessage_oneof_kind::subscribe(..)) => true,
+            _ => false,
+        }
+    }
+
+    // Param is passed by value, moved
+    pub fn set_subscribe(&mut self, v: Subscribe) {
+        self.kind = ::std::option::Option::Some(PodMessage_oneof_kind::subscribe(v))
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_subscribe(&mut self) -> &mut Subscribe {
+        if let ::std::option::Option::Some(PodMessage_oneof_kind::subscribe(_)) = self.kind {
+        } else {
+            self.kind = ::std::option::Option::Some(PodMessage_oneof_kind::subscribe(Subscribe::new()));
+        }
+        match self.kind {
+            ::std::option::Option::Some(PodMessage_oneof_kind::subscribe(ref mut v)) => v,
+            _ => panic!(),
+        }
+    }
+
+    // Take field
+    pub fn take_subscribe(&mut self) -> Subscribe {
+        if self.has_subscribe() {
+            match self.kind.take() {
+                ::std::option::Option::Some(PodMessage_oneof_kind::subscribe(v)) => v,
+                _ => panic!(),
+            }
+        } else {
+            Subscribe::new()
+        }
+    }
+
+    // .proto.Ack ack = 2;
+
+
+    pub fn get_ack(&self) -> &Ack {
+        match self.kind {
+            ::std::option::Option::Some(PodMessage_oneof_kind::ack(ref v)) => v,
+            _ => <Ack as ::protobuf::Message>::default_instance(),
+        }
+    }
+    pub fn clear_ack(&mut self) {
+        self.kind = ::std::option::Option::None;
+    }
+
+    pub fn has_ack(&self) -> bool {
+        match self.kind {
+            ::std::option::Option::Some(PodMessage_oneof_kind::ack(..)) => true,
+            _ => false,
+        }
+    }
+
+    // Param is passed by value, moved
+    pub fn set_ack(&mut self, v: Ack) {
+        self.kind = ::std::option::Option::Some(PodMessage_oneof_kind::ack(v))
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_ack(&mut self) -> &mut Ack {
+        if let ::std::option::Option::Some(PodMessage_oneof_kind::ack(_)) = self.kind {
+        } else {
+            self.kind = ::std::option::Option::Some(PodMessage_oneof_kind::ack(Ack::new()));
+        }
+        match self.kind {
+            ::std::option::Option::Some(PodMessage_oneof_kind::ack(ref mut v)) => v,
+            _ => panic!(),
+        }
+    }
+
+    // Take field
+    pub fn take_ack(&mut self) -> Ack {
+        if self.has_ack() {
+            match self.kind.take() {
+                ::std::option::Option::Some(PodMessage_oneof_kind::ack(v)) => v,
+                _ => panic!(),
+            }
+        } else {
+            Ack::new()
+        }
+    }
+
+    // .proto.PodStatus status = 3;
+
+
+    pub fn get_status(&self) -> &PodStatus {
+        match self.kind {
+            ::std::option::Option::Some(PodMessage_oneof_kind::status(ref v)) => v,
+            _ => <PodStatus as ::protobuf::Message>::default_instance(),
+        }
+    }
+    pub fn clear_status(&mut self) {
+        self.kind = ::std::option::Option::None;
+    }
+
+    pub fn has_status(&self) -> bool {
+        match self.kind {
+            ::std::option::Option::Some(PodMessage_oneof_kind::status(..)) => true,
+            _ => false,
+        }
+    }
+
+    // Param is passed by value, moved
+    pub fn set_status(&mut self, v: PodStatus) {
+        self.kind = ::std::option::Option::Some(PodMessage_oneof_kind::status(v))
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_status(&mut self) -> &mut PodStatus {
+        if let ::std::option::Option::Some(PodMessage_oneof_kind::status(_)) = self.kind {
+        } else {
+            self.kind = ::std::option::Option::Some(PodMessage_oneof_kind::status(PodStatus::new()));
+        }
+        match self.kind {
+            ::std::option::Option::Some(PodMessage_oneof_kind::status(ref mut v)) => v,
+            _ => panic!(),
+        }
+    }
+
+    // Take field
+    pub fn take_status(&mut self) -> PodStatus {
+        if self.has_status() {
+            match self.kind.take() {
+                ::std::option::Option::Some(PodMessage_oneof_kind::status(v)) => v,
+                _ => panic!(),
+            }
+        } else {
+            PodStatus::new()
+        }
+    }
+
+    // .proto.StatusRequest status_request = 4;
+
+
+    pub fn get_status_request(&self) -> &StatusRequest {
+        match self.kind {
+            ::std::option::Option::Some(PodMessage_oneof_kind::status_request(ref v)) => v,
+            _ => <StatusRequest as ::protobuf::Message>::default_instance(),
+        }
+    }
+    pub fn clear_status_request(&mut self) {
+        self.kind = ::std::option::Option::None;
+    }
+
+    pub fn has_status_request(&self) -> bool {
+        match self.kind {
+            ::std::option::Option::Some(PodMessage_oneof_kind::status_request(..)) => true,
+            _ => false,
+        }
+    }
+
+    // Param is passed by value, moved
+    pub fn set_status_request(&mut self, v: StatusRequest) {
+        self.kind = ::std::option::Option::Some(PodMessage_oneof_kind::status_request(v))
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_status_request(&mut self) -> &mut StatusRequest {
+        if let ::std::option::Option::Some(PodMessage_oneof_kind::status_request(_)) = self.kind {
+        } else {
+            self.kind = ::std::option::Option::Some(PodMessage_oneof_kind::status_request(StatusRequest::new()));
+        }
+        match self.kind {
+            ::std::option::Option::Some(PodMessage_oneof_kind::status_request(ref mut v)) => v,
+            _ => panic!(),
+        }
+    }
+
+    // Take field
+    pub fn take_status_request(&mut self) -> StatusRequest {
+        if self.has_status_request() {
+            match self.kind.take() {
+                ::std::option::Option::Some(PodMessage_oneof_kind::status_request(v)) => v,
+                _ => panic!(),
+            }
+        } else {
+            StatusRequest::new()
+        }
+    }
+
+    // .proto.FetchModule fetch_module = 5;
+
+
+    pub fn get_fetch_module(&self) -> &FetchModule {
+        match self.kind {
+            ::std::option::Option::Some(PodMessage_oneof_kind::fetch_module(ref v)) => v,
+            _ => <FetchModule as ::protobuf::Message>::default_instance(),
+        }
+    }
+    pub fn clear_fetch_module(&mut self) {
+        self.kind = ::std::option::Option::None;
+    }
+
+    pub fn has_fetch_module(&self) -> bool {
+        match self.kind {
+            ::std::option::Option::Some(PodMessage_oneof_kind::fetch_module(..)) => true,
+            _ => false,
+        }
+    }
+
+    // Param is passed by value, moved
+    pub fn set_fetch_module(&mut self, v: FetchModule) {
+        self.kind = ::std::option::Option::Some(PodMessage_oneof_kind::fetch_module(v))
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_fetch_module(&mut self) -> &mut FetchModule {
+        if let ::std::option::Option::Some(PodMessage_oneof_kind::fetch_module(_)) = self.kind {
+        } else {
+            self.kind = ::std::option::Option::Some(PodMessage_oneof_kind::fetch_module(FetchModule::new()));
+        }
+        match self.kind {
+            ::std::option::Option::Some(PodMessage_oneof_kind::fetch_module(ref mut v)) => v,
+            _ => panic!(),
+        }
+    }
+
+    // Take field
+    pub fn take_fetch_module(&mut self) -> FetchModule {
+        if self.has_fetch_module() {
+            match self.kind.take() {
+                ::std::option::Option::Some(PodMessage_oneof_kind::fetch_module(v)) => v,
+                _ => panic!(),
+            }
+        } else {
+            FetchModule::new()
+        }
+    }
+}
+
+impl ::protobuf::Message for PodMessage {
+    fn is_initialized(&self) -> bool {
+        if let Some(PodMessage_oneof_kind::subscribe(ref v)) = self.kind {
+            if !v.is_initialized() {
+                return false;
+            }
+        }
+        if let Some(PodMessage_oneof_kind::ack(ref v)) = self.kind {
+            if !v.is_initialized() {
+                return false;
+            }
+        }
+        if let Some(PodMessage_oneof_kind::status(ref v)) = self.kind {
+            if !v.is_initialized() {
+                return false;
+            }
+        }
+        if let Some(PodMessage_oneof_kind::status_request(ref v)) = self.kind {
+            if !v.is_initialized() {
+                return false;
+            }
+        }
+        if let Some(PodMessage_oneof_kind::fetch_module(ref v)) = self.kind {
+            if !v.is_initialized() {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    self.kind = ::std::option::Option::Some(PodMessage_oneof_kind::subscribe(is.read_message()?));
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    self.kind = ::std::option::Option::Some(PodMessage_oneof_kind::ack(is.read_message()?));
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    self.kind = ::std::option::Option::Some(PodMessage_oneof_kind::status(is.read_message()?));
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    self.kind = ::std::option::Option::Some(PodMessage_oneof_kind::status_request(is.read_message()?));
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    self.kind = ::std::option::Option::Some(PodMessage_oneof_kind::fetch_module(is.read_message()?));
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let ::std::option::Option::Some(ref v) = self.kind {
+            match v {
+                &PodMessage_oneof_kind::subscribe(ref v) => {
+                    let len = v.compute_size();
+                    my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+                },
+                &PodMessage_oneof_kind::ack(ref v) => {
+                    let len = v.compute_size();
+                    my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+                },
+                &PodMessage_oneof_kind::status(ref v) => {
+                    let len = v.compute_size();
+                    my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+                },
+                &PodMessage_oneof_kind::status_request(ref v) => {
+                    let len = v.compute_size();
+                    my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+                },
+                &PodMessage_oneof_kind::fetch_module(ref v) => {
+                    let len = v.compute_size();
+                    my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+                },
+            };
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if let ::std::option::Option::Some(ref v) = self.kind {
+            match v {
+                &PodMessage_oneof_kind::subscribe(ref v) => {
+                    os.write_tag(1, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+                    os.write_raw_varint32(v.get_cached_size())?;
+                    v.write_to_with_cached_sizes(os)?;
+                },
+                &PodMessage_oneof_kind::ack(ref v) => {
+                    os.write_tag(2, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+                    os.write_raw_varint32(v.get_cached_size())?;
+                    v.write_to_with_cached_sizes(os)?;
+                },
+                &PodMessage_oneof_kind::status(ref v) => {
+                    os.write_tag(3, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+                    os.write_raw_varint32(v.get_cached_size())?;
+                    v.write_to_with_cached_sizes(os)?;
+                },
+                &PodMessage_oneof_kind::status_request(ref v) => {
+                    os.write_tag(4, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+                    os.write_raw_varint32(v.get_cached_size())?;
+                    v.write_to_with_cached_sizes(os)?;
+                },
+                &PodMessage_oneof_kind::fetch_module(ref v) => {
+                    os.write_tag(5, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+                    os.write_raw_varint32(v.get_cached_size())?;
+                    v.write_to_with_cached_sizes(os)?;
+                },
+            };
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> PodMessage {
+        PodMessage::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_singular_message_accessor::<_, Subscribe>(
+                "subscribe",
+                PodMessage::has_subscribe,
+                PodMessage::get_subscribe,
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_message_accessor::<_, Ack>(
+                "ack",
+                PodMessage::has_ack,
+                PodMessage::get_ack,
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_message_accessor::<_, PodStatus>(
+                "status",
+                PodMessage::has_status,
+                PodMessage::get_status,
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_message_accessor::<_, StatusRequest>(
+                "status_request",
+                PodMessage::has_status_request,
+                PodMessage::get_status_request,
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_message_accessor::<_, FetchModule>(
+                "fetch_module",
+                PodMessage::has_fetch_module,
+                PodMessage::get_fetch_module,
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<PodMessage>(
+                "PodMessage",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static PodMessage {
+        static instance: ::protobuf::rt::LazyV2<PodMessage> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(PodMessage::new)
+    }
+}
+
+impl ::protobuf::Clear for PodMessage {
+    fn clear(&mut self) {
+        self.kind = ::std::option::Option::None;
+        self.kind = ::std::option::Option::None;
+        self.kind = ::std::option::Option::None;
+        self.kind = ::std::option::Option::None;
+        self.kind = ::std::option::Option::None;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for PodMessage {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for PodMessage {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct PlaneMessage {
+    // message oneof groups
+    pub kind: ::std::option::Option<PlaneMessage_oneof_kind>,
+    // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
+    pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a PlaneMessage {
+    fn default() -> &'a PlaneMessage {
+        <PlaneMessage as ::protobuf::Message>::default_instance()
+    }
+}
+
+#[derive(Clone,PartialEq,Debug)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum PlaneMessage_oneof_kind {
+    update(ConfigUpdate),
+    status(PlaneStatus),
+    module(Module),
+}
+
+impl PlaneMessage {
+    pub fn new() -> PlaneMessage {
+        ::std::default::Default::default()
+    }
+
+    // .proto.ConfigUpdate update = 1;
+
+
+    pub fn get_update(&self) -> &ConfigUpdate {
+        match self.kind {
+            ::std::option::Option::Some(PlaneMessage_oneof_kind::update(ref v)) => v,
+            _ => <ConfigUpdate as ::protobuf::Message>::default_instance(),
+        }
+    }
+    pub fn clear_update(&mut self) {
+        self.kind = ::std::option::Option::None;
+    }
+
+    pub fn has_update(&self) -> bool {
+        match self.kind {
+            ::std::option::Option::Some(PlaneMessage_oneof_kind::update(..)) => true,
+            _ => false,
+        }
+    }
+
+    // Param is passed by value, moved
+    pub fn set_update(&mut self, v: ConfigUpdate) {
+        self.kind = ::std::option::Option::Some(PlaneMessage_oneof_kind::update(v))
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_update(&mut self) -> &mut ConfigUpdate {
+        if let ::std::option::Option::Some(PlaneMessage_oneof_kind::update(_)) = self.kind {
+        } else {
+            self.kind = ::std::option::Option::Some(PlaneMessage_oneof_kind::update(ConfigUpdate::new()));
+        }
+        match self.kind {
+            ::std::option::Option::Some(PlaneMessage_oneof_kind::update(ref mut v)) => v,
+            _ => panic!(),
+        }
+    }
+
+    // Take field
+    pub fn take_update(&mut self) -> ConfigUpdate {
+        if self.has_update() {
+            match self.kind.take() {
+                ::std::option::Option::Some(PlaneMessage_oneof_kind::update(v)) => v,
+                _ => panic!(),
+            }
+        } else {
+            ConfigUpdate::new()
+        }
+    }
+
+    // .proto.PlaneStatus status = 2;
+
+
+    pub fn get_status(&self) -> &PlaneStatus {
+        match self.kind {
+            ::std::option::Option::Some(PlaneMessage_oneof_kind::status(ref v)) => v,
+            _ => <PlaneStatus as ::protobuf::Message>::default_instance(),
+        }
+    }
+    pub fn clear_status(&mut self) {
+        self.kind = ::std::option::Option::None;
+    }
+
+    pub fn has_status(&self) -> bool {
+        match self.kind {
+            ::std::option::Option::Some(PlaneMessage_oneof_kind::status(..)) => true,
+            _ => false,
+        }
+    }
+
+    // Param is passed by value, moved
+    pub fn set_status(&mut self, v: PlaneStatus) {
+        self.kind = ::std::option::Option::Some(PlaneMessage_oneof_kind::status(v))
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_status(&mut self) -> &mut PlaneStatus {
+        if let ::std::option::Option::Some(PlaneMessage_oneof_kind::status(_)) = self.kind {
+        } else {
+            self.kind = ::std::option::Option::Some(PlaneMessage_oneof_kind::status(PlaneStatus::new()));
+        }
+        match self.kind {
+            ::std::option::Option::Some(PlaneMessage_oneof_kind::status(ref mut v)) => v,
+            _ => panic!(),
+        }
+    }
+
+    // Take field
+    pub fn take_status(&mut self) -> PlaneStatus {
+        if self.has_status() {
+            match self.kind.take() {
+                ::std::option::Option::Some(PlaneMessage_oneof_kind::status(v)) => v,
+                _ => panic!(),
+            }
+        } else {
+            PlaneStatus::new()
+        }
+    }
+
+    // .proto.Module module = 3;
+
+
+    pub fn get_module(&self) -> &Module {
+        match self.kind {
+            ::std::option::Option::Some(PlaneMessage_oneof_kind::module(ref v)) => v,
+            _ => <Module as ::protobuf::Message>::default_instance(),
+        }
+    }
+    pub fn clear_module(&mut self) {
+        self.kind = ::std::option::Option::None;
+    }
+
+    pub fn has_module(&self) -> bool {
+        match self.kind {
+            ::std::option::Option::Some(PlaneMessage_oneof_kind::module(..)) => true,
+            _ => false,
+        }
+    }
+
+    // Param is passed by value, moved
+    pub fn set_module(&mut self, v: Module) {
+        self.kind = ::std::option::Option::Some(PlaneMessage_oneof_kind::module(v))
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_module(&mut self) -> &mut Module {
+        if let ::std::option::Option::Some(PlaneMessage_oneof_kind::module(_)) = self.kind {
+        } else {
+            self.kind = ::std::option::Option::Some(PlaneMessage_oneof_kind::module(Module::new()));
+        }
+        match self.kind {
+            ::std::option::Option::Some(PlaneMessage_oneof_kind::module(ref mut v)) => v,
+            _ => panic!(),
+        }
+    }
+
+    // Take field
+    pub fn take_module(&mut self) -> Module {
+        if self.has_module() {
+            match self.kind.take() {
+                ::std::option::Option::Some(PlaneMessage_oneof_kind::module(v)) => v,
+                _ => panic!(),
+            }
+        } else {
+            Module::new()
+        }
+    }
+}
+
+impl ::protobuf::Message for PlaneMessage {
+    fn is_initialized(&self) -> bool {
+        if let Some(PlaneMessage_oneof_kind::update(ref v)) = self.kind {
+            if !v.is_initialized() {
+                return false;
+            }
+        }
+        if let Some(PlaneMessage_oneof_kind::status(ref v)) = self.kind {
+            if !v.is_initialized() {
+                return false;
+            }
+        }
+        if let Some(PlaneMessage_oneof_kind::module(ref v)) = self.kind {
+            if !v.is_initialized() {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    self.kind = ::std::option::Option::Some(PlaneMessage_oneof_kind::update(is.read_message()?));
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    self.kind = ::std::option::Option::Some(PlaneMessage_oneof_kind::status(is.read_message()?));
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    self.kind = ::std::option::Option::Some(PlaneMessage_oneof_kind::module(is.read_message()?));
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let ::std::option::Option::Some(ref v) = self.kind {
+            match v {
+                &PlaneMessage_oneof_kind::update(ref v) => {
+                    let len = v.compute_size();
+                    my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+                },
+                &PlaneMessage_oneof_kind::status(ref v) => {
+                    let len = v.compute_size();
+                    my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+                },
+                &PlaneMessage_oneof_kind::module(ref v) => {
+                    let len = v.compute_size();
+                    my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+                },
+            };
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if let ::std::option::Option::Some(ref v) = self.kind {
+            match v {
+                &PlaneMessage_oneof_kind::update(ref v) => {
+                    os.write_tag(1, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+                    os.write_raw_varint32(v.get_cached_size())?;
+                    v.write_to_with_cached_sizes(os)?;
+                },
+                &PlaneMessage_oneof_kind::status(ref v) => {
+                    os.write_tag(2, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+                    os.write_raw_varint32(v.get_cached_size())?;
+                    v.write_to_with_cached_sizes(os)?;
+                },
+                &PlaneMessage_oneof_kind::module(ref v) => {
+                    os.write_tag(3, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+                    os.write_raw_varint32(v.get_cached_size())?;
+                    v.write_to_with_cached_sizes(os)?;
+                },
+            };
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> PlaneMessage {
+        PlaneMessage::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_singular_message_accessor::<_, ConfigUpdate>(
+                "update",
+                PlaneMessage::has_update,
+                PlaneMessage::get_update,
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_message_accessor::<_, PlaneStatus>(
+                "status",
+                PlaneMessage::has_status,
+                PlaneMessage::get_status,
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_message_accessor::<_, Module>(
+                "module",
+                PlaneMessage::has_module,
+                PlaneMessage::get_module,
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<PlaneMessage>(
+                "PlaneMessage",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static PlaneMessage {
+        static instance: ::protobuf::rt::LazyV2<PlaneMessage> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(PlaneMessage::new)
+    }
+}
+
+impl ::protobuf::Clear for PlaneMessage {
+    fn clear(&mut self) {
+        self.kind = ::std::option::Option::None;
+        self.kind = ::std::option::Option::None;
+        self.kind = ::std::option::Option::None;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for PlaneMessage {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for PlaneMessage {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct Subscribe {
+    // message fields
+    pub pod_id: ::std::string::String,
+    pub version: ::std::string::String,
+    // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
+    pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a Subscribe {
+    fn default() -> &'a Subscribe {
+        <Subscribe as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Subscribe {
+    pub fn new() -> Subscribe {
+        ::std::default::Default::default()
+    }
+
+    // string pod_id = 1;
+
+
+    pub fn get_pod_id(&self) -> &str {
+        &self.pod_id
+    }
+    pub fn clear_pod_id(&mut self) {
+        self.pod_id.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_pod_id(&mut self, v: ::std::string::String) {
+        self.pod_id = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_pod_id(&mut self) -> &mut ::std::string::String {
+        &mut self.pod_id
+    }
+
+    // Take field
+    pub fn take_pod_id(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.pod_id, ::std::string::String::new())
+    }
+
+    // string version = 2;
+
+
+    pub fn get_version(&self) -> &str {
+        &self.version
+    }
+    pub fn clear_version(&mut self) {
+        self.version.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_version(&mut self, v: ::std::string::String) {
+        self.version = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_version(&mut self) -> &mut ::std::string::String {
+        &mut self.version
+    }
+
+    // Take field
+    pub fn take_version(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.version, ::std::string::String::new())
+    }
+}
+
+impl ::protobuf::Message for Subscribe {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.pod_id)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.version)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.pod_id.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.pod_id);
+        }
+        if !self.version.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.version);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.pod_id.is_empty() {
+            os.write_string(1, &self.pod_id)?;
+        }
+        if !self.version.is_empty() {
+            os.write_string(2, &self.version)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> Subscribe {
+        Subscribe::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "pod_id",
+                |m: &Subscribe| { &m.pod_id },
+                |m: &mut Subscribe| { &mut m.pod_id },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "version",
+                |m: &Subscribe| { &m.version },
+                |m: &mut Subscribe| { &mut m.version },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<Subscribe>(
+                "Subscribe",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static Subscribe {
+        static instance: ::protobuf::rt::LazyV2<Subscribe> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(Subscribe::new)
+    }
+}
+
+impl ::protobuf::Clear for Subscribe {
+    fn clear(&mut self) {
+        self.pod_id.clear();
+        self.version.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Subscribe {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Subscribe {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct Resource {
+    // message fields
+    pub name: ::std::string::String,
+    pub json: ::std::string::String,
+    pub order: u32,
+    // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
+    pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a Resource {
+    fn default() -> &'a Resource {
+        <Resource as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Resource {
+    pub fn new() -> Resource {
+        ::std::default::Default::default()
+    }
+
+    // string name = 1;
+
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+    pub fn clear_name(&mut self) {
+        self.name.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_name(&mut self, v: ::std::string::String) {
+        self.name = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_name(&mut self) -> &mut ::std::string::String {
+        &mut self.name
+    }
+
+    // Take field
+    pub fn take_name(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.name, ::std::string::String::new())
+    }
+
+    // string json = 2;
+
+
+    pub fn get_json(&self) -> &str {
+        &self.json
+    }
+    pub fn clear_json(&mut self) {
+        self.json.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_json(&mut self, v: ::std::string::String) {
+        self.json = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_json(&mut self) -> &mut ::std::string::String {
+        &mut self.json
+    }
+
+    // Take field
+    pub fn take_json(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.json, ::std::string::String::new())
+    }
+
+    // uint32 order = 3;
+
+
+    pub fn get_order(&self) -> u32 {
+        self.order
+    }
+    pub fn clear_order(&mut self) {
+        self.order = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_order(&mut self, v: u32) {
+        self.order = v;
+    }
+}
+
+impl ::protobuf::Message for Resource {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.name)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.json)?;
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.order = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.name.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.name);
+        }
+        if !self.json.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.json);
+        }
+        if self.order != 0 {
+            my_size += ::protobuf::rt::value_size(3, self.order, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.name.is_empty() {
+            os.write_string(1, &self.name)?;
+        }
+        if !self.json.is_empty() {
+            os.write_string(2, &self.json)?;
+        }
+        if self.order != 0 {
+            os.write_uint32(3, self.order)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> Resource {
+        Resource::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "name",
+                |m: &Resource| { &m.name },
+                |m: &mut Resource| { &mut m.name },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "json",
+                |m: &Resource| { &m.json },
+                |m: &mut Resource| { &mut m.json },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "order",
+                |m: &Resource| { &m.order },
+                |m: &mut Resource| { &mut m.order },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<Resource>(
+                "Resource",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static Resource {
+        static instance: ::protobuf::rt::LazyV2<Resource> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(Resource::new)
+    }
+}
+
+impl ::protobuf::Clear for Resource {
+    fn clear(&mut self) {
+        self.name.clear();
+        self.json.clear();
+        self.order = 0;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Resource {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Resource {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct ConfigUpdate {
+    // message fields
+    pub version: ::std::string::String,
+    pub nonce: ::std::string::String,
+    pub resources: ::protobuf::RepeatedField<Resource>,
+    pub removed: ::protobuf::RepeatedField<::std::string::String>,
+    pub full: bool,
+    // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
+    pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a ConfigUpdate {
+    fn default() -> &'a ConfigUpdate {
+        <ConfigUpdate as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl ConfigUpdate {
+    pub fn new() -> ConfigUpdate {
+        ::std::default::Default::default()
+    }
+
+    // string version = 1;
+
+
+    pub fn get_version(&self) -> &str {
+        &self.version
+    }
+    pub fn clear_version(&mut self) {
+        self.version.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_version(&mut self, v: ::std::string::String) {
+        self.version = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_version(&mut self) -> &mut ::std::string::String {
+        &mut self.version
+    }
+
+    // Take field
+    pub fn take_version(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.version, ::std::string::String::new())
+    }
+
+    // string nonce = 2;
+
+
+    pub fn get_nonce(&self) -> &str {
+        &self.nonce
+    }
+    pub fn clear_nonce(&mut self) {
+        self.nonce.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_nonce(&mut self, v: ::std::string::String) {
+        self.nonce = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_nonce(&mut self) -> &mut ::std::string::String {
+        &mut self.nonce
+    }
+
+    // Take field
+    pub fn take_nonce(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.nonce, ::std::string::String::new())
+    }
+
+    // repeated .proto.Resource resources = 3;
+
+
+    pub fn get_resources(&self) -> &[Resource] {
+        &self.resources
+    }
+    pub fn clear_resources(&mut self) {
+        self.resources.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_resources(&mut self, v: ::protobuf::RepeatedField<Resource>) {
+        self.resources = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_resources(&mut self) -> &mut ::protobuf::RepeatedField<Resource> {
+        &mut self.resources
+    }
+
+    // Take field
+    pub fn take_resources(&mut self) -> ::protobuf::RepeatedField<Resource> {
+        ::std::mem::replace(&mut self.resources, ::protobuf::RepeatedField::new())
+    }
+
+    // repeated string removed = 4;
+
+
+    pub fn get_removed(&self) -> &[::std::string::String] {
+        &self.removed
+    }
+    pub fn clear_removed(&mut self) {
+        self.removed.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_removed(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
+        self.removed = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_removed(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.removed
+    }
+
+    // Take field
+    pub fn take_removed(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
+        ::std::mem::replace(&mut self.removed, ::protobuf::RepeatedField::new())
+    }
+
+    // bool full = 5;
+
+
+    pub fn get_full(&self) -> bool {
+        self.full
+    }
+    pub fn clear_full(&mut self) {
+        self.full = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_full(&mut self, v: bool) {
+        self.full = v;
+    }
+}
+
+impl ::protobuf::Message for ConfigUpdate {
+    fn is_initialized(&self) -> bool {
+        for v in &self.resources {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.version)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.nonce)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.resources)?;
+                },
+                4 => {
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.removed)?;
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.full = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.version.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.version);
+        }
+        if !self.nonce.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.nonce);
+        }
+        for value in &self.resources {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        for value in &self.removed {
+            my_size += ::protobuf::rt::string_size(4, &value);
+        };
+        if self.full != false {
+            my_size += 2;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.version.is_empty() {
+            os.write_string(1, &self.version)?;
+        }
+        if !self.nonce.is_empty() {
+            os.write_string(2, &self.nonce)?;
+        }
+        for v in &self.resources {
+            os.write_tag(3, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        for v in &self.removed {
+            os.write_string(4, &v)?;
+        };
+        if self.full != false {
+            os.write_bool(5, self.full)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> ConfigUpdate {
+        ConfigUpdate::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "version",
+                |m: &ConfigUpdate| { &m.version },
+                |m: &mut ConfigUpdate| { &mut m.version },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "nonce",
+                |m: &ConfigUpdate| { &m.nonce },
+                |m: &mut ConfigUpdate| { &mut m.nonce },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Resource>>(
+                "resources",
+                |m: &ConfigUpdate| { &m.resources },
+                |m: &mut ConfigUpdate| { &mut m.resources },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "removed",
+                |m: &ConfigUpdate| { &m.removed },
+                |m: &mut ConfigUpdate| { &mut m.removed },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                "full",
+                |m: &ConfigUpdate| { &m.full },
+                |m: &mut ConfigUpdate| { &mut m.full },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<ConfigUpdate>(
+                "ConfigUpdate",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static ConfigUpdate {
+        static instance: ::protobuf::rt::LazyV2<ConfigUpdate> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(ConfigUpdate::new)
+    }
+}
+
+impl ::protobuf::Clear for ConfigUpdate {
+    fn clear(&mut self) {
+        self.version.clear();
+        self.nonce.clear();
+        self.resources.clear();
+        self.removed.clear();
+        self.full = false;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for ConfigUpdate {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for ConfigUpdate {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct Ack {
+    // message fields
+    pub version: ::std::string::String,
+    pub nonce: ::std::string::String,
+    pub error: ::std::string::String,
+    // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
+    pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a Ack {
+    fn default() -> &'a Ack {
+        <Ack as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Ack {
+    pub fn new() -> Ack {
+        ::std::default::Default::default()
+    }
+
+    // string version = 1;
+
+
+    pub fn get_version(&self) -> &str {
+        &self.version
+    }
+    pub fn clear_version(&mut self) {
+        self.version.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_version(&mut self, v: ::std::string::String) {
+        self.version = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_version(&mut self) -> &mut ::std::string::String {
+        &mut self.version
+    }
+
+    // Take field
+    pub fn take_version(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.version, ::std::string::String::new())
+    }
+
+    // string nonce = 2;
+
+
+    pub fn get_nonce(&self) -> &str {
+        &self.nonce
+    }
+    pub fn clear_nonce(&mut self) {
+        self.nonce.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_nonce(&mut self, v: ::std::string::String) {
+        self.nonce = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_nonce(&mut self) -> &mut ::std::string::String {
+        &mut self.nonce
+    }
+
+    // Take field
+    pub fn take_nonce(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.nonce, ::std::string::String::new())
+    }
+
+    // string error = 3;
+
+
+    pub fn get_error(&self) -> &str {
+        &self.error
+    }
+    pub fn clear_error(&mut self) {
+        self.error.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_error(&mut self, v: ::std::string::String) {
+        self.error = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_error(&mut self) -> &mut ::std::string::String {
+        &mut self.error
+    }
+
+    // Take field
+    pub fn take_error(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.error, ::std::string::String::new())
+    }
+}
+
+impl ::protobuf::Message for Ack {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.version)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.nonce)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.error)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.version.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.version);
+        }
+        if !self.nonce.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.nonce);
+        }
+        if !self.error.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.error);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.version.is_empty() {
+            os.write_string(1, &self.version)?;
+        }
+        if !self.nonce.is_empty() {
+            os.write_string(2, &self.nonce)?;
+        }
+        if !self.error.is_empty() {
+            os.write_string(3, &self.error)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> Ack {
+        Ack::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "version",
+                |m: &Ack| { &m.version },
+                |m: &mut Ack| { &mut m.version },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "nonce",
+                |m: &Ack| { &m.nonce },
+                |m: &mut Ack| { &mut m.nonce },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "error",
+                |m: &Ack| { &m.error },
+                |m: &mut Ack| { &mut m.error },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<Ack>(
+                "Ack",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static Ack {
+        static instance: ::protobuf::rt::LazyV2<Ack> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(Ack::new)
+    }
+}
+
+impl ::protobuf::Clear for Ack {
+    fn clear(&mut self) {
+        self.version.clear();
+        self.nonce.clear();
+        self.error.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Ack {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Ack {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct PodStatus {
+    // message fields
+    pub version: ::std::string::String,
+    pub error: ::std::string::String,
+    pub services: u32,
+    pub requests: u64,
+    pub errors: u64,
+    // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
+    pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a PodStatus {
+    fn default() -> &'a PodStatus {
+        <PodStatus as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl PodStatus {
+    pub fn new() -> PodStatus {
+        ::std::default::Default::default()
+    }
+
+    // string version = 1;
+
+
+    pub fn get_version(&self) -> &str {
+        &self.version
+    }
+    pub fn clear_version(&mut self) {
+        self.version.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_version(&mut self, v: ::std::string::String) {
+        self.version = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_version(&mut self) -> &mut ::std::string::String {
+        &mut self.version
+    }
+
+    // Take field
+    pub fn take_version(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.version, ::std::string::String::new())
+    }
+
+    // string error = 2;
+
+
+    pub fn get_error(&self) -> &str {
+        &self.error
+    }
+    pub fn clear_error(&mut self) {
+        self.error.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_error(&mut self, v: ::std::string::String) {
+        self.error = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_error(&mut self) -> &mut ::std::string::String {
+        &mut self.error
+    }
+
+    // Take field
+    pub fn take_error(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.error, ::std::string::String::new())
+    }
+
+    // uint32 services = 3;
+
+
+    pub fn get_services(&self) -> u32 {
+        self.services
+    }
+    pub fn clear_services(&mut self) {
+        self.services = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_services(&mut self, v: u32) {
+        self.services = v;
+    }
+
+    // uint64 requests = 4;
+
+
+    pub fn get_requests(&self) -> u64 {
+        self.requests
+    }
+    pub fn clear_requests(&mut self) {
+        self.requests = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_requests(&mut self, v: u64) {
+        self.requests = v;
+    }
+
+    // uint64 errors = 5;
+
+
+    pub fn get_errors(&self) -> u64 {
+        self.errors
+    }
+    pub fn clear_errors(&mut self) {
+        self.errors = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_errors(&mut self, v: u64) {
+        self.errors = v;
+    }
+}
+
+impl ::protobuf::Message for PodStatus {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.version)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.error)?;
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.services = tmp;
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.requests = tmp;
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.errors = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.version.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.version);
+        }
+        if !self.error.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.error);
+        }
+        if self.services != 0 {
+            my_size += ::protobuf::rt::value_size(3, self.services, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.requests != 0 {
+            my_size += ::protobuf::rt::value_size(4, self.requests, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.errors != 0 {
+            my_size += ::protobuf::rt::value_size(5, self.errors, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.version.is_empty() {
+            os.write_string(1, &self.version)?;
+        }
+        if !self.error.is_empty() {
+            os.write_string(2, &self.error)?;
+        }
+        if self.services != 0 {
+            os.write_uint32(3, self.services)?;
+        }
+        if self.requests != 0 {
+            os.write_uint64(4, self.requests)?;
+        }
+        if self.errors != 0 {
+            os.write_uint64(5, self.errors)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> PodStatus {
+        PodStatus::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "version",
+                |m: &PodStatus| { &m.version },
+                |m: &mut PodStatus| { &mut m.version },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "error",
+                |m: &PodStatus| { &m.error },
+                |m: &mut PodStatus| { &mut m.error },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                "services",
+                |m: &PodStatus| { &m.services },
+                |m: &mut PodStatus| { &mut m.services },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "requests",
+                |m: &PodStatus| { &m.requests },
+                |m: &mut PodStatus| { &mut m.requests },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "errors",
+                |m: &PodStatus| { &m.errors },
+                |m: &mut PodStatus| { &mut m.errors },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<PodStatus>(
+                "PodStatus",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static PodStatus {
+        static instance: ::protobuf::rt::LazyV2<PodStatus> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(PodStatus::new)
+    }
+}
+
+impl ::protobuf::Clear for PodStatus {
+    fn clear(&mut self) {
+        self.version.clear();
+        self.error.clear();
+        self.services = 0;
+        self.requests = 0;
+        self.errors = 0;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for PodStatus {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for PodStatus {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct StatusRequest {
+    // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
+    pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a StatusRequest {
+    fn default() -> &'a StatusRequest {
+        <StatusRequest as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl StatusRequest {
+    pub fn new() -> StatusRequest {
+        ::std::default::Default::default()
+    }
+}
+
+impl ::protobuf::Message for StatusRequest {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> StatusRequest {
+        StatusRequest::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let fields = ::std::vec::Vec::new();
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<StatusRequest>(
+                "StatusRequest",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static StatusRequest {
+        static instance: ::protobuf::rt::LazyV2<StatusRequest> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(StatusRequest::new)
+    }
+}
+
+impl ::protobuf::Clear for StatusRequest {
+    fn clear(&mut self) {
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for StatusRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for StatusRequest {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct FetchModule {
+    // message fields
+    pub digest: ::std::string::String,
+    // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
+    pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a FetchModule {
+    fn default() -> &'a FetchModule {
+        <FetchModule as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl FetchModule {
+    pub fn new() -> FetchModule {
+        ::std::default::Default::default()
+    }
+
+    // string digest = 1;
+
+
+    pub fn get_digest(&self) -> &str {
+        &self.digest
+    }
+    pub fn clear_digest(&mut self) {
+        self.digest.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_digest(&mut self, v: ::std::string::String) {
+        self.digest = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_digest(&mut self) -> &mut ::std::string::String {
+        &mut self.digest
+    }
+
+    // Take field
+    pub fn take_digest(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.digest, ::std::string::String::new())
+    }
+}
+
+impl ::protobuf::Message for FetchModule {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.digest)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.digest.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.digest);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.digest.is_empty() {
+            os.write_string(1, &self.digest)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> FetchModule {
+        FetchModule::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "digest",
+                |m: &FetchModule| { &m.digest },
+                |m: &mut FetchModule| { &mut m.digest },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<FetchModule>(
+                "FetchModule",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static FetchModule {
+        static instance: ::protobuf::rt::LazyV2<FetchModule> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(FetchModule::new)
+    }
+}
+
+impl ::protobuf::Clear for FetchModule {
+    fn clear(&mut self) {
+        self.digest.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for FetchModule {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for FetchModule {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct Module {
+    // message fields
+    pub digest: ::std::string::String,
+    pub wasm: ::bytes::Bytes,
+    pub error: ::std::string::String,
+    pub signature: ::bytes::Bytes,
+    // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
+    pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a Module {
+    fn default() -> &'a Module {
+        <Module as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Module {
+    pub fn new() -> Module {
+        ::std::default::Default::default()
+    }
+
+    // string digest = 1;
+
+
+    pub fn get_digest(&self) -> &str {
+        &self.digest
+    }
+    pub fn clear_digest(&mut self) {
+        self.digest.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_digest(&mut self, v: ::std::string::String) {
+        self.digest = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_digest(&mut self) -> &mut ::std::string::String {
+        &mut self.digest
+    }
+
+    // Take field
+    pub fn take_digest(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.digest, ::std::string::String::new())
+    }
+
+    // bytes wasm = 2;
+
+
+    pub fn get_wasm(&self) -> &[u8] {
+        &self.wasm
+    }
+    pub fn clear_wasm(&mut self) {
+        self.wasm.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_wasm(&mut self, v: ::bytes::Bytes) {
+        self.wasm = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_wasm(&mut self) -> &mut ::bytes::Bytes {
+        &mut self.wasm
+    }
+
+    // Take field
+    pub fn take_wasm(&mut self) -> ::bytes::Bytes {
+        ::std::mem::replace(&mut self.wasm, ::bytes::Bytes::new())
+    }
+
+    // string error = 3;
+
+
+    pub fn get_error(&self) -> &str {
+        &self.error
+    }
+    pub fn clear_error(&mut self) {
+        self.error.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_error(&mut self, v: ::std::string::String) {
+        self.error = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_error(&mut self) -> &mut ::std::string::String {
+        &mut self.error
+    }
+
+    // Take field
+    pub fn take_error(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.error, ::std::string::String::new())
+    }
+
+    // bytes signature = 4;
+
+
+    pub fn get_signature(&self) -> &[u8] {
+        &self.signature
+    }
+    pub fn clear_signature(&mut self) {
+        self.signature.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_signature(&mut self, v: ::bytes::Bytes) {
+        self.signature = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_signature(&mut self) -> &mut ::bytes::Bytes {
+        &mut self.signature
+    }
+
+    // Take field
+    pub fn take_signature(&mut self) -> ::bytes::Bytes {
+        ::std::mem::replace(&mut self.signature, ::bytes::Bytes::new())
+    }
+}
+
+impl ::protobuf::Message for Module {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.digest)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_carllerche_bytes_into(wire_type, is, &mut self.wasm)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.error)?;
+                },
+                4 => {
+                    ::protobuf::rt::read_singular_proto3_carllerche_bytes_into(wire_type, is, &mut self.signature)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.digest.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.digest);
+        }
+        if !self.wasm.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(2, &self.wasm);
+        }
+        if !self.error.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.error);
+        }
+        if !self.signature.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(4, &self.signature);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.digest.is_empty() {
+            os.write_string(1, &self.digest)?;
+        }
+        if !self.wasm.is_empty() {
+            os.write_bytes(2, &self.wasm)?;
+        }
+        if !self.error.is_empty() {
+            os.write_string(3, &self.error)?;
+        }
+        if !self.signature.is_empty() {
+            os.write_bytes(4, &self.signature)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: ::std::boxed::Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> Module {
+        Module::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            let mut fields = ::std::vec::Vec::new();
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "digest",
+                |m: &Module| { &m.digest },
+                |m: &mut Module| { &mut m.digest },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeCarllercheBytes>(
+                "wasm",
+                |m: &Module| { &m.wasm },
+                |m: &mut Module| { &mut m.wasm },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "error",
+                |m: &Module| { &m.error },
+                |m: &mut Module| { &mut m.error },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeCarllercheBytes>(
+                "signature",
+                |m: &Module| { &m.signature },
+                |m: &mut Module| { &mut m.signature },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<Module>(
+                "Module",
+                fields,
+                file_descriptor_proto()
+            )
+        })
+    }
+
+    fn default_instance() -> &'static Module {
+        static instance: ::protobuf::rt::LazyV2<Module> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(Module::new)
+    }
+}
+
+impl ::protobuf::Clear for Module {
+    fn clear(&mut self) {
+        self.digest.clear();
+        self.wasm.clear();
+        self.error.clear();
+        self.signature.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Module {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Module {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct PodReport {
+    // message fields
+    pub pod_id: ::std::string::String,
+    pub address: ::std::string::String,
+    pub connected: bool,
+    pub acked_version: ::std::string::String,
+    pub error: ::std::string::String,
+    pub status: ::protobuf::SingularPtrField<PodStatus>,
+    pub last_seen_ms: u64,
+    // special fields
+    #[cfg_attr(feature = "with-serde", serde(skip))]
+    pub unknown_fields: ::protobuf::UnknownFields,
+    #[cfg_attr(feature = "with-serde", serde(skip))]
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a PodReport {
+    fn default() -> &'a PodReport {
+        <PodReport as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl PodReport {
+    pub fn new() -> PodReport {
+        ::std::default::Default::default()
+    }
+
+    // string pod_id = 1;
+
+
+    pub fn get_pod_id(&self) -> &str {
+        &self.pod_id
+    }
+    pub fn clear_pod_id(&mut self) {
+        self.pod_id.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_pod_id(&mut self, v: ::std::string::String) {
+        self.pod_id = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_pod_id(&mut self) -> &mut ::std::string::String {
+        &mut self.pod_id
+    }
+
+    // Take field
+    pub fn take_pod_id(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.pod_id, ::std::string::String::new())
+    }
+
+    // string address = 2;
+
+
+    pub fn get_address(&self) -> &str {
+        &self.address
+    }
+    pub fn clear_address(&mut self) {
+        self.address.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_address(&mut self, v: ::std::string::String) {
+        self.address = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_address(&mut self) -> &mut ::std::string::String {
+        &mut self.address
+    }
+
+    // Take field
+    pub fn take_address(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.address, ::std::string::String::new())
+    }
+
+    // bool connected = 3;
+
+
+    pub fn get_connected(&self) -> bool {
+        self.connected
+    }
+    pub fn clear_connected(&mut self) {
+        self.connected = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_connected(&mut self, v: bool) {
+        self.connected = v;
+    }
+
+    // string acked_version = 4;
+
+
+    pub fn get_acked_version(&self) -> &str {
+        &self.acked_version
+    }
+    pub fn clear_acked_version(&mut self) {
+        self.acked_version.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_acked_version(&mut self, v: ::std::string::String) {
+        self.acked_version = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_acked_version(&mut self) -> &mut ::std::string::String {
+        &mut self.acked_version
+    }
+
+    // Take field
+    pub fn take_acked_version(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.acked_version, ::std::string::String::new())
+    }
+
+    // string error = 5;
+
+
+    pub fn get_error(&self) -> &str {
+        &self.error
+    }
+    pub fn clear_error(&mut self) {
+        self.error.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_error(&mut self, v: ::std::string::String) {
+        self.error = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_error(&mut self) -> &mut ::std::string::String {
+        &mut self.error
+    }
+
+    // Take field
+    pub fn take_error(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.error, ::std::string::String::new())
+    }
+
+    // .proto.PodStatus status = 6;
+
+
+    pub fn get_status(&self) -> &PodStatus {
+        self.status.as_ref().unwrap_or_else(|| <PodStatus as ::protobuf::Message>::default_instance())
+    }
+    pub fn clear_status(&mut self) {
+        self.status.clear();
+    }
+
+    pub fn has_status(&self) -> bool {
+        self.status.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_status(&mut self, v: PodStatus) {
+        self.status = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_status(&mut self) -> &mut PodStatus {
+        if self.status.is_none() {
+            self.status.set_default();
+        }
+        self.status.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_status(&mut self) -> PodStatus {
+        self.status.take().unwrap_or_else(|| PodStatus::new())
+    }
+
+    // uint64 last_seen_ms = 7;
+
+
+    pub fn get_last_seen_ms(&self) -> u64 {
+        self.last_seen_ms
+    }
+    pub fn clear_last_seen_ms(&mut self) {
+        self.last_seen_ms = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_last_seen_ms(&mut self, v: u64) {
+        self.last_seen_ms = v;
+    }
 }
 
-impl ::protobuf::Message for HttpRequest {
+impl ::protobuf::Message for PodReport {
     fn is_initialized(&self) -> bool {
+        for v in &self.status {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
         true
     }
 
@@ -152,16 +4184,33 @@ impl ::protobuf::Message for HttpRequest {
             let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.url)?;
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.pod_id)?;
                 },
                 2 => {
-                    ::protobuf::rt::read_proto3_enum_with_unknown_fields_into(wire_type, is, &mut self.method, 2, &mut self.unknown_fields)?
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.address)?;
                 },
                 3 => {
-                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(wire_type, is, &mut self.headers)?;
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.connected = tmp;
                 },
                 4 => {
-                    ::protobuf::rt::read_singular_proto3_carllerche_bytes_into(wire_type, is, &mut self.body)?;
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.acked_version)?;
+                },
+                5 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.error)?;
+                },
+                6 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.status)?;
+                },
+                7 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.last_seen_ms = tmp;
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -175,15 +4224,27 @@ impl ::protobuf::Message for HttpRequest {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        if !self.url.is_empty() {
-            my_size += ::protobuf::rt::string_size(1, &self.url);
+        if !self.pod_id.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.pod_id);
         }
-        if self.method != HttpMethod::GET {
-            my_size += ::protobuf::rt::enum_size(2, self.method);
+        if !self.address.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.address);
         }
-        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(3, &self.headers);
-        if !self.body.is_empty() {
-            my_size += ::protobuf::rt::bytes_size(4, &self.body);
+        if self.connected != false {
+            my_size += 2;
+        }
+        if !self.acked_version.is_empty() {
+            my_size += ::protobuf::rt::string_size(4, &self.acked_version);
+        }
+        if !self.error.is_empty() {
+            my_size += ::protobuf::rt::string_size(5, &self.error);
+        }
+        if let Some(ref v) = self.status.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        if self.last_seen_ms != 0 {
+            my_size += ::protobuf::rt::value_size(7, self.last_seen_ms, ::protobuf::wire_format::WireTypeVarint);
         }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
@@ -191,15 +4252,28 @@ impl ::protobuf::Message for HttpRequest {
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        if !self.url.is_empty() {
-            os.write_string(1, &self.url)?;
+        if !self.pod_id.is_empty() {
+            os.write_string(1, &self.pod_id)?;
         }
-        if self.method != HttpMethod::GET {
-            os.write_enum(2, ::protobuf::ProtobufEnum::value(&self.method))?;
+        if !self.address.is_empty() {
+            os.write_string(2, &self.address)?;
         }
-        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(3, &self.headers, os)?;
-        if !self.body.is_empty() {
-            os.write_bytes(4, &self.body)?;
+        if self.connected != false {
+            os.write_bool(3, self.connected)?;
+        }
+        if !self.acked_version.is_empty() {
+            os.write_string(4, &self.acked_version)?;
+        }
+        if !self.error.is_empty() {
+            os.write_string(5, &self.error)?;
+        }
+        if let Some(ref v) = self.status.as_ref() {
+            os.write_tag(6, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        if self.last_seen_ms != 0 {
+            os.write_uint64(7, self.last_seen_ms)?;
         }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
@@ -231,8 +4305,8 @@ impl ::protobuf::Message for HttpRequest {
         Self::descriptor_static()
     }
 
-    fn new() -> HttpRequest {
-        HttpRequest::new()
+    fn new() -> PodReport {
+        PodReport::new()
     }
 
     fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
@@ -240,56 +4314,74 @@ impl ::protobuf::Message for HttpRequest {
         descriptor.get(|| {
             let mut fields = ::std::vec::Vec::new();
             fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                "url",
-                |m: &HttpRequest| { &m.url },
-                |m: &mut HttpRequest| { &mut m.url },
+                "pod_id",
+                |m: &PodReport| { &m.pod_id },
+                |m: &mut PodReport| { &mut m.pod_id },
             ));
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<HttpMethod>>(
-                "method",
-                |m: &HttpRequest| { &m.method },
-                |m: &mut HttpRequest| { &mut m.method },
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "address",
+                |m: &PodReport| { &m.address },
+                |m: &mut PodReport| { &mut m.address },
             ));
-            fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(
-                "headers",
-                |m: &HttpRequest| { &m.headers },
-                |m: &mut HttpRequest| { &mut m.headers },
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                "connected",
+                |m: &PodReport| { &m.connected },
+                |m: &mut PodReport| { &mut m.connected },
             ));
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeCarllercheBytes>(
-                "body",
-                |m: &HttpRequest| { &m.body },
-                |m: &mut HttpRequest| { &mut m.body },
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "acked_version",
+                |m: &PodReport| { &m.acked_version },
+                |m: &mut PodReport| { &mut m.acked_version },
             ));
-            ::protobuf::reflect::MessageDescriptor::new_pb_name::<HttpRequest>(
-                "HttpRequest",
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "error",
+                |m: &PodReport| { &m.error },
+                |m: &mut PodReport| { &mut m.error },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<PodStatus>>(
+                "status",
+                |m: &PodReport| { &m.status },
+                |m: &mut PodReport| { &mut m.status },
+            ));
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                "last_seen_ms",
+                |m: &PodReport| { &m.last_seen_ms },
+                |m: &mut PodReport| { &mut m.last_seen_ms },
+            ));
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<PodReport>(
+                "PodReport",
                 fields,
                 file_descriptor_proto()
             )
         })
     }
 
-    fn default_instance() -> &'static HttpRequest {
-        static instance: ::protobuf::rt::LazyV2<HttpRequest> = ::protobuf::rt::LazyV2::INIT;
-        instance.get(HttpRequest::new)
+    fn default_instance() -> &'static PodReport {
+        static instance: ::protobuf::rt::LazyV2<PodReport> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(PodReport::new)
     }
 }
 
-impl ::protobuf::Clear for HttpRequest {
+impl ::protobuf::Clear for PodReport {
     fn clear(&mut self) {
-        self.url.clear();
-        self.method = HttpMethod::GET;
-        self.headers.clear();
-        self.body.clear();
+        self.pod_id.clear();
+        self.address.clear();
+        self.connected = false;
+        self.acked_version.clear();
+        self.error.clear();
+        self.status.clear();
+        self.last_seen_ms = 0;
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::fmt::Debug for HttpRequest {
+impl ::std::fmt::Debug for PodReport {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for HttpRequest {
+impl ::protobuf::reflect::ProtobufValue for PodReport {
     fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
         ::protobuf::reflect::ReflectValueRef::Message(self)
     }
@@ -297,11 +4389,10 @@ impl ::protobuf::reflect::ProtobufValue for HttpRequest {
 
 #[derive(PartialEq,Clone,Default)]
 #[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
-pub struct HttpResponse {
+pub struct PlaneStatus {
     // message fields
-    pub status: i32,
-    pub headers: ::std::collections::HashMap<::std::string::String, ::std::string::String>,
-    pub body: ::bytes::Bytes,
+    pub version: ::std::string::String,
+    pub pods: ::protobuf::RepeatedField<PodReport>,
     // special fields
     #[cfg_attr(feature = "with-serde", serde(skip))]
     pub unknown_fields: ::protobuf::UnknownFields,
@@ -309,86 +4400,76 @@ pub struct HttpResponse {
     pub cached_size: ::protobuf::CachedSize,
 }
 
-impl<'a> ::std::default::Default for &'a HttpResponse {
-    fn default() -> &'a HttpResponse {
-        <HttpResponse as ::protobuf::Message>::default_instance()
+impl<'a> ::std::default::Default for &'a PlaneStatus {
+    fn default() -> &'a PlaneStatus {
+        <PlaneStatus as ::protobuf::Message>::default_instance()
     }
 }
 
-impl HttpResponse {
-    pub fn new() -> HttpResponse {
+impl PlaneStatus {
+    pub fn new() -> PlaneStatus {
         ::std::default::Default::default()
     }
 
-    // int32 status = 1;
-
-
-    pub fn get_status(&self) -> i32 {
-        self.status
-    }
-    pub fn clear_status(&mut self) {
-        self.status = 0;
-    }
-
-    // Param is passed by value, moved
-    pub fn set_status(&mut self, v: i32) {
-        self.status = v;
-    }
-
-    // repeated .proto.HttpResponse.HeadersEntry headers = 2;
+    // string version = 1;
 
 
-    pub fn get_headers(&self) -> &::std::collections::HashMap<::std::string::String, ::std::string::String> {
-        &self.headers
+    pub fn get_version(&self) -> &str {
+        &self.version
     }
-    pub fn clear_headers(&mut self) {
-        self.headers.clear();
+    pub fn clear_version(&mut self) {
+        self.version.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_headers(&mut self, v: ::std::collections::HashMap<::std::string::String, ::std::string::String>) {
-        self.headers = v;
+    pub fn set_version(&mut self, v: ::std::string::String) {
+        self.version = v;
     }
 
     // Mutable pointer to the field.
-    pub fn mut_headers(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, ::std::string::String> {
-        &mut self.headers
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_version(&mut self) -> &mut ::std::string::String {
+        &mut self.version
     }
 
     // Take field
-    pub fn take_headers(&mut self) -> ::std::collections::HashMap<::std::string::String, ::std::string::String> {
-        ::std::mem::replace(&mut self.headers, ::std::collections::HashMap::new())
+    pub fn take_version(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.version, ::std::string::String::new())
     }
 
-    // bytes body = 3;
+    // repeated .proto.PodReport pods = 2;
 
 
-    pub fn get_body(&self) -> &[u8] {
-        &self.body
+    pub fn get_pods(&self) -> &[PodReport] {
+        &self.pods
     }
-    pub fn clear_body(&mut self) {
-        self.body.clear();
+    pub fn clear_pods(&mut self) {
+        self.pods.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_body(&mut self, v: ::bytes::Bytes) {
-        self.body = v;
+    pub fn set_pods(&mut self, v: ::protobuf::RepeatedField<PodReport>) {
+        self.pods = v;
     }
 
     // Mutable pointer to the field.
-    // If field is not initialized, it is initialized with default value first.
-    pub fn mut_body(&mut self) -> &mut ::bytes::Bytes {
-        &mut self.body
+    pub fn mut_pods(&mut self) -> &mut ::protobuf::RepeatedField<PodReport> {
+        &mut self.pods
     }
 
     // Take field
-    pub fn take_body(&mut self) -> ::bytes::Bytes {
-        ::std::mem::replace(&mut self.body, ::bytes::Bytes::new())
+    pub fn take_pods(&mut self) -> ::protobuf::RepeatedField<PodReport> {
+        ::std::mem::replace(&mut self.pods, ::protobuf::RepeatedField::new())
     }
 }
 
-impl ::protobuf::Message for HttpResponse {
+impl ::protobuf::Message for PlaneStatus {
     fn is_initialized(&self) -> bool {
+        for v in &self.pods {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
         true
     }
 
@@ -397,17 +4478,10 @@ impl ::protobuf::Message for HttpResponse {
             let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
-                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
-                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    }
-                    let tmp = is.read_int32()?;
-                    self.status = tmp;
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.version)?;
                 },
                 2 => {
-                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(wire_type, is, &mut self.headers)?;
-                },
-                3 => {
-                    ::protobuf::rt::read_singular_proto3_carllerche_bytes_into(wire_type, is, &mut self.body)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.pods)?;
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -421,26 +4495,27 @@ impl ::protobuf::Message for HttpResponse {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        if self.status != 0 {
-            my_size += ::protobuf::rt::value_size(1, self.status, ::protobuf::wire_format::WireTypeVarint);
-        }
-        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(2, &self.headers);
-        if !self.body.is_empty() {
-            my_size += ::protobuf::rt::bytes_size(3, &self.body);
+        if !self.version.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.version);
         }
+        for value in &self.pods {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        if self.status != 0 {
-            os.write_int32(1, self.status)?;
-        }
-        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(2, &self.headers, os)?;
-        if !self.body.is_empty() {
-            os.write_bytes(3, &self.body)?;
+        if !self.version.is_empty() {
+            os.write_string(1, &self.version)?;
         }
+        for v in &self.pods {
+            os.write_tag(2, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -471,59 +4546,53 @@ impl ::protobuf::Message for HttpResponse {
         Self::descriptor_static()
     }
 
-    fn new() -> HttpResponse {
-        HttpResponse::new()
+    fn new() -> PlaneStatus {
+        PlaneStatus::new()
     }
 
     fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
         static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::LazyV2::INIT;
         descriptor.get(|| {
             let mut fields = ::std::vec::Vec::new();
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt32>(
-                "status",
-                |m: &HttpResponse| { &m.status },
-                |m: &mut HttpResponse| { &mut m.status },
-            ));
-            fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(
-                "headers",
-                |m: &HttpResponse| { &m.headers },
-                |m: &mut HttpResponse| { &mut m.headers },
+            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                "version",
+                |m: &PlaneStatus| { &m.version },
+                |m: &mut PlaneStatus| { &mut m.version },
             ));
-            fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeCarllercheBytes>(
-                "body",
-                |m: &HttpResponse| { &m.body },
-                |m: &mut HttpResponse| { &mut m.body },
+            fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<PodReport>>(
+                "pods",
+                |m: &PlaneStatus| { &m.pods },
+                |m: &mut PlaneStatus| { &mut m.pods },
             ));
-            ::protobuf::reflect::MessageDescriptor::new_pb_name::<HttpResponse>(
-                "HttpResponse",
+            ::protobuf::reflect::MessageDescriptor::new_pb_name::<PlaneStatus>(
+                "PlaneStatus",
                 fields,
                 file_descriptor_proto()
             )
         })
     }
 
-    fn default_instance() -> &'static HttpResponse {
-        static instance: ::protobuf::rt::LazyV2<HttpResponse> = ::protobuf::rt::LazyV2::INIT;
-        instance.get(HttpResponse::new)
+    fn default_instance() -> &'static PlaneStatus {
+        static instance: ::protobuf::rt::LazyV2<PlaneStatus> = ::protobuf::rt::LazyV2::INIT;
+        instance.get(PlaneStatus::new)
     }
 }
 
-impl ::protobuf::Clear for HttpResponse {
+impl ::protobuf::Clear for PlaneStatus {
     fn clear(&mut self) {
-        self.status = 0;
-        self.headers.clear();
-        self.body.clear();
+        self.version.clear();
+        self.pods.clear();
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::fmt::Debug for HttpResponse {
+impl ::std::fmt::Debug for PlaneStatus {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for HttpResponse {
+impl ::protobuf::reflect::ProtobufValue for PlaneStatus {
     fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
         ::protobuf::reflect::ReflectValueRef::Message(self)
     }
@@ -637,6 +4706,7 @@ pub enum HttpMethod {
     OPTIONS = 6,
     TRACE = 7,
     PATCH = 8,
+    EXTENSION = 9,
 }
 
 impl ::protobuf::ProtobufEnum for HttpMethod {
@@ -655,6 +4725,7 @@ impl ::protobuf::ProtobufEnum for HttpMethod {
             6 => ::std::option::Option::Some(HttpMethod::OPTIONS),
             7 => ::std::option::Option::Some(HttpMethod::TRACE),
             8 => ::std::option::Option::Some(HttpMethod::PATCH),
+            9 => ::std::option::Option::Some(HttpMethod::EXTENSION),
             _ => ::std::option::Option::None
         }
     }
@@ -670,6 +4741,7 @@ impl ::protobuf::ProtobufEnum for HttpMethod {
             HttpMethod::OPTIONS,
             HttpMethod::TRACE,
             HttpMethod::PATCH,
+            HttpMethod::EXTENSION,
         ];
         values
     }
@@ -697,23 +4769,174 @@ impl ::protobuf::reflect::ProtobufValue for HttpMethod {
     }
 }
 
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+#[cfg_attr(feature = "with-serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum ErrorCode {
+    UNKNOWN = 0,
+    INVALID_ARGUMENT = 1,
+    UNAUTHENTICATED = 2,
+    PERMISSION_DENIED = 3,
+    NOT_FOUND = 4,
+    CONFLICT = 5,
+    RESOURCE_EXHAUSTED = 6,
+    INTERNAL = 7,
+    UNIMPLEMENTED = 8,
+    UNAVAILABLE = 9,
+    DEADLINE_EXCEEDED = 10,
+    DNS_FAILURE = 20,
+    CONNECTION_REFUSED = 21,
+    TIMEOUT = 22,
+    TLS_FAILURE = 23,
+    UPSTREAM_FAILURE = 24,
+}
+
+impl ::protobuf::ProtobufEnum for ErrorCode {
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<ErrorCode> {
+        match value {
+            0 => ::std::option::Option::Some(ErrorCode::UNKNOWN),
+            1 => ::std::option::Option::Some(ErrorCode::INVALID_ARGUMENT),
+            2 => ::std::option::Option::Some(ErrorCode::UNAUTHENTICATED),
+            3 => ::std::option::Option::Some(ErrorCode::PERMISSION_DENIED),
+            4 => ::std::option::Option::Some(ErrorCode::NOT_FOUND),
+            5 => ::std::option::Option::Some(ErrorCode::CONFLICT),
+            6 => ::std::option::Option::Some(ErrorCode::RESOURCE_EXHAUSTED),
+            7 => ::std::option::Option::Some(ErrorCode::INTERNAL),
+            8 => ::std::option::Option::Some(ErrorCode::UNIMPLEMENTED),
+            9 => ::std::option::Option::Some(ErrorCode::UNAVAILABLE),
+            10 => ::std::option::Option::Some(ErrorCode::DEADLINE_EXCEEDED),
+            20 => ::std::option::Option::Some(ErrorCode::DNS_FAILURE),
+            21 => ::std::option::Option::Some(ErrorCode::CONNECTION_REFUSED),
+            22 => ::std::option::Option::Some(ErrorCode::TIMEOUT),
+            23 => ::std::option::Option::Some(ErrorCode::TLS_FAILURE),
+            24 => ::std::option::Option::Some(ErrorCode::UPSTREAM_FAILURE),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn values() -> &'static [Self] {
+        static values: &'static [ErrorCode] = &[
+            ErrorCode::UNKNOWN,
+            ErrorCode::INVALID_ARGUMENT,
+            ErrorCode::UNAUTHENTICATED,
+            ErrorCode::PERMISSION_DENIED,
+            ErrorCode::NOT_FOUND,
+            ErrorCode::CONFLICT,
+            ErrorCode::RESOURCE_EXHAUSTED,
+            ErrorCode::INTERNAL,
+            ErrorCode::UNIMPLEMENTED,
+            ErrorCode::UNAVAILABLE,
+            ErrorCode::DEADLINE_EXCEEDED,
+            ErrorCode::DNS_FAILURE,
+            ErrorCode::CONNECTION_REFUSED,
+            ErrorCode::TIMEOUT,
+            ErrorCode::TLS_FAILURE,
+            ErrorCode::UPSTREAM_FAILURE,
+        ];
+        values
+    }
+
+    fn enum_descriptor_static() -> &'static ::protobuf::reflect::EnumDescriptor {
+        static descriptor: ::protobuf::rt::LazyV2<::protobuf::reflect::EnumDescriptor> = ::protobuf::rt::LazyV2::INIT;
+        descriptor.get(|| {
+            ::protobuf::reflect::EnumDescriptor::new_pb_name::<ErrorCode>("ErrorCode", file_descriptor_proto())
+        })
+    }
+}
+
+impl ::std::marker::Copy for ErrorCode {
+}
+
+impl ::std::default::Default for ErrorCode {
+    fn default() -> Self {
+        ErrorCode::UNKNOWN
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for ErrorCode {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Enum(::protobuf::ProtobufEnum::descriptor(self))
+    }
+}
+
 static file_descriptor_proto_data: &'static [u8] = b"\
-    \n\x0bproto.proto\x12\x05proto\"\xd5\x01\n\x0bHttpRequest\x12\x10\n\x03u\
+    \n\x0bproto.proto\x12\x05proto\"\xf2\x03\n\x0bHttpRequest\x12\x10\n\x03u\
     rl\x18\x01\x20\x01(\tR\x03url\x12)\n\x06method\x18\x02\x20\x01(\x0e2\x11\
     .proto.HttpMethodR\x06method\x129\n\x07headers\x18\x03\x20\x03(\x0b2\x1f\
     .proto.HttpRequest.HeadersEntryR\x07headers\x12\x12\n\x04body\x18\x04\
-    \x20\x01(\x0cR\x04body\x1a:\n\x0cHeadersEntry\x12\x10\n\x03key\x18\x01\
-    \x20\x01(\tR\x03key\x12\x14\n\x05value\x18\x02\x20\x01(\tR\x05value:\x02\
-    8\x01\"\xb2\x01\n\x0cHttpResponse\x12\x16\n\x06status\x18\x01\x20\x01(\
-    \x05R\x06status\x12:\n\x07headers\x18\x02\x20\x03(\x0b2\x20.proto.HttpRe\
-    sponse.HeadersEntryR\x07headers\x12\x12\n\x04body\x18\x03\x20\x01(\x0cR\
-    \x04body\x1a:\n\x0cHeadersEntry\x12\x10\n\x03key\x18\x01\x20\x01(\tR\x03\
-    key\x12\x14\n\x05value\x18\x02\x20\x01(\tR\x05value:\x028\x01*\x16\n\x08\
-    VmMethod\x12\n\n\x06V_HTTP\x10\0*\x18\n\nWasmMethod\x12\n\n\x06W_HTTP\
-    \x10\0*n\n\nHttpMethod\x12\x07\n\x03GET\x10\0\x12\x08\n\x04HEAD\x10\x01\
-    \x12\x08\n\x04POST\x10\x02\x12\x07\n\x03PUT\x10\x03\x12\n\n\x06DELETE\
-    \x10\x04\x12\x0b\n\x07CONNECT\x10\x05\x12\x0b\n\x07OPTIONS\x10\x06\x12\t\
-    \n\x05TRACE\x10\x07\x12\t\n\x05PATCH\x10\x08b\x06proto3\
+    \x20\x01(\x0cR\x04body\x12#\n\rpeer_identity\x18\x05\x20\x01(\tR\x0cpeer\
+    Identity\x12\x1f\n\x0bremote_addr\x18\x06\x20\x01(\tR\nremoteAddr\x12\
+    \x18\n\x07version\x18\x07\x20\x01(\tR\x07version\x12\x16\n\x06scheme\x18\
+    \x08\x20\x01(\tR\x06scheme\x12\x10\n\x03tls\x18\t\x20\x01(\x08R\x03tls\
+    \x12\x1d\n\nrequest_id\x18\n\x20\x01(\tR\trequestId\x12\x12\n\x04path\
+    \x18\x0b\x20\x01(\tR\x04path\x12\x14\n\x05query\x18\x0c\x20\x01(\tR\x05q\
+    uery\x12)\n\x10extension_method\x18\r\x20\x01(\tR\x0fextensionMethod\x12\
+    \x1d\n\ntimeout_ms\x18\x0e\x20\x01(\rR\ttimeoutMs\x1a:\n\x0cHeadersEntry\
+    \x12\x10\n\x03key\x18\x01\x20\x01(\tR\x03key\x12\x14\n\x05value\x18\x02\
+    \x20\x01(\tR\x05value:\x028\x01\"\xb2\x01\n\x0cHttpResponse\x12\x16\n\
+    \x06status\x18\x01\x20\x01(\x05R\x06status\x12:\n\x07headers\x18\x02\x20\
+    \x03(\x0b2\x20.proto.HttpResponse.HeadersEntryR\x07headers\x12\x12\n\x04\
+    body\x18\x03\x20\x01(\x0cR\x04body\x1a:\n\x0cHeadersEntry\x12\x10\n\x03k\
+    ey\x18\x01\x20\x01(\tR\x03key\x12\x14\n\x05value\x18\x02\x20\x01(\tR\x05\
+    value:\x028\x01\"\xde\x01\n\tMeshError\x12$\n\x04code\x18\x01\x20\x01(\
+    \x0e2\x10.proto.ErrorCodeR\x04code\x12\x18\n\x07message\x18\x02\x20\x01(\
+    \tR\x07message\x12\x1c\n\tretryable\x18\x03\x20\x01(\x08R\tretryable\x12\
+    7\n\x07details\x18\x04\x20\x03(\x0b2\x1d.proto.MeshError.DetailsEntryR\
+    \x07details\x1a:\n\x0cDetailsEntry\x12\x10\n\x03key\x18\x01\x20\x01(\tR\
+    \x03key\x12\x14\n\x05value\x18\x02\x20\x01(\tR\x05value:\x028\x01\"\x8a\
+    \x02\n\nPodMessage\x120\n\tsubscribe\x18\x01\x20\x01(\x0b2\x10.proto.Sub\
+    scribeH\0R\tsubscribe\x12\x1e\n\x03ack\x18\x02\x20\x01(\x0b2\n.proto.Ack\
+    H\0R\x03ack\x12*\n\x06status\x18\x03\x20\x01(\x0b2\x10.proto.PodStatusH\
+    \0R\x06status\x12=\n\x0estatus_request\x18\x04\x20\x01(\x0b2\x14.proto.S\
+    tatusRequestH\0R\rstatusRequest\x127\n\x0cfetch_module\x18\x05\x20\x01(\
+    \x0b2\x12.proto.FetchModuleH\0R\x0bfetchModuleB\x06\n\x04kind\"\x9c\x01\
+    \n\x0cPlaneMessage\x12-\n\x06update\x18\x01\x20\x01(\x0b2\x13.proto.Conf\
+    igUpdateH\0R\x06update\x12,\n\x06status\x18\x02\x20\x01(\x0b2\x12.proto.\
+    PlaneStatusH\0R\x06status\x12'\n\x06module\x18\x03\x20\x01(\x0b2\r.proto\
+    .ModuleH\0R\x06moduleB\x06\n\x04kind\"<\n\tSubscribe\x12\x15\n\x06pod_id\
+    \x18\x01\x20\x01(\tR\x05podId\x12\x18\n\x07version\x18\x02\x20\x01(\tR\
+    \x07version\"H\n\x08Resource\x12\x12\n\x04name\x18\x01\x20\x01(\tR\x04na\
+    me\x12\x12\n\x04json\x18\x02\x20\x01(\tR\x04json\x12\x14\n\x05order\x18\
+    \x03\x20\x01(\rR\x05order\"\x9b\x01\n\x0cConfigUpdate\x12\x18\n\x07versi\
+    on\x18\x01\x20\x01(\tR\x07version\x12\x14\n\x05nonce\x18\x02\x20\x01(\tR\
+    \x05nonce\x12-\n\tresources\x18\x03\x20\x03(\x0b2\x0f.proto.ResourceR\tr\
+    esources\x12\x18\n\x07removed\x18\x04\x20\x03(\tR\x07removed\x12\x12\n\
+    \x04full\x18\x05\x20\x01(\x08R\x04full\"K\n\x03Ack\x12\x18\n\x07version\
+    \x18\x01\x20\x01(\tR\x07version\x12\x14\n\x05nonce\x18\x02\x20\x01(\tR\
+    \x05nonce\x12\x14\n\x05error\x18\x03\x20\x01(\tR\x05error\"\x8b\x01\n\tP\
+    odStatus\x12\x18\n\x07version\x18\x01\x20\x01(\tR\x07version\x12\x14\n\
+    \x05error\x18\x02\x20\x01(\tR\x05error\x12\x1a\n\x08services\x18\x03\x20\
+    \x01(\rR\x08services\x12\x1a\n\x08requests\x18\x04\x20\x01(\x04R\x08requ\
+    ests\x12\x16\n\x06errors\x18\x05\x20\x01(\x04R\x06errors\"\x0f\n\rStatus\
+    Request\"%\n\x0bFetchModule\x12\x16\n\x06digest\x18\x01\x20\x01(\tR\x06d\
+    igest\"h\n\x06Module\x12\x16\n\x06digest\x18\x01\x20\x01(\tR\x06digest\
+    \x12\x12\n\x04wasm\x18\x02\x20\x01(\x0cR\x04wasm\x12\x14\n\x05error\x18\
+    \x03\x20\x01(\tR\x05error\x12\x1c\n\tsignature\x18\x04\x20\x01(\x0cR\tsi\
+    gnature\"\xe1\x01\n\tPodReport\x12\x15\n\x06pod_id\x18\x01\x20\x01(\tR\
+    \x05podId\x12\x18\n\x07address\x18\x02\x20\x01(\tR\x07address\x12\x1c\n\
+    \tconnected\x18\x03\x20\x01(\x08R\tconnected\x12#\n\racked_version\x18\
+    \x04\x20\x01(\tR\x0cackedVersion\x12\x14\n\x05error\x18\x05\x20\x01(\tR\
+    \x05error\x12(\n\x06status\x18\x06\x20\x01(\x0b2\x10.proto.PodStatusR\
+    \x06status\x12\x20\n\x0clast_seen_ms\x18\x07\x20\x01(\x04R\nlastSeenMs\"\
+    M\n\x0bPlaneStatus\x12\x18\n\x07version\x18\x01\x20\x01(\tR\x07version\
+    \x12$\n\x04pods\x18\x02\x20\x03(\x0b2\x10.proto.PodReportR\x04pods*\x16\
+    \n\x08VmMethod\x12\n\n\x06V_HTTP\x10\0*\x18\n\nWasmMethod\x12\n\n\x06W_H\
+    TTP\x10\0*}\n\nHttpMethod\x12\x07\n\x03GET\x10\0\x12\x08\n\x04HEAD\x10\
+    \x01\x12\x08\n\x04POST\x10\x02\x12\x07\n\x03PUT\x10\x03\x12\n\n\x06DELET\
+    E\x10\x04\x12\x0b\n\x07CONNECT\x10\x05\x12\x0b\n\x07OPTIONS\x10\x06\x12\
+    \t\n\x05TRACE\x10\x07\x12\t\n\x05PATCH\x10\x08\x12\r\n\tEXTENSION\x10\t*\
+    \xb5\x02\n\tErrorCode\x12\x0b\n\x07UNKNOWN\x10\0\x12\x14\n\x10INVALID_AR\
+    GUMENT\x10\x01\x12\x13\n\x0fUNAUTHENTICATED\x10\x02\x12\x15\n\x11PERMISS\
+    ION_DENIED\x10\x03\x12\r\n\tNOT_FOUND\x10\x04\x12\x0c\n\x08CONFLICT\x10\
+    \x05\x12\x16\n\x12RESOURCE_EXHAUSTED\x10\x06\x12\x0c\n\x08INTERNAL\x10\
+    \x07\x12\x11\n\rUNIMPLEMENTED\x10\x08\x12\x0f\n\x0bUNAVAILABLE\x10\t\x12\
+    \x15\n\x11DEADLINE_EXCEEDED\x10\n\x12\x0f\n\x0bDNS_FAILURE\x10\x14\x12\
+    \x16\n\x12CONNECTION_REFUSED\x10\x15\x12\x0b\n\x07TIMEOUT\x10\x16\x12\
+    \x0f\n\x0bTLS_FAILURE\x10\x17\x12\x14\n\x10UPSTREAM_FAILURE\x10\x18b\x06\
+    proto3\
 ";
 
 static file_descriptor_proto_lazy: ::protobuf::rt::LazyV2<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::rt::LazyV2::INIT;
@@ -0,0 +1,79 @@
+//! Framing of the control plane RPC transport: every message is its length as
+//! a 4-byte big-endian integer, followed by its protobuf encoding. The frames
+//! go over plain TCP, or mutual TLS when both ends have an identity.
+
+use std::io::{self, Read, Write};
+
+use protobuf::Message;
+
+/// Largest frame accepted, a bigger length means a broken stream. Frames
+/// carry whole modules, see `FetchModule`.
+pub const MAX_FRAME_LEN: usize = 64 << 20;
+
+/// Byte stream the frames go over.
+pub trait Stream: Read + Write + Send {}
+
+impl<T: Read + Write + Send> Stream for T {}
+
+/// Writes a message as one frame and flushes the writer.
+pub fn write_frame<M: Message, W: Write>(w: &mut W, msg: &M) -> io::Result<()> {
+    let bytes = msg.write_to_bytes().map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    if bytes.len() > MAX_FRAME_LEN {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("frame of {} bytes is too large", bytes.len())));
+    }
+    w.write_all(&(bytes.len() as u32).to_be_bytes())?;
+    w.write_all(&bytes)?;
+    w.flush()
+}
+
+/// Reads the next frame, `UnexpectedEof` means the peer closed the stream.
+pub fn read_frame<M: Message, R: Read>(r: &mut R) -> io::Result<M> {
+    let mut len = [0u8; 4];
+    r.read_exact(&mut len)?;
+    let len = u32::from_be_bytes(len) as usize;
+    if len > MAX_FRAME_LEN {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!("frame of {} bytes is too large", len)));
+    }
+    let mut bytes = vec![0u8; len];
+    r.read_exact(&mut bytes)?;
+    M::parse_from_bytes(&bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Reads frames from a stream with a read timeout, keeping a partly read
+/// frame over timeouts, so one thread can read a stream and write to it.
+#[derive(Debug, Default)]
+pub struct FrameReader {
+    buf: Vec<u8>,
+}
+
+impl FrameReader {
+    pub fn new() -> Self {
+        FrameReader::default()
+    }
+
+    /// Reads the next frame, `None` when the read timed out before a whole
+    /// frame came. `UnexpectedEof` means the peer closed the stream.
+    pub fn read<M: Message, R: Read>(&mut self, r: &mut R) -> io::Result<Option<M>> {
+        let mut chunk = [0u8; 8192];
+        loop {
+            if self.buf.len() >= 4 {
+                let len = u32::from_be_bytes([self.buf[0], self.buf[1], self.buf[2], self.buf[3]]) as usize;
+                if len > MAX_FRAME_LEN {
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, format!("frame of {} bytes is too large", len)));
+                }
+                if self.buf.len() >= 4 + len {
+                    let msg = M::parse_from_bytes(&self.buf[4..4 + len]).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e));
+                    self.buf.drain(..4 + len);
+                    return msg.map(Some);
+                }
+            }
+            match r.read(&mut chunk) {
+                Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+                Ok(n) => self.buf.extend_from_slice(&chunk[..n]),
+                Err(e) if matches!(e.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) => return Ok(None),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
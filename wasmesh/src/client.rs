@@ -0,0 +1,179 @@
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use wasmesh_proto::*;
+
+/// Outbound HTTP request, sent by the pod on behalf of the guest.
+///
+/// ```ignore
+/// let order: Order = Request::get("http://orders/v1/orders/1")
+///     .header("accept", "application/json")
+///     .timeout(Duration::from_secs(2))
+///     .send(&ctx)?
+///     .error_for_status()?
+///     .json()?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct Request {
+    req: HttpRequest,
+    error: Option<MeshError>,
+}
+
+impl Request {
+    pub fn new(method: &str, url: &str) -> Self {
+        let mut req = HttpRequest::new();
+        req.set_url(url.to_string());
+        let error = req.set_method_str(method).err()
+                       .map(|e| MeshError::with_code(ErrorCode::INVALID_ARGUMENT, e).with_detail("method", method));
+        Request { req, error }
+    }
+    pub fn get(url: &str) -> Self {
+        Self::new("GET", url)
+    }
+    pub fn post(url: &str) -> Self {
+        Self::new("POST", url)
+    }
+    pub fn put(url: &str) -> Self {
+        Self::new("PUT", url)
+    }
+    pub fn patch(url: &str) -> Self {
+        Self::new("PATCH", url)
+    }
+    pub fn delete(url: &str) -> Self {
+        Self::new("DELETE", url)
+    }
+    pub fn header<K: ToString, V: ToString>(mut self, name: K, value: V) -> Self {
+        self.req.headers.insert(name.to_string().to_ascii_lowercase(), value.to_string());
+        self
+    }
+    /// Appends the serialized value to the query string of the url.
+    pub fn query<T: Serialize + ?Sized>(mut self, query: &T) -> Self {
+        match serde_urlencoded::to_string(query) {
+            Ok(q) if q.is_empty() => {}
+            Ok(q) => {
+                let sep = if self.req.url.contains('?') { '&' } else { '?' };
+                self.req.url = format!("{}{}{}", self.req.url, sep, q);
+            }
+            Err(e) => self.fail(format!("invalid query: {}", e)),
+        }
+        self
+    }
+    pub fn body<B: Into<Bytes>>(mut self, body: B) -> Self {
+        self.req.set_body(body.into());
+        self
+    }
+    /// Sends the value as a JSON body.
+    pub fn json<T: Serialize + ?Sized>(mut self, body: &T) -> Self {
+        match serde_json::to_vec(body) {
+            Ok(body) => self = self.header("content-type", "application/json").body(body),
+            Err(e) => self.fail(format!("invalid JSON body: {}", e)),
+        }
+        self
+    }
+    /// Sends the value as a form-urlencoded body.
+    pub fn form<T: Serialize + ?Sized>(mut self, body: &T) -> Self {
+        match serde_urlencoded::to_string(body) {
+            Ok(body) => self = self.header("content-type", "application/x-www-form-urlencoded").body(body),
+            Err(e) => self.fail(format!("invalid form body: {}", e)),
+        }
+        self
+    }
+    /// Calls a mesh peer over mutual TLS; the server must prove the workload
+    /// identity, e.g. `spiffe://wasmesh/ns/shop/module/orders`. Needs an `https` url.
+    pub fn peer(mut self, identity: &str) -> Self {
+        self.req.set_peer_identity(identity.to_string());
+        self
+    }
+    /// Limits the whole call, which fails with `ErrorCode::TIMEOUT` when exceeded.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.req.set_timeout_ms(timeout.as_millis().clamp(1, u32::MAX as u128) as u32);
+        self
+    }
+    /// Returns the request that would be sent.
+    pub fn into_request(self) -> std::result::Result<HttpRequest, MeshError> {
+        match self.error {
+            Some(e) => Err(e),
+            None => Ok(self.req),
+        }
+    }
+    /// Sends the request through the pod.
+    ///
+    /// Error statuses are returned as responses, see [`ResponseExt::error_for_status`].
+    pub fn send(self, ctx: &Ctx) -> std::result::Result<HttpResponse, MeshError> {
+        let req = self.into_request()?;
+        #[cfg(not(feature = "mock"))]
+        let r = ctx.call_host(Method::from(VmMethod::V_HTTP), &req);
+        #[cfg(feature = "mock")]
+        let r = { let _ = ctx; crate::mock::http(&req) };
+        r.map_err(|e| MeshError::from_code_msg(&e))
+    }
+
+    fn fail(&mut self, message: String) {
+        if self.error.is_none() {
+            self.error = Some(MeshError::with_code(ErrorCode::INVALID_ARGUMENT, message));
+        }
+    }
+}
+
+/// Helpers on `HttpResponse`.
+pub trait ResponseExt: Sized {
+    /// Returns the value of a header, ignoring the case of its name.
+    fn header(&self, name: &str) -> Option<&str>;
+    /// Returns the body as UTF-8 text.
+    fn text(&self) -> std::result::Result<String, MeshError>;
+    /// Deserializes the JSON body.
+    fn json<T: DeserializeOwned>(&self) -> std::result::Result<T, MeshError>;
+    /// Turns 4xx and 5xx responses into `ErrorCode::UPSTREAM_FAILURE`.
+    fn error_for_status(self) -> std::result::Result<Self, MeshError>;
+}
+
+impl ResponseExt for HttpResponse {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(k, _)| k.eq_ignore_ascii_case(name)).map(|(_, v)| v.as_str())
+    }
+    fn text(&self) -> std::result::Result<String, MeshError> {
+        String::from_utf8(self.body.to_vec())
+            .map_err(|e| MeshError::with_code(ErrorCode::UPSTREAM_FAILURE, format!("response body is not UTF-8: {}", e)))
+    }
+    fn json<T: DeserializeOwned>(&self) -> std::result::Result<T, MeshError> {
+        serde_json::from_slice(&self.body)
+            .map_err(|e| MeshError::with_code(ErrorCode::UPSTREAM_FAILURE, format!("invalid JSON in response: {}", e)))
+    }
+    fn error_for_status(self) -> std::result::Result<Self, MeshError> {
+        if self.status < 400 {
+            return Ok(self);
+        }
+        Err(MeshError::with_code(ErrorCode::UPSTREAM_FAILURE, format!("upstream responded with status {}", self.status))
+            .with_retryable(self.status >= 500 || self.status == 429)
+            .with_detail("status", self.status))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_request() {
+        let req = Request::post("http://orders/v1/orders?dry_run=1")
+            .header("X-Trace", "abc")
+            .query(&[("page", "2")])
+            .json(&serde_json::json!({"item": "tea"}))
+            .timeout(Duration::from_millis(1500))
+            .peer("spiffe://wasmesh/ns/shop/module/orders")
+            .into_request()
+            .unwrap();
+        assert_eq!(req.method_str(), "POST");
+        assert_eq!(req.url, "http://orders/v1/orders?dry_run=1&page=2");
+        assert_eq!(req.headers["x-trace"], "abc");
+        assert_eq!(req.headers["content-type"], "application/json");
+        assert_eq!(req.body.as_ref(), br#"{"item":"tea"}"#);
+        assert_eq!(req.timeout_ms, 1500);
+        assert_eq!(req.peer_identity, "spiffe://wasmesh/ns/shop/module/orders");
+
+        let e = Request::new("BAD METHOD", "http://orders/").into_request().unwrap_err();
+        assert_eq!(e.get_code(), ErrorCode::INVALID_ARGUMENT);
+    }
+}
@@ -0,0 +1,332 @@
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use wasmesh_proto::*;
+
+const APPLICATION_JSON: &str = "application/json";
+const APPLICATION_FORM: &str = "application/x-www-form-urlencoded";
+
+/// Types that can be built from a request, e.g. `Json<T>`, `Form<T>` or `Query<T>`.
+pub trait FromRequest: Sized {
+    fn from_request(req: &HttpRequest) -> std::result::Result<Self, Rejection>;
+}
+
+/// Types that can be turned into a response.
+pub trait IntoResponse {
+    fn into_response(self) -> HttpResponse;
+}
+
+/// Helpers on `HttpRequest`.
+pub trait RequestExt {
+    /// Returns the value of a header, ignoring the case of its name.
+    fn header(&self, name: &str) -> Option<&str>;
+    /// Returns the media type of the body without parameters, in lower case.
+    fn content_type(&self) -> Option<String>;
+    /// Returns the verified workload identity of the caller, e.g.
+    /// `spiffe://wasmesh/ns/shop/module/billing`, when it connected over mutual TLS.
+    fn caller(&self) -> Option<&str>;
+    fn extract<T: FromRequest>(&self) -> std::result::Result<T, Rejection>;
+}
+
+impl RequestExt for HttpRequest {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(k, _)| k.eq_ignore_ascii_case(name)).map(|(_, v)| v.as_str())
+    }
+    fn content_type(&self) -> Option<String> {
+        self.header("content-type").map(media_type)
+    }
+    fn caller(&self) -> Option<&str> {
+        Some(self.get_peer_identity()).filter(|peer| !peer.is_empty())
+    }
+    fn extract<T: FromRequest>(&self) -> std::result::Result<T, Rejection> {
+        T::from_request(self)
+    }
+}
+
+/// Why a request could not be extracted.
+///
+/// It is answered with its status, also when returned from a handler through `?`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rejection {
+    pub status: u16,
+    pub message: String,
+}
+
+impl Rejection {
+    pub fn bad_request<S: ToString>(message: S) -> Self {
+        Rejection { status: 400, message: message.to_string() }
+    }
+    pub fn unsupported_media_type(expected: &str) -> Self {
+        Rejection { status: 415, message: format!("expected content-type {}", expected) }
+    }
+    pub fn not_acceptable() -> Self {
+        Rejection {
+            status: 406,
+            message: format!("no acceptable representation, available: {}, {}", APPLICATION_JSON, APPLICATION_FORM),
+        }
+    }
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.status, self.message)
+    }
+}
+
+impl std::error::Error for Rejection {}
+
+impl IntoResponse for Rejection {
+    fn into_response(self) -> HttpResponse {
+        let body = serde_json::json!({
+            "type": "urn:wasmesh:error:invalid_argument",
+            "status": self.status,
+            "code": "INVALID_ARGUMENT",
+            "detail": self.message,
+        });
+        bytes_response(self.status, "application/problem+json", serde_json::to_vec(&body).unwrap_or_default())
+    }
+}
+
+impl From<Rejection> for MeshError {
+    fn from(r: Rejection) -> Self {
+        MeshError::with_code(ErrorCode::INVALID_ARGUMENT, r.message).with_detail(HTTP_STATUS_DETAIL, r.status)
+    }
+}
+
+impl From<Rejection> for CodeMsg {
+    fn from(r: Rejection) -> Self {
+        MeshError::from(r).into()
+    }
+}
+
+/// JSON body, extracted from `application/json` (or `*+json`) requests and
+/// serialized as `application/json` when responded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Json<T>(pub T);
+
+impl<T: DeserializeOwned> FromRequest for Json<T> {
+    fn from_request(req: &HttpRequest) -> std::result::Result<Self, Rejection> {
+        match req.content_type() {
+            Some(ct) if is_json(&ct) => {}
+            _ => return Err(Rejection::unsupported_media_type(APPLICATION_JSON)),
+        }
+        serde_json::from_slice(&req.body)
+            .map(Json)
+            .map_err(|e| Rejection::bad_request(format!("invalid JSON body: {}", e)))
+    }
+}
+
+impl<T: Serialize> IntoResponse for Json<T> {
+    fn into_response(self) -> HttpResponse {
+        match serde_json::to_vec(&self.0) {
+            Ok(body) => bytes_response(200, APPLICATION_JSON, body),
+            Err(e) => bytes_response(500, "text/plain; charset=utf-8", format!("failed to serialize JSON: {}", e)),
+        }
+    }
+}
+
+/// Form body, extracted from `application/x-www-form-urlencoded` requests.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Form<T>(pub T);
+
+impl<T: DeserializeOwned> FromRequest for Form<T> {
+    fn from_request(req: &HttpRequest) -> std::result::Result<Self, Rejection> {
+        if req.content_type().as_deref() != Some(APPLICATION_FORM) {
+            return Err(Rejection::unsupported_media_type(APPLICATION_FORM));
+        }
+        serde_urlencoded::from_bytes(&req.body)
+            .map(Form)
+            .map_err(|e| Rejection::bad_request(format!("invalid form body: {}", e)))
+    }
+}
+
+impl<T: Serialize> IntoResponse for Form<T> {
+    fn into_response(self) -> HttpResponse {
+        match serde_urlencoded::to_string(&self.0) {
+            Ok(body) => bytes_response(200, APPLICATION_FORM, body),
+            Err(e) => bytes_response(500, "text/plain; charset=utf-8", format!("failed to serialize form: {}", e)),
+        }
+    }
+}
+
+/// Query string of the url.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Query<T>(pub T);
+
+impl<T: DeserializeOwned> FromRequest for Query<T> {
+    fn from_request(req: &HttpRequest) -> std::result::Result<Self, Rejection> {
+        let query = if req.query.is_empty() {
+            req.url.split_once('?').map_or("", |(_, q)| q.split('#').next().unwrap_or_default())
+        } else {
+            &req.query
+        };
+        serde_urlencoded::from_str(query)
+            .map(Query)
+            .map_err(|e| Rejection::bad_request(format!("invalid query string: {}", e)))
+    }
+}
+
+/// Body in either JSON or form encoding, chosen by the request's content-type.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Payload<T>(pub T);
+
+impl<T: DeserializeOwned> FromRequest for Payload<T> {
+    fn from_request(req: &HttpRequest) -> std::result::Result<Self, Rejection> {
+        match req.content_type() {
+            Some(ct) if is_json(&ct) => Json::from_request(req).map(|Json(v)| Payload(v)),
+            Some(ct) if ct == APPLICATION_FORM => Form::from_request(req).map(|Form(v)| Payload(v)),
+            _ => Err(Rejection::unsupported_media_type(&format!("{} or {}", APPLICATION_JSON, APPLICATION_FORM))),
+        }
+    }
+}
+
+/// Serializes `value` in the representation the request's `Accept` header
+/// prefers: JSON, or form-urlencoded, otherwise 406.
+///
+/// Without an `Accept` header JSON is used.
+pub fn negotiate<T: Serialize>(req: &HttpRequest, value: T) -> HttpResponse {
+    let accept = match req.header("accept") {
+        Some(accept) if !accept.trim().is_empty() => accept,
+        _ => return Json(value).into_response(),
+    };
+    let mut best: Option<(f32, bool)> = None;
+    for range in accept.split(',') {
+        let mut params = range.split(';');
+        let media = params.next().unwrap_or_default().trim().to_ascii_lowercase();
+        let q = params
+            .filter_map(|p| p.trim().strip_prefix("q="))
+            .find_map(|q| q.trim().parse::<f32>().ok())
+            .unwrap_or(1.0);
+        let json = match media.as_str() {
+            "*/*" | "application/*" => true,
+            m if is_json(m) => true,
+            APPLICATION_FORM => false,
+            _ => continue,
+        };
+        if q > 0.0 && best.is_none_or(|(bq, _)| q > bq) {
+            best = Some((q, json));
+        }
+    }
+    match best {
+        Some((_, true)) => Json(value).into_response(),
+        Some((_, false)) => Form(value).into_response(),
+        None => Rejection::not_acceptable().into_response(),
+    }
+}
+
+impl IntoResponse for HttpResponse {
+    fn into_response(self) -> HttpResponse {
+        self
+    }
+}
+
+impl IntoResponse for String {
+    fn into_response(self) -> HttpResponse {
+        bytes_response(200, "text/plain; charset=utf-8", self)
+    }
+}
+
+impl IntoResponse for &'static str {
+    fn into_response(self) -> HttpResponse {
+        bytes_response(200, "text/plain; charset=utf-8", self)
+    }
+}
+
+/// Overrides the status of the response.
+impl<R: IntoResponse> IntoResponse for (u16, R) {
+    fn into_response(self) -> HttpResponse {
+        let mut resp = self.1.into_response();
+        resp.set_status(self.0 as i32);
+        resp
+    }
+}
+
+impl<R: IntoResponse, E: IntoResponse> IntoResponse for std::result::Result<R, E> {
+    fn into_response(self) -> HttpResponse {
+        match self {
+            Ok(r) => r.into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+}
+
+fn bytes_response<B: Into<Bytes>>(status: u16, content_type: &str, body: B) -> HttpResponse {
+    let mut resp = HttpResponse::new();
+    resp.set_status(status as i32);
+    resp.headers.insert("content-type".to_string(), content_type.to_string());
+    resp.set_body(body.into());
+    resp
+}
+
+fn media_type(value: &str) -> String {
+    value.split(';').next().unwrap_or_default().trim().to_ascii_lowercase()
+}
+
+fn is_json(media: &str) -> bool {
+    media == APPLICATION_JSON || (media.starts_with("application/") && media.ends_with("+json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Deserialize, Serialize, Debug, PartialEq)]
+    struct Order {
+        id: u32,
+        item: String,
+    }
+
+    fn request(content_type: &str, body: &'static str) -> HttpRequest {
+        let mut req = HttpRequest::new();
+        req.headers.insert("content-type".to_string(), content_type.to_string());
+        req.set_body(Bytes::from(body));
+        req
+    }
+
+    #[test]
+    fn extract_bodies() {
+        let order = Order { id: 1, item: "tea".to_string() };
+        let Json(got) = request("application/json; charset=utf-8", r#"{"id":1,"item":"tea"}"#).extract::<Json<Order>>().unwrap();
+        assert_eq!(got, order);
+        let Payload(got) = request("application/x-www-form-urlencoded", "id=1&item=tea").extract::<Payload<Order>>().unwrap();
+        assert_eq!(got, order);
+
+        let r = request("application/json", r#"{"id":"x"}"#).extract::<Json<Order>>().unwrap_err();
+        assert_eq!(r.status, 400);
+        let r = request("text/plain", "id=1").extract::<Json<Order>>().unwrap_err();
+        assert_eq!(r.status, 415);
+        assert_eq!(r.clone().into_response().status, 415);
+        let e = MeshError::from_code_msg(&CodeMsg::from(r));
+        assert_eq!((e.get_code(), e.http_status()), (ErrorCode::INVALID_ARGUMENT, 415));
+
+        let mut req = HttpRequest::new();
+        req.set_url("/orders?id=1&item=tea".to_string());
+        let Query(got) = req.extract::<Query<Order>>().unwrap();
+        assert_eq!(got, order);
+    }
+
+    #[test]
+    fn negotiate_accept() {
+        let order = Order { id: 1, item: "tea".to_string() };
+        let mut req = HttpRequest::new();
+        assert_eq!(negotiate(&req, &order).headers["content-type"], APPLICATION_JSON);
+        req.headers.insert("accept".to_string(), "application/json;q=0.5, application/x-www-form-urlencoded".to_string());
+        let resp = negotiate(&req, &order);
+        assert_eq!(resp.headers["content-type"], APPLICATION_FORM);
+        assert_eq!(resp.body.as_ref(), b"id=1&item=tea");
+        req.headers.insert("accept".to_string(), "text/html".to_string());
+        assert_eq!(negotiate(&req, &order).status, 406);
+    }
+
+    #[test]
+    fn caller_identity() {
+        let mut req = HttpRequest::new();
+        assert_eq!(req.caller(), None);
+        req.set_peer_identity("spiffe://wasmesh/ns/shop/module/billing".to_string());
+        assert_eq!(req.caller(), Some("spiffe://wasmesh/ns/shop/module/billing"));
+    }
+}
@@ -1,5 +1,19 @@
-use wasmesh_proto::*;
+pub use client::*;
+pub use extract::*;
+pub use log::*;
+pub use middleware::*;
+#[cfg(feature = "mock")]
+pub use mock::MockHost;
+pub use wasmesh_proto::*;
 
+mod client;
+mod extract;
+mod log;
+mod middleware;
+#[cfg(feature = "mock")]
+pub mod mock;
+
+#[cfg(not(feature = "mock"))]
 #[wasm_handler(0)]
 fn handle_http(ctx: Ctx, req: HttpRequest) -> Result<HttpResponse> {
     todo!()
@@ -0,0 +1,35 @@
+use std::fmt;
+
+use wasmesh_proto::Ctx;
+
+/// Severity of a log record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+        })
+    }
+}
+
+/// Writes a log record to the guest's stderr, which the pod forwards.
+///
+/// With the `mock` feature records go to the installed `MockHost`.
+#[allow(unused_variables)]
+pub fn log<M: fmt::Display>(ctx: &Ctx, level: Level, message: M) {
+    #[cfg(feature = "mock")]
+    if crate::mock::log(level, &message) {
+        return;
+    }
+    eprintln!("[{}] {}", level, message);
+}
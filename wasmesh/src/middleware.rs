@@ -0,0 +1,347 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use base64::Engine;
+
+use wasmesh_proto::*;
+
+use crate::RequestExt;
+
+/// A step around the handler: it may change the request, answer it itself,
+/// or call `next` and change the response.
+pub trait Middleware {
+    fn handle(&self, ctx: &Ctx, req: HttpRequest, next: Next<'_>) -> Result<HttpResponse>;
+}
+
+/// The rest of the chain after a middleware.
+pub struct Next<'a> {
+    middlewares: &'a [Box<dyn Middleware>],
+    handler: &'a dyn Fn(&Ctx, HttpRequest) -> Result<HttpResponse>,
+}
+
+impl Next<'_> {
+    pub fn run(self, ctx: &Ctx, req: HttpRequest) -> Result<HttpResponse> {
+        match self.middlewares.split_first() {
+            Some((first, rest)) => first.handle(ctx, req, Next { middlewares: rest, handler: self.handler }),
+            None => (self.handler)(ctx, req),
+        }
+    }
+}
+
+/// Middlewares run in the order they were added, outermost first.
+///
+/// ```ignore
+/// #[wasm_handler(0)]
+/// fn handle_http(ctx: Ctx, req: HttpRequest) -> Result<HttpResponse> {
+///     Chain::new()
+///         .with(CatchPanic)
+///         .with(RequestId)
+///         .with(Cors::permissive())
+///         .handle(&ctx, req, route)
+/// }
+/// ```
+#[derive(Default)]
+pub struct Chain {
+    middlewares: Vec<Box<dyn Middleware>>,
+}
+
+impl Chain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn with<M: Middleware + 'static>(mut self, middleware: M) -> Self {
+        self.middlewares.push(Box::new(middleware));
+        self
+    }
+    /// Adds a hook run before the handler, returning a response skips the rest of the chain.
+    pub fn before<F>(self, hook: F) -> Self
+        where F: Fn(&Ctx, &mut HttpRequest) -> Option<HttpResponse> + 'static {
+        self.with(Before(hook))
+    }
+    /// Adds a hook run on the response of the rest of the chain.
+    pub fn after<F>(self, hook: F) -> Self
+        where F: Fn(&Ctx, &mut HttpResponse) + 'static {
+        self.with(After(hook))
+    }
+    pub fn handle<H>(&self, ctx: &Ctx, req: HttpRequest, handler: H) -> Result<HttpResponse>
+        where H: Fn(&Ctx, HttpRequest) -> Result<HttpResponse> {
+        Next { middlewares: &self.middlewares, handler: &handler }.run(ctx, req)
+    }
+}
+
+struct Before<F>(F);
+
+impl<F> Middleware for Before<F>
+    where F: Fn(&Ctx, &mut HttpRequest) -> Option<HttpResponse> {
+    fn handle(&self, ctx: &Ctx, mut req: HttpRequest, next: Next<'_>) -> Result<HttpResponse> {
+        match (self.0)(ctx, &mut req) {
+            Some(resp) => Ok(resp),
+            None => next.run(ctx, req),
+        }
+    }
+}
+
+struct After<F>(F);
+
+impl<F> Middleware for After<F>
+    where F: Fn(&Ctx, &mut HttpResponse) {
+    fn handle(&self, ctx: &Ctx, req: HttpRequest, next: Next<'_>) -> Result<HttpResponse> {
+        let mut resp = next.run(ctx, req)?;
+        (self.0)(ctx, &mut resp);
+        Ok(resp)
+    }
+}
+
+/// Answers CORS preflight requests and adds `Access-Control-Allow-*` headers
+/// to responses for allowed origins. Responses vary by `Origin`.
+#[derive(Debug, Clone)]
+pub struct Cors {
+    origins: Vec<String>,
+    methods: String,
+    headers: String,
+    max_age: u32,
+    credentials: bool,
+}
+
+impl Cors {
+    /// Allows the given origins, e.g. `https://shop.example.com`, `*` allows any.
+    pub fn new<S: ToString>(origins: &[S]) -> Self {
+        Cors {
+            origins: origins.iter().map(|o| o.to_string()).collect(),
+            methods: "GET, HEAD, POST, PUT, PATCH, DELETE, OPTIONS".to_string(),
+            headers: "*".to_string(),
+            max_age: 600,
+            credentials: false,
+        }
+    }
+    /// Allows any origin.
+    pub fn permissive() -> Self {
+        Self::new(&["*"])
+    }
+    pub fn methods(mut self, methods: &[&str]) -> Self {
+        self.methods = methods.join(", ");
+        self
+    }
+    pub fn headers(mut self, headers: &[&str]) -> Self {
+        self.headers = headers.join(", ");
+        self
+    }
+    pub fn max_age(mut self, secs: u32) -> Self {
+        self.max_age = secs;
+        self
+    }
+    /// Allows credentials, only for the origins listed by name: an origin
+    /// allowed through `*` never gets them.
+    pub fn credentials(mut self, allow: bool) -> Self {
+        self.credentials = allow;
+        self
+    }
+    fn allow_origin(&self, origin: &str) -> Option<String> {
+        if self.origins.iter().any(|o| o == origin) {
+            Some(origin.to_string())
+        } else if self.origins.iter().any(|o| o == "*") {
+            Some("*".to_string())
+        } else {
+            None
+        }
+    }
+}
+
+impl Middleware for Cors {
+    fn handle(&self, ctx: &Ctx, req: HttpRequest, next: Next<'_>) -> Result<HttpResponse> {
+        let origin = match req.header("origin") {
+            Some(origin) => origin.to_string(),
+            None => return next.run(ctx, req).map(vary_origin),
+        };
+        let allowed = self.allow_origin(&origin);
+        let preflight = req.get_method() == HttpMethod::OPTIONS && req.header("access-control-request-method").is_some();
+        let mut resp = if preflight {
+            let mut resp = HttpResponse::new();
+            resp.set_status(204);
+            if allowed.is_some() {
+                resp.headers.insert("access-control-allow-methods".to_string(), self.methods.clone());
+                resp.headers.insert("access-control-allow-headers".to_string(), self.headers.clone());
+                resp.headers.insert("access-control-max-age".to_string(), self.max_age.to_string());
+            }
+            resp
+        } else {
+            next.run(ctx, req)?
+        };
+        if let Some(allowed) = allowed {
+            if self.credentials && allowed != "*" {
+                resp.headers.insert("access-control-allow-credentials".to_string(), "true".to_string());
+            }
+            resp.headers.insert("access-control-allow-origin".to_string(), allowed);
+        }
+        Ok(vary_origin(resp))
+    }
+}
+
+fn vary_origin(mut resp: HttpResponse) -> HttpResponse {
+    let vary = resp.headers.entry("vary".to_string()).or_default();
+    if !vary.split(',').any(|v| v.trim().eq_ignore_ascii_case("origin")) {
+        if !vary.is_empty() {
+            vary.push_str(", ");
+        }
+        vary.push_str("Origin");
+    }
+    resp
+}
+
+/// Makes sure the request has an id and echoes it in the `x-request-id` response header.
+///
+/// The pod assigns ids to the requests it receives; this covers requests
+/// built by the guest itself, e.g. in tests.
+pub struct RequestId;
+
+impl Middleware for RequestId {
+    fn handle(&self, ctx: &Ctx, mut req: HttpRequest, next: Next<'_>) -> Result<HttpResponse> {
+        if req.request_id.is_empty() {
+            let id = req.header(REQUEST_ID_HEADER).map_or_else(new_request_id, str::to_string);
+            req.set_request_id(id);
+        }
+        let id = req.request_id.clone();
+        let mut resp = next.run(ctx, req)?;
+        resp.headers.entry(REQUEST_ID_HEADER.to_string()).or_insert(id);
+        Ok(resp)
+    }
+}
+
+fn new_request_id() -> String {
+    static SEQ: AtomicU64 = AtomicU64::new(0);
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos() as u64;
+    format!("{:016x}{:016x}", nanos, SEQ.fetch_add(1, Ordering::Relaxed))
+}
+
+/// Requires HTTP basic credentials accepted by `check`, otherwise answers 401.
+pub struct BasicAuth<F> {
+    realm: String,
+    check: F,
+}
+
+impl<F: Fn(&str, &str) -> bool> BasicAuth<F> {
+    /// `check` is called with the user and password.
+    pub fn new(realm: &str, check: F) -> Self {
+        BasicAuth { realm: realm.to_string(), check }
+    }
+    fn credentials(req: &HttpRequest) -> Option<(String, String)> {
+        let value = req.header("authorization")?;
+        let (scheme, encoded) = value.trim().split_once(' ')?;
+        if !scheme.eq_ignore_ascii_case("basic") {
+            return None;
+        }
+        let decoded = base64::engine::general_purpose::STANDARD.decode(encoded.trim()).ok()?;
+        let (user, password) = String::from_utf8(decoded).ok()?.split_once(':').map(|(u, p)| (u.to_string(), p.to_string()))?;
+        Some((user, password))
+    }
+}
+
+impl<F: Fn(&str, &str) -> bool> Middleware for BasicAuth<F> {
+    fn handle(&self, ctx: &Ctx, req: HttpRequest, next: Next<'_>) -> Result<HttpResponse> {
+        match Self::credentials(&req) {
+            Some((user, password)) if (self.check)(&user, &password) => next.run(ctx, req),
+            _ => {
+                let mut resp = HttpResponse::new();
+                resp.set_status(401);
+                resp.headers.insert("www-authenticate".to_string(), format!("Basic realm=\"{}\", charset=\"UTF-8\"", self.realm));
+                resp.set_body(Bytes::from_static(b"unauthorized"));
+                Ok(resp)
+            }
+        }
+    }
+}
+
+/// Answers 500 when the rest of the chain panics.
+///
+/// Panics only unwind when the guest is built with `panic = "unwind"`; with
+/// the default `abort` of wasm targets the pod answers the trap instead.
+pub struct CatchPanic;
+
+impl Middleware for CatchPanic {
+    fn handle(&self, ctx: &Ctx, req: HttpRequest, next: Next<'_>) -> Result<HttpResponse> {
+        let request_id = req.request_id.clone();
+        panic::catch_unwind(AssertUnwindSafe(|| next.run(ctx, req))).unwrap_or_else(|payload| {
+            let message = payload.downcast_ref::<&str>().map(|s| s.to_string())
+                                 .or_else(|| payload.downcast_ref::<String>().cloned())
+                                 .unwrap_or_else(|| "panic".to_string());
+            Err(MeshError::with_code(ErrorCode::INTERNAL, format!("handler panicked: {}", message))
+                .with_detail("request_id", request_id)
+                .into())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ok(_: &Ctx, req: HttpRequest) -> Result<HttpResponse> {
+        let mut resp = HttpResponse::new();
+        resp.set_body(Bytes::from(req.request_id));
+        Ok(resp)
+    }
+
+    #[test]
+    fn chain_order_and_short_circuit() {
+        let ctx = Ctx::default();
+        let chain = Chain::new()
+            .with(RequestId)
+            .after(|_, resp| { resp.headers.insert("x-after".to_string(), "1".to_string()); })
+            .before(|_, req| (req.path == "/blocked").then(|| {
+                let mut resp = HttpResponse::new();
+                resp.set_status(418);
+                resp
+            }));
+
+        let mut req = HttpRequest::new();
+        req.set_request_id("abc".to_string());
+        let resp = chain.handle(&ctx, req, ok).unwrap();
+        assert_eq!(resp.body.as_ref(), b"abc");
+        assert_eq!(resp.headers["x-request-id"], "abc");
+        assert_eq!(resp.headers["x-after"], "1");
+
+        let mut req = HttpRequest::new();
+        req.set_path("/blocked".to_string());
+        let resp = chain.handle(&ctx, req, ok).unwrap();
+        assert_eq!(resp.status, 418);
+        assert_eq!(resp.headers["x-after"], "1");
+        assert!(!resp.headers["x-request-id"].is_empty());
+    }
+
+    #[test]
+    fn builtins() {
+        let ctx = Ctx::default();
+        let chain = Chain::new()
+            .with(CatchPanic)
+            .with(Cors::new(&["https://shop.example.com"]))
+            .with(BasicAuth::new("shop", |user, password| user == "admin" && password == "secret"));
+
+        let mut preflight = HttpRequest::new();
+        preflight.set_method(HttpMethod::OPTIONS);
+        preflight.headers.insert("origin".to_string(), "https://shop.example.com".to_string());
+        preflight.headers.insert("access-control-request-method".to_string(), "PUT".to_string());
+        let resp = chain.handle(&ctx, preflight, ok).unwrap();
+        assert_eq!(resp.status, 204);
+        assert_eq!(resp.headers["access-control-allow-origin"], "https://shop.example.com");
+        assert_eq!(resp.headers["vary"], "Origin");
+
+        // a wildcard never hands out credentials to the origin asking
+        let cors = Chain::new().with(Cors::permissive().credentials(true));
+        let mut req = HttpRequest::new();
+        req.headers.insert("origin".to_string(), "https://evil.example.com".to_string());
+        let resp = cors.handle(&ctx, req, ok).unwrap();
+        assert_eq!(resp.headers["access-control-allow-origin"], "*");
+        assert!(!resp.headers.contains_key("access-control-allow-credentials"));
+        let resp = cors.handle(&ctx, HttpRequest::new(), ok).unwrap();
+        assert_eq!(resp.headers["vary"], "Origin");
+
+        let resp = chain.handle(&ctx, HttpRequest::new(), ok).unwrap();
+        assert_eq!(resp.status, 401);
+
+        let mut req = HttpRequest::new();
+        req.headers.insert("authorization".to_string(), "Basic YWRtaW46c2VjcmV0".to_string());
+        let e = chain.handle(&ctx, req, |_, _| panic!("boom")).unwrap_err();
+        assert_eq!(MeshError::from_code_msg(&e).get_code(), ErrorCode::INTERNAL);
+    }
+}
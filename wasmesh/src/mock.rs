@@ -0,0 +1,137 @@
+//! Scripted host for running handlers natively with `cargo test`.
+//!
+//! ```ignore
+//! let host = MockHost::install();
+//! host.on_http(|req| req.url.starts_with("http://inventory/").then(|| Ok(json(200, r#"{"in_stock":true}"#))));
+//! let resp = create_order(&host.ctx(), order_request()).unwrap();
+//! assert_eq!(resp.status, 201);
+//! assert_eq!(host.http_requests().len(), 1);
+//! assert!(host.logs().iter().any(|(level, msg)| *level == Level::Info && msg.contains("created")));
+//! ```
+//!
+//! The pod offers guests outbound HTTP only, so that is the host call to script.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+use wasmesh_proto::*;
+
+use crate::Level;
+
+type HttpScript = Box<dyn Fn(&HttpRequest) -> Option<std::result::Result<HttpResponse, MeshError>>>;
+
+thread_local! {
+    static HOST: RefCell<Option<Rc<MockHost>>> = const { RefCell::new(None) };
+}
+
+/// Fake host answering the host calls made on the thread it is installed on.
+///
+/// Outbound requests no script answers fail with `ErrorCode::UNAVAILABLE`.
+#[derive(Default)]
+pub struct MockHost {
+    http: RefCell<Vec<HttpScript>>,
+    http_requests: RefCell<Vec<HttpRequest>>,
+    logs: RefCell<Vec<(Level, String)>>,
+}
+
+impl MockHost {
+    /// Installs a new host for the current thread, replacing any previous one.
+    pub fn install() -> Rc<MockHost> {
+        let host = Rc::new(MockHost::default());
+        HOST.with(|h| *h.borrow_mut() = Some(host.clone()));
+        host
+    }
+    /// Context to pass to handlers.
+    pub fn ctx(&self) -> Ctx {
+        Ctx::default()
+    }
+    /// Adds a script for outbound requests, the first one returning `Some` answers.
+    pub fn on_http<F>(&self, script: F)
+        where F: Fn(&HttpRequest) -> Option<std::result::Result<HttpResponse, MeshError>> + 'static {
+        self.http.borrow_mut().push(Box::new(script));
+    }
+    /// Answers requests with `method` whose url starts with `prefix`.
+    pub fn expect_http(&self, method: &str, prefix: &str, resp: HttpResponse) {
+        let (method, prefix) = (method.to_string(), prefix.to_string());
+        self.on_http(move |req| (req.method_str() == method && req.url.starts_with(&prefix)).then(|| Ok(resp.clone())));
+    }
+    /// Outbound requests made so far, in order.
+    pub fn http_requests(&self) -> Vec<HttpRequest> {
+        self.http_requests.borrow().clone()
+    }
+    /// Log records written so far, in order.
+    pub fn logs(&self) -> Vec<(Level, String)> {
+        self.logs.borrow().clone()
+    }
+    fn http(&self, req: &HttpRequest) -> std::result::Result<HttpResponse, MeshError> {
+        self.http_requests.borrow_mut().push(req.clone());
+        self.http.borrow().iter()
+            .find_map(|script| script(req))
+            .unwrap_or_else(|| Err(MeshError::with_code(ErrorCode::UNAVAILABLE, format!("no mock for {} {}", req.method_str(), req.url))))
+    }
+}
+
+fn current() -> Rc<MockHost> {
+    HOST.with(|h| h.borrow().clone())
+        .expect("no MockHost installed on this thread, call MockHost::install() first")
+}
+
+pub(crate) fn http(req: &HttpRequest) -> Result<HttpResponse> {
+    current().http(req).map_err(Into::into)
+}
+
+pub(crate) fn log(level: Level, message: &dyn fmt::Display) -> bool {
+    match HOST.with(|h| h.borrow().clone()) {
+        Some(host) => {
+            host.logs.borrow_mut().push((level, message.to_string()));
+            true
+        }
+        None => false,
+    }
+}
+
+/// Builds a JSON response for scripts.
+pub fn json(status: i32, body: &str) -> HttpResponse {
+    let mut resp = HttpResponse::new();
+    resp.set_status(status);
+    resp.headers.insert("content-type".to_string(), "application/json".to_string());
+    resp.set_body(Bytes::from(body.to_string()));
+    resp
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use crate::{log, Request, ResponseExt};
+
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct Stock {
+        in_stock: bool,
+    }
+
+    fn handler(ctx: &Ctx, _req: HttpRequest) -> Result<HttpResponse> {
+        let stock: Stock = Request::get("http://inventory/items/tea").send(ctx)?.error_for_status()?.json()?;
+        log(ctx, Level::Info, format!("in stock: {}", stock.in_stock));
+        let mut resp = HttpResponse::new();
+        resp.set_status(if stock.in_stock { 201 } else { 409 });
+        Ok(resp)
+    }
+
+    #[test]
+    fn scripted_host() {
+        let host = MockHost::install();
+        host.expect_http("GET", "http://inventory/", json(200, r#"{"in_stock":true}"#));
+        let resp = handler(&host.ctx(), HttpRequest::new()).unwrap();
+        assert_eq!(resp.status, 201);
+        assert_eq!(host.http_requests()[0].url, "http://inventory/items/tea");
+        assert_eq!(host.logs(), vec![(Level::Info, "in stock: true".to_string())]);
+
+        let host = MockHost::install();
+        let e = handler(&host.ctx(), HttpRequest::new()).unwrap_err();
+        assert_eq!(MeshError::from_code_msg(&e).get_code(), ErrorCode::UNAVAILABLE);
+    }
+}